@@ -0,0 +1,102 @@
+//! Compares the average number of steps each march method takes on the default scene.
+//!
+//! Run with `cargo bench --bench march_steps --no-default-features`. The camera is turned through
+//! the same rotation as the interactive demo so the tesseract's edges are seen from many angles.
+
+use std::time::{Duration, Instant};
+
+use raymarching::application::Application;
+use raymarching::camera::{Camera, Camera4};
+use raymarching::mathematics::bivector4::Bivector4;
+use raymarching::mathematics::float2::Float2;
+use raymarching::mathematics::float4::Float4;
+use raymarching::mathematics::multivectors::Rotor;
+use raymarching::mathematics::rotor4::Rotor4;
+use raymarching::raymarching::{raymarch, Hit, MarchMethod, RaymarchSettings};
+use raymarching::scene::{Scene, SubScene4};
+
+const FRAMES: u32 = 8;
+
+struct MethodResult
+{
+    steps: u64,
+    hit_steps: u64,
+    rays: u64,
+    hits: u64,
+    /// Rays that hit or missed differently to sphere tracing
+    disagreements: u64,
+    time: Duration,
+}
+
+fn main()
+{
+    let application: Application = Application::new(16.0 / 9.0, 480, false);
+    let mut scene: Scene = Scene::new_4d(
+        Camera4::new(Float4::new(0.0, 0.0, 0.0, 0.0), Float4::new(0.0, 0.0, 4.0, 0.0), Rotor4::IDENTITY, application.aspect_ratio, 70.0, 1.0),
+        Float4::new(2.0, 2.0, 4.0, 0.0)
+    );
+
+    let methods: [MarchMethod; 3] = [MarchMethod::Sphere, MarchMethod::OverRelaxed(1.6), MarchMethod::Accelerated(0.9)];
+    let mut results: Vec<MethodResult> = methods.iter().map(|_| MethodResult{
+        steps: 0, hit_steps: 0, rays: 0, hits: 0, disagreements: 0, time: Duration::ZERO
+    }).collect();
+
+    let rotation: Rotor4 = Rotor4::bivector_angle(&Bivector4::new(1.0, 1.0, 1.0, 1.0, 1.0, 1.0), 0.4);
+    for _frame in 0..FRAMES
+    {
+        let scene_4d: &SubScene4 = scene.scene_4d.as_ref().unwrap();
+        let ro: Float4 = scene_4d.camera.get_camera_position();
+        let rays: Vec<Float4> = (0..application.height)
+            .flat_map(|y| (0..application.width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let uv: Float2 = Float2::new(x as f32 / (application.width - 1) as f32, y as f32 / (application.height - 1) as f32);
+                return scene_4d.camera.get_ray_direction(uv);
+            })
+            .collect();
+
+        let mut reference: Vec<bool> = Vec::new();
+        for (method, result) in methods.iter().zip(results.iter_mut())
+        {
+            let settings: RaymarchSettings = RaymarchSettings{ method: *method, ..RaymarchSettings::DEFAULT };
+            let start: Instant = Instant::now();
+            let hits: Vec<Hit<Float4>> = rays.iter().map(|rd| raymarch(&ro, rd, scene_4d, &settings)).collect();
+            result.time += start.elapsed();
+
+            if reference.is_empty()
+            {
+                reference = hits.iter().map(|hit| hit.hit).collect();
+            }
+            for (hit, reference_hit) in hits.iter().zip(&reference)
+            {
+                result.steps += hit.steps as u64;
+                result.rays += 1;
+                if hit.hit
+                {
+                    result.hits += 1;
+                    result.hit_steps += hit.steps as u64;
+                }
+                if hit.hit != *reference_hit
+                {
+                    result.disagreements += 1;
+                }
+            }
+        }
+
+        scene.scene_4d.as_mut().unwrap().camera.rotate_camera(rotation);
+    }
+
+    println!("{} frames of {}x{} on the default tesseract scene", FRAMES, application.width, application.height);
+    println!("{:<22} {:>10} {:>14} {:>8} {:>14} {:>10}", "method", "avg steps", "avg hit steps", "hits", "disagreements", "time");
+    for (method, result) in methods.iter().zip(&results)
+    {
+        println!(
+            "{:<22} {:>10.2} {:>14.2} {:>8} {:>14} {:>8.0}ms",
+            format!("{:?}", method),
+            result.steps as f64 / result.rays as f64,
+            result.hit_steps as f64 / u64::max(result.hits, 1) as f64,
+            result.hits,
+            result.disagreements,
+            result.time.as_secs_f64() * 1000.0
+        );
+    }
+}
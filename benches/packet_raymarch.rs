@@ -0,0 +1,83 @@
+//! Compares marching rays one at a time with marching them in packets of eight.
+//!
+//! Run with `cargo bench --bench packet_raymarch --no-default-features`. Both use a single thread
+//! and the same rays, packets are made of eight neighbouring pixels on a row like the renderer.
+
+use std::time::{Duration, Instant};
+
+use raymarching::application::Application;
+use raymarching::camera::{Camera, Camera4};
+use raymarching::mathematics::bivector4::Bivector4;
+use raymarching::mathematics::f32x8::LANES;
+use raymarching::mathematics::float2::Float2;
+use raymarching::mathematics::float4::Float4;
+use raymarching::mathematics::float4x8::Float4x8;
+use raymarching::mathematics::multivectors::{Rotor, VectorPacket};
+use raymarching::mathematics::rotor4::Rotor4;
+use raymarching::raymarching::{raymarch, raymarch_packet, Hit, RaymarchSettings};
+use raymarching::scene::{Scene, SubScene4};
+
+const FRAMES: u32 = 8;
+
+fn main()
+{
+    let application: Application = Application::new(16.0 / 9.0, 480, false);
+    let mut scene: Scene = Scene::new_4d(
+        Camera4::new(Float4::new(0.0, 0.0, 0.0, 0.0), Float4::new(0.0, 0.0, 4.0, 0.0), Rotor4::IDENTITY, application.aspect_ratio, 70.0, 1.0),
+        Float4::new(2.0, 2.0, 4.0, 0.0)
+    );
+    let settings: RaymarchSettings = RaymarchSettings::DEFAULT;
+
+    let mut scalar_time: Duration = Duration::ZERO;
+    let mut packet_time: Duration = Duration::ZERO;
+    let mut rays: u64 = 0;
+    let mut disagreements: u64 = 0;
+
+    let rotation: Rotor4 = Rotor4::bivector_angle(&Bivector4::new(1.0, 1.0, 1.0, 1.0, 1.0, 1.0), 0.4);
+    for _frame in 0..FRAMES
+    {
+        let scene_4d: &SubScene4 = scene.scene_4d.as_ref().unwrap();
+        let ro: Float4 = scene_4d.camera.get_camera_position();
+        // The width is a multiple of the packet size so every packet is full
+        let directions: Vec<Float4> = (0..application.height)
+            .flat_map(|y| (0..application.width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let uv: Float2 = Float2::new(x as f32 / (application.width - 1) as f32, y as f32 / (application.height - 1) as f32);
+                return scene_4d.camera.get_ray_direction(uv);
+            })
+            .collect();
+
+        let start: Instant = Instant::now();
+        let scalar_hits: Vec<Hit<Float4>> = directions.iter().map(|rd| raymarch(&ro, rd, scene_4d, &settings)).collect();
+        scalar_time += start.elapsed();
+
+        let start: Instant = Instant::now();
+        let ro_packet: Float4x8 = Float4x8::splat(ro);
+        let packet_hits: Vec<Hit<Float4>> = directions.chunks_exact(LANES)
+            .flat_map(|chunk| {
+                let rd: Float4x8 = Float4x8::from_lanes(std::array::from_fn(|i| chunk[i]));
+                return raymarch_packet(&ro_packet, &rd, scene_4d, &settings);
+            })
+            .collect();
+        packet_time += start.elapsed();
+
+        for (scalar, packet) in scalar_hits.iter().zip(&packet_hits)
+        {
+            rays += 1;
+            if scalar.hit != packet.hit || scalar.steps != packet.steps
+            {
+                disagreements += 1;
+            }
+        }
+
+        scene.scene_4d.as_mut().unwrap().camera.rotate_camera(rotation);
+    }
+
+    println!("{} frames of {}x{} on the default tesseract scene", FRAMES, application.width, application.height);
+    println!("{:<10} {:>10} {:>14}", "march", "time", "rays per ms");
+    for (name, time) in [("scalar", scalar_time), ("packet", packet_time)]
+    {
+        println!("{:<10} {:>8.0}ms {:>14.0}", name, time.as_secs_f64() * 1000.0, rays as f64 / (time.as_secs_f64() * 1000.0));
+    }
+    println!("{} of {} rays hit differently or took a different number of steps", disagreements, rays);
+}
@@ -0,0 +1,284 @@
+//! Anti aliasing sample patterns and adaptive supersampling.
+//!
+//! Supersampling every pixel multiplies the render time by the number of samples, even though only
+//! pixels on edges change. Adaptive anti aliasing renders the image with one sample per pixel first,
+//! then supersamples only the pixels whose colour, depth or object differs from a neighbour.
+
+use std::sync::{Arc, RwLock};
+
+use crate::application::Application;
+use crate::framebuffer::Framebuffer;
+use crate::mathematics::f32x8::LANES;
+use crate::mathematics::float2::Float2;
+use crate::mathematics::float3::Float3;
+use crate::renderer::{self, RaySample};
+use crate::scene::Scene;
+use crate::worker_pool::{Tile, WorkerPool, TILE_SIZE};
+
+/// Where the samples of a pixel are placed
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SamplePattern
+{
+    /// A square grid reaching the edges of the pixel
+    Grid,
+    /// A square grid rotated so no two samples share a row or column, wrapped into the pixel
+    RotatedGrid,
+    /// One random sample in each cell of a square grid, different for every pixel
+    Stratified,
+    /// The Halton sequence in bases 2 and 3, any number of samples spread evenly
+    Halton,
+}
+
+impl SamplePattern
+{
+    pub const NAMES: &'static str = "grid, rotated, stratified or halton";
+
+    pub fn from_name(name: &str) -> Option<SamplePattern>
+    {
+        return match name
+        {
+            "grid" => Some(SamplePattern::Grid),
+            "rotated" => Some(SamplePattern::RotatedGrid),
+            "stratified" => Some(SamplePattern::Stratified),
+            "halton" => Some(SamplePattern::Halton),
+            _ => None,
+        };
+    }
+
+    /// Samples actually taken when asked for samples, patterns built on a grid round to a square
+    pub fn sample_count(&self, samples: u32) -> u32
+    {
+        return match self
+        {
+            SamplePattern::Halton => u32::max(samples, 1),
+            _ => grid_side(samples) * grid_side(samples),
+        };
+    }
+
+    /// Offset from the centre of pixel (x, y) of its index'th sample, in pixels
+    pub fn offset(&self, index: u32, samples: u32, x: u32, y: u32) -> Float2
+    {
+        let side: u32 = grid_side(samples);
+        let (column, row): (f32, f32) = ((index % side) as f32, (index / side) as f32);
+        return match self
+        {
+            SamplePattern::Grid =>
+            {
+                if side == 1
+                {
+                    return Float2::new(0.0, 0.0);
+                }
+                let spacing: f32 = 1.0 / (side - 1) as f32;
+                Float2::new(0.5 - column * spacing, 0.5 - row * spacing)
+            }
+            SamplePattern::RotatedGrid =>
+            {
+                // Rotating by atan(1/2) and stretching by its secant turns the 2x2 grid into the classic rotated grid
+                let cell: Float2 = Float2::new((column + 0.5) / side as f32 - 0.5, (row + 0.5) / side as f32 - 0.5);
+                let rotated: Float2 = Float2::new(cell.x - 0.5 * cell.y, 0.5 * cell.x + cell.y);
+                Float2::new(rotated.x - rotated.x.round(), rotated.y - rotated.y.round())
+            }
+            SamplePattern::Stratified =>
+            {
+                let jitter: Float2 = Float2::new(hash(x, y, 2 * index), hash(x, y, 2 * index + 1));
+                Float2::new((column + jitter.x) / side as f32 - 0.5, (row + jitter.y) / side as f32 - 0.5)
+            }
+            SamplePattern::Halton => Float2::new(halton(index + 1, 2) - 0.5, halton(index + 1, 3) - 0.5),
+        };
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AntiAliasing
+{
+    pub pattern: SamplePattern,
+    /// Samples per anti aliased pixel, see `SamplePattern::sample_count`
+    pub samples: u32,
+    /// Only supersample pixels on edges rather than every pixel
+    pub adaptive: bool,
+    /// Largest difference in any colour channel between neighbours that is not an edge
+    pub colour_threshold: f32,
+    /// Largest difference in depth between neighbours that is not an edge, as a fraction of the nearer depth
+    pub depth_threshold: f32,
+}
+
+impl AntiAliasing
+{
+    pub const DEFAULT: AntiAliasing = AntiAliasing{
+        pattern: SamplePattern::Grid,
+        samples: 9,
+        adaptive: true,
+        colour_threshold: 0.1,
+        depth_threshold: 0.1,
+    };
+
+    /// Average colour of each pixel over every sample of the pattern, the pixels are traced together
+    pub fn supersample(&self, pixels: &[(u32, u32)], scene: &Scene, application: &Application) -> [Float3; LANES]
+    {
+        let count: u32 = self.pattern.sample_count(self.samples);
+        let mut colours: [Float3; LANES] = [Float3::new(0.0, 0.0, 0.0); LANES];
+        for index in 0..count
+        {
+            let samples: [RaySample; LANES] = trace_pixels(pixels, |x, y| self.pattern.offset(index, self.samples, x, y), scene, application);
+            for (colour, sample) in colours.iter_mut().zip(samples)
+            {
+                *colour += sample.colour;
+            }
+        }
+        return colours.map(|colour| colour / count as f32);
+    }
+
+    /// Whether two neighbouring samples differ enough for the pixels between them to need anti aliasing
+    pub fn is_edge_between(&self, a: &RaySample, b: &RaySample) -> bool
+    {
+        if a.object_id != b.object_id || a.depth.is_finite() != b.depth.is_finite()
+        {
+            return true;
+        }
+        if a.depth.is_finite() && f32::abs(a.depth - b.depth) > self.depth_threshold * f32::min(a.depth, b.depth)
+        {
+            return true;
+        }
+
+        let difference: Float3 = a.colour - b.colour;
+        let contrast: f32 = f32::max(f32::abs(difference.x), f32::max(f32::abs(difference.y), f32::abs(difference.z)));
+        return contrast > self.colour_threshold;
+    }
+
+    /// Whether pixel (x, y) of a one sample image differs from any pixel beside it
+    pub fn is_edge(&self, first_pass: &[RaySample], application: &Application, x: u32, y: u32) -> bool
+    {
+        let width: usize = application.width as usize;
+        let centre: &RaySample = &first_pass[y as usize * width + x as usize];
+        let neighbours: [(i64, i64); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+        return neighbours.iter().any(|(dx, dy)| {
+            let (nx, ny): (i64, i64) = (x as i64 + dx, y as i64 + dy);
+            if nx < 0 || ny < 0 || nx >= application.width as i64 || ny >= application.height as i64
+            {
+                return false;
+            }
+            return self.is_edge_between(centre, &first_pass[ny as usize * width + nx as usize]);
+        });
+    }
+}
+
+impl Default for AntiAliasing
+{
+    fn default() -> Self
+    {
+        return AntiAliasing::DEFAULT;
+    }
+}
+
+/// Renders the image with one sample per pixel, then supersamples the pixels on edges
+pub fn render_adaptive(pool: &WorkerPool, scene: &Arc<RwLock<Scene>>, application: &Application, anti_aliasing: &AntiAliasing, framebuffer: &Arc<Framebuffer>)
+{
+    let application: Application = *application;
+    let anti_aliasing: AntiAliasing = *anti_aliasing;
+    let tiles: Vec<Tile> = Tile::split(application.width, application.height, TILE_SIZE);
+
+    let first_scene: Arc<RwLock<Scene>> = scene.clone();
+    let results: Vec<(Tile, Vec<RaySample>)> = pool.run_tiles(tiles.clone(), move |tile| {
+        let scene = first_scene.read().expect("Scene lock was poisoned");
+        let mut samples: Vec<RaySample> = Vec::with_capacity((tile.width * tile.height) as usize);
+        for packet in tile_pixels(tile).chunks(LANES)
+        {
+            let packet_samples: [RaySample; LANES] = trace_pixels(packet, |_, _| Float2::new(0.0, 0.0), &scene, &application);
+            samples.extend_from_slice(&packet_samples[..packet.len()]);
+        }
+        return samples;
+    });
+
+    // Gather the tiles into one image so edges can be found across tile borders
+    let mut first_pass: Vec<RaySample> = vec![RaySample::MISS; (application.width * application.height) as usize];
+    for (tile, samples) in results
+    {
+        for ((x, y), sample) in tile_pixels(&tile).into_iter().zip(samples)
+        {
+            first_pass[(y * application.width + x) as usize] = sample;
+        }
+    }
+    let first_pass: Arc<Vec<RaySample>> = Arc::new(first_pass);
+
+    let scene: Arc<RwLock<Scene>> = scene.clone();
+    let framebuffer: Arc<Framebuffer> = framebuffer.clone();
+    pool.run_tiles(tiles, move |tile| {
+        let scene = scene.read().expect("Scene lock was poisoned");
+        let mut edges: Vec<(u32, u32)> = Vec::new();
+        for (x, y) in tile_pixels(tile)
+        {
+            if anti_aliasing.is_edge(&first_pass, &application, x, y)
+            {
+                edges.push((x, y));
+            }
+            else
+            {
+                // Framebuffer rows count down from the top
+                framebuffer.set(x, application.height - 1 - y, first_pass[(y * application.width + x) as usize].colour);
+            }
+        }
+
+        for packet in edges.chunks(LANES)
+        {
+            let colours: [Float3; LANES] = anti_aliasing.supersample(packet, &scene, &application);
+            for (&(x, y), colour) in packet.iter().zip(colours)
+            {
+                framebuffer.set(x, application.height - 1 - y, colour);
+            }
+        }
+    });
+}
+
+/// Ray samples of up to LANES pixels each moved by its own offset, short packets repeat their last pixel
+fn trace_pixels(pixels: &[(u32, u32)], offset: impl Fn(u32, u32) -> Float2, scene: &Scene, application: &Application) -> [RaySample; LANES]
+{
+    let uvs: [Float2; LANES] = std::array::from_fn(|i| {
+        let (x, y): (u32, u32) = pixels[usize::min(i, pixels.len() - 1)];
+        let offset: Float2 = offset(x, y);
+        return renderer::pixel_uv(x as f32 + offset.x, y as f32 + offset.y, application);
+    });
+    return renderer::get_packet_samples(&uvs, scene);
+}
+
+/// Every pixel of a tile a row at a time, y counts up from the bottom
+fn tile_pixels(tile: &Tile) -> Vec<(u32, u32)>
+{
+    return (tile.y..tile.y + tile.height).flat_map(|y| (tile.x..tile.x + tile.width).map(move |x| (x, y))).collect();
+}
+
+/// Samples along each side of the square grid closest to the requested number of samples
+fn grid_side(samples: u32) -> u32
+{
+    return u32::max((samples as f32).sqrt().round() as u32, 1);
+}
+
+/// The index'th number of the Halton sequence in a prime base, evenly spread over 0 to 1
+pub fn halton(index: u32, base: u32) -> f32
+{
+    let mut result: f32 = 0.0;
+    let mut fraction: f32 = 1.0;
+    let mut i: u32 = index;
+    while i > 0
+    {
+        fraction /= base as f32;
+        result += fraction * (i % base) as f32;
+        i /= base;
+    }
+    return result;
+}
+
+/// A number from 0 to 1 that looks random but is the same every time for the same pixel and sample
+fn hash(x: u32, y: u32, index: u32) -> f32
+{
+    let mut h: u32 = x.wrapping_mul(0x8da6b343) ^ y.wrapping_mul(0xd8163841) ^ index.wrapping_mul(0xcb1ab31f);
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x7feb352d);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x846ca68b);
+    h ^= h >> 16;
+    return (h >> 8) as f32 / (1u32 << 24) as f32;
+}
+
+#[cfg(test)]
+#[path = "anti_aliasing_tests.rs"]
+mod tests;
@@ -0,0 +1,148 @@
+use std::sync::{Arc, RwLock};
+
+use crate::anti_aliasing::*;
+use crate::application::Application;
+use crate::camera::{Camera, Camera4};
+use crate::framebuffer::Framebuffer;
+use crate::mathematics::approx_equal;
+use crate::mathematics::float2::Float2;
+use crate::mathematics::float3::Float3;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::rotor4::Rotor4;
+use crate::renderer::{self, RaySample};
+use crate::scene::Scene;
+use crate::worker_pool::WorkerPool;
+
+const PATTERNS: [SamplePattern; 4] = [SamplePattern::Grid, SamplePattern::RotatedGrid, SamplePattern::Stratified, SamplePattern::Halton];
+
+#[test]
+fn grid_reaches_the_pixel_edges()
+{
+    let grid: SamplePattern = SamplePattern::Grid;
+    assert_eq!(grid.sample_count(9), 9);
+    assert_eq!(grid.offset(0, 9, 0, 0), Float2::new(0.5, 0.5));
+    assert_eq!(grid.offset(4, 9, 0, 0), Float2::new(0.0, 0.0));
+    assert_eq!(grid.offset(8, 9, 0, 0), Float2::new(-0.5, -0.5));
+    assert_eq!(grid.offset(0, 1, 0, 0), Float2::new(0.0, 0.0));
+}
+
+#[test]
+fn grid_patterns_round_to_a_square()
+{
+    assert_eq!(SamplePattern::Grid.sample_count(10), 9);
+    assert_eq!(SamplePattern::RotatedGrid.sample_count(3), 4);
+    assert_eq!(SamplePattern::Stratified.sample_count(0), 1);
+    assert_eq!(SamplePattern::Halton.sample_count(7), 7);
+}
+
+#[test]
+fn rotated_grid_shares_no_rows_or_columns()
+{
+    let offsets: Vec<Float2> = (0..4).map(|index| SamplePattern::RotatedGrid.offset(index, 4, 0, 0)).collect();
+    assert!(offsets.contains(&Float2::new(-0.125, -0.375)));
+    assert!(offsets.contains(&Float2::new(0.375, -0.125)));
+    for (i, a) in offsets.iter().enumerate()
+    {
+        for b in &offsets[i + 1..]
+        {
+            assert!(a.x != b.x && a.y != b.y, "{:?} and {:?} line up", a, b);
+        }
+    }
+}
+
+#[test]
+fn samples_stay_inside_the_pixel()
+{
+    for pattern in PATTERNS
+    {
+        for samples in [1, 4, 9, 16, 25]
+        {
+            for index in 0..pattern.sample_count(samples)
+            {
+                let offset: Float2 = pattern.offset(index, samples, 17, 3);
+                assert!(f32::abs(offset.x) <= 0.5 && f32::abs(offset.y) <= 0.5, "{:?} {} {:?}", pattern, samples, offset);
+            }
+        }
+    }
+}
+
+#[test]
+fn stratified_samples_jitter_within_their_cell()
+{
+    let stratified: SamplePattern = SamplePattern::Stratified;
+    for index in 0..16
+    {
+        let offset: Float2 = stratified.offset(index, 16, 5, 9);
+        let cell: (u32, u32) = (((offset.x + 0.5) * 4.0) as u32, ((offset.y + 0.5) * 4.0) as u32);
+        assert_eq!(cell, (index % 4, index / 4));
+    }
+
+    // The same every time for a pixel, but not the same for every pixel
+    assert_eq!(stratified.offset(3, 16, 5, 9), stratified.offset(3, 16, 5, 9));
+    assert!(stratified.offset(3, 16, 5, 9) != stratified.offset(3, 16, 6, 9));
+}
+
+#[test]
+fn halton_sequence()
+{
+    assert!(approx_equal(halton(1, 2), 0.5));
+    assert!(approx_equal(halton(2, 2), 0.25));
+    assert!(approx_equal(halton(3, 2), 0.75));
+    assert!(approx_equal(halton(1, 3), 1.0 / 3.0));
+    assert!(approx_equal(halton(4, 3), 1.0 / 3.0 + 1.0 / 9.0));
+}
+
+#[test]
+fn edges_come_from_colour_depth_or_object()
+{
+    let anti_aliasing: AntiAliasing = AntiAliasing::DEFAULT;
+    let sample: RaySample = RaySample{ colour: Float3::new(0.5, 0.5, 0.5), depth: 4.0, object_id: Some(0) };
+    assert!(!anti_aliasing.is_edge_between(&sample, &sample));
+    assert!(!anti_aliasing.is_edge_between(&sample, &RaySample{ colour: Float3::new(0.55, 0.5, 0.5), depth: 4.1, ..sample }));
+
+    assert!(anti_aliasing.is_edge_between(&sample, &RaySample{ colour: Float3::new(0.5, 0.8, 0.5), ..sample }));
+    assert!(anti_aliasing.is_edge_between(&sample, &RaySample{ depth: 5.0, ..sample }));
+    assert!(anti_aliasing.is_edge_between(&sample, &RaySample{ object_id: Some(1), ..sample }));
+    assert!(anti_aliasing.is_edge_between(&sample, &RaySample::MISS));
+    assert!(!anti_aliasing.is_edge_between(&RaySample::MISS, &RaySample::MISS));
+}
+
+#[test]
+fn is_edge_checks_the_neighbours()
+{
+    let application: Application = Application::new(1.0, 3, true);
+    let mut first_pass: Vec<RaySample> = vec![RaySample::MISS; 9];
+    first_pass[0] = RaySample{ colour: Float3::new(1.0, 1.0, 1.0), depth: 2.0, object_id: Some(0) };
+
+    let anti_aliasing: AntiAliasing = AntiAliasing::DEFAULT;
+    assert!(anti_aliasing.is_edge(&first_pass, &application, 0, 0));
+    assert!(anti_aliasing.is_edge(&first_pass, &application, 1, 0));
+    assert!(anti_aliasing.is_edge(&first_pass, &application, 0, 1));
+    assert!(!anti_aliasing.is_edge(&first_pass, &application, 1, 1));
+    assert!(!anti_aliasing.is_edge(&first_pass, &application, 2, 2));
+}
+
+#[test]
+fn adaptive_matches_supersampling_every_pixel()
+{
+    let mut application: Application = Application::new(16.0 / 9.0, 70, true);
+    let camera: Camera4 = Camera4::new(Float4::new(0.0, 0.0, 0.0, 0.0), Float4::new(0.0, 0.0, 4.0, 0.0), Rotor4::IDENTITY, application.aspect_ratio, 70.0, 1.0);
+    let scene: Arc<RwLock<Scene>> = Arc::new(RwLock::new(Scene::new_4d(camera, Float4::new(2.0, 2.0, 4.0, 0.0))));
+    let pool: WorkerPool = WorkerPool::new(2);
+
+    let adaptive: Framebuffer = renderer::render_image(&pool, &scene, &application);
+    application.anti_aliasing = Some(AntiAliasing{ adaptive: false, ..AntiAliasing::DEFAULT });
+    let full: Framebuffer = renderer::render_image(&pool, &scene, &application);
+
+    for (a, b) in adaptive.colours().iter().zip(full.colours())
+    {
+        let difference: Float3 = *a - b;
+        assert!(f32::abs(difference.x) < 0.02 && f32::abs(difference.y) < 0.02 && f32::abs(difference.z) < 0.02, "{} {}", a, b);
+    }
+
+    // Flat areas keep their single centre sample
+    application.anti_aliasing = None;
+    let aliased: Framebuffer = renderer::render_image(&pool, &scene, &application);
+    let matching: usize = adaptive.colours().iter().zip(aliased.colours()).filter(|(a, b)| **a == *b).count();
+    assert!(matching > adaptive.colours().len() / 2, "only {} pixels skipped supersampling", matching);
+}
@@ -1,21 +1,26 @@
+use crate::anti_aliasing::AntiAliasing;
+
+#[derive(Copy, Clone, Debug)]
 pub struct Application
 {
     pub aspect_ratio: f32,
     pub width: u32,
     pub height: u32,
 
-    pub anti_aliasing: bool,
+    /// None renders one sample per pixel
+    pub anti_aliasing: Option<AntiAliasing>,
 }
 
 impl Application
 {
+    /// Anti aliasing uses the default adaptive settings, replace them afterwards for others
     pub fn new(aspect_ratio: f32, window_width: u32, anti_aliasing: bool) -> Application
     {
         return Application{
             aspect_ratio: aspect_ratio,
             width: window_width,
             height: (window_width as f32 / aspect_ratio) as u32,
-            anti_aliasing: anti_aliasing,
+            anti_aliasing: if anti_aliasing { Some(AntiAliasing::DEFAULT) } else { None },
         };
     }
-}
\ No newline at end of file
+}
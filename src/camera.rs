@@ -21,6 +21,20 @@ pub trait Camera<V: Vector, B: Bivector, R: Rotor<V, B>>
 
     fn get_ray_direction(&self, uv: Float2) -> V;
 
+    /// Offset of the camera from its pivot before rotation, its length is the orbit distance
+    fn get_offset_position(&self) -> V;
+
+    fn set_offset_position(&mut self, offset_position: V);
+
+    fn get_camera_rotation(&self) -> R;
+
+    /// Rotates the camera about its own position rather than the pivot, the pivot moves to stay
+    /// the same offset in front of it
+    fn turn_in_place(&mut self, rotation: R);
+
+    /// Moves the camera and its pivot together by a world space offset
+    fn move_camera(&mut self, offset: V);
+
     #[allow(dead_code)]
     fn set_camera_rotation(&mut self, rotation: R);
 
@@ -94,6 +108,35 @@ impl Camera<Float3, Bivector3, Rotor3> for Camera3
         return self.lower_left_corner + uv.x * self.horizontal + uv.y * self.vertical - self.position;
     }
 
+    fn get_offset_position(&self) -> Float3
+    {
+        return self.offset_position;
+    }
+
+    fn set_offset_position(&mut self, offset_position: Float3)
+    {
+        self.offset_position = offset_position;
+        self.recalculate();
+    }
+
+    fn get_camera_rotation(&self) -> Rotor3
+    {
+        return self.rotation;
+    }
+
+    fn turn_in_place(&mut self, rotation: Rotor3)
+    {
+        self.rotation *= rotation;
+        self.pivot = self.position - (self.rotation * self.offset_position);
+        self.recalculate();
+    }
+
+    fn move_camera(&mut self, offset: Float3)
+    {
+        self.pivot += offset;
+        self.recalculate();
+    }
+
     fn set_camera_rotation(&mut self, rotation: Rotor3)
     {
         self.rotation = rotation;
@@ -107,6 +150,7 @@ impl Camera<Float3, Bivector3, Rotor3> for Camera3
     }
 }
 
+#[derive(Copy, Clone)]
 pub struct Camera4
 {
     pivot: Float4,
@@ -173,6 +217,35 @@ impl Camera<Float4, Bivector4, Rotor4> for Camera4
         return self.lower_left_corner + uv.x * self.horizontal + uv.y * self.vertical - self.position;
     }
 
+    fn get_offset_position(&self) -> Float4
+    {
+        return self.offset_position;
+    }
+
+    fn set_offset_position(&mut self, offset_position: Float4)
+    {
+        self.offset_position = offset_position;
+        self.recalculate();
+    }
+
+    fn get_camera_rotation(&self) -> Rotor4
+    {
+        return self.rotation;
+    }
+
+    fn turn_in_place(&mut self, rotation: Rotor4)
+    {
+        self.rotation *= rotation;
+        self.pivot = self.position - (self.rotation * self.offset_position);
+        self.recalculate();
+    }
+
+    fn move_camera(&mut self, offset: Float4)
+    {
+        self.pivot += offset;
+        self.recalculate();
+    }
+
     fn set_camera_rotation(&mut self, rotation: Rotor4)
     {
         self.rotation = rotation;
@@ -184,4 +257,17 @@ impl Camera<Float4, Bivector4, Rotor4> for Camera4
         self.rotation *= rotation;
         self.recalculate(); 
     }
-}
\ No newline at end of file
+}
+
+impl Camera4
+{
+    /// The position and direction of the ray through uv for this camera turned about its pivot by
+    /// turn, without changing the camera. The same ray as after `rotate_camera(turn)`
+    pub fn get_turned_ray(&self, uv: Float2, turn: Rotor4) -> (Float4, Float4)
+    {
+        let rotation: Rotor4 = self.rotation * turn;
+        let position: Float4 = self.pivot + (rotation * self.offset_position);
+        let local: Float4 = Float4::new(self.viewport_dimensions.x * (uv.x - 0.5), self.viewport_dimensions.y * (uv.y - 0.5), -self.viewport_dimensions.z, 0.0);
+        return (position, rotation * local);
+    }
+}
@@ -0,0 +1,283 @@
+//! Turns mouse and keyboard input into camera rotations, movement and zoom.
+//!
+//! The window maps its events onto planes of rotation and hands them here, so the controls work the
+//! same whatever drives them. Dragging or holding a key turns the camera in one of the six planes of
+//! 4D space, only the three without w exist in 3D scenes. The orbit camera turns around its pivot,
+//! while the free flying camera turns in place and moves along its own axes, as in 4D Games.
+
+use crate::mathematics::bivector3::Bivector3;
+use crate::mathematics::bivector4::Bivector4;
+use crate::mathematics::float3::Float3;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::multivectors::{Magnitude, Rotor, Vector};
+use crate::mathematics::rotor3::Rotor3;
+use crate::mathematics::rotor4::Rotor4;
+
+/// Closest the camera can zoom to its pivot
+pub const MIN_DISTANCE: f32 = 0.5;
+
+/// Slowest and fastest free flying speeds in units per second
+pub const MIN_MOVE_SPEED: f32 = 0.1;
+pub const MAX_MOVE_SPEED: f32 = 100.0;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CameraMode
+{
+    /// Turns around the pivot and zooms towards it
+    Orbit,
+    /// Turns in place and moves along its own axes
+    FreeFly,
+}
+
+/// A direction the free flying camera moves in, relative to where it is looking
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MoveAxis
+{
+    Right,
+    Up,
+    Forward,
+    /// Along w, towards ana and away towards kata
+    Ana,
+}
+
+impl MoveAxis
+{
+    pub const ALL: [MoveAxis; 4] = [MoveAxis::Right, MoveAxis::Up, MoveAxis::Forward, MoveAxis::Ana];
+
+    /// The axis before the camera's rotation, the camera looks down negative z
+    pub fn local4(&self) -> Float4
+    {
+        return match self
+        {
+            MoveAxis::Right => Float4::new(1.0, 0.0, 0.0, 0.0),
+            MoveAxis::Up => Float4::new(0.0, 1.0, 0.0, 0.0),
+            MoveAxis::Forward => Float4::new(0.0, 0.0, -1.0, 0.0),
+            MoveAxis::Ana => Float4::new(0.0, 0.0, 0.0, 1.0),
+        };
+    }
+
+    fn index(&self) -> usize
+    {
+        return MoveAxis::ALL.iter().position(|axis| axis == self).unwrap_or(0);
+    }
+}
+
+/// A plane of rotation named by the two axes it turns between
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum RotationPlane
+{
+    XY,
+    XZ,
+    YZ,
+    XW,
+    YW,
+    ZW,
+}
+
+impl RotationPlane
+{
+    pub const ALL: [RotationPlane; 6] = [RotationPlane::XY, RotationPlane::XZ, RotationPlane::YZ, RotationPlane::XW, RotationPlane::YW, RotationPlane::ZW];
+
+    /// The unit bivector of the plane
+    pub fn bivector4(&self) -> Bivector4
+    {
+        return match self
+        {
+            RotationPlane::XY => Bivector4::new(0.0, 0.0, 1.0, 0.0, 0.0, 0.0),
+            RotationPlane::XZ => Bivector4::new(0.0, 1.0, 0.0, 0.0, 0.0, 0.0),
+            RotationPlane::YZ => Bivector4::new(1.0, 0.0, 0.0, 0.0, 0.0, 0.0),
+            RotationPlane::XW => Bivector4::new(0.0, 0.0, 0.0, 1.0, 0.0, 0.0),
+            RotationPlane::YW => Bivector4::new(0.0, 0.0, 0.0, 0.0, 1.0, 0.0),
+            RotationPlane::ZW => Bivector4::new(0.0, 0.0, 0.0, 0.0, 0.0, 1.0),
+        };
+    }
+
+    /// The unit bivector of the plane in 3D, None for the planes that use w
+    pub fn bivector3(&self) -> Option<Bivector3>
+    {
+        return match self
+        {
+            RotationPlane::XY => Some(Bivector3::new(0.0, 0.0, 1.0)),
+            RotationPlane::XZ => Some(Bivector3::new(0.0, 1.0, 0.0)),
+            RotationPlane::YZ => Some(Bivector3::new(1.0, 0.0, 0.0)),
+            _ => None,
+        };
+    }
+
+    fn index(&self) -> usize
+    {
+        return RotationPlane::ALL.iter().position(|plane| plane == self).unwrap_or(0);
+    }
+}
+
+pub struct CameraController
+{
+    pub mode: CameraMode,
+    /// Keep turning the orbit camera on its own
+    pub auto_rotate: bool,
+    /// Radians turned per pixel the mouse is dragged
+    pub mouse_sensitivity: f32,
+    /// Radians turned per second while a key is held
+    pub key_speed: f32,
+    /// Fraction of the distance to the pivot each scroll step zooms in or out, or the free flying speed changes by
+    pub zoom_step: f32,
+    /// Free flying speed in units per second
+    pub move_speed: f32,
+    /// Direction each plane is being turned by held keys, indexed like `RotationPlane::ALL`
+    held: [f32; 6],
+    /// Direction along each axis the free flying camera is being moved by held keys, indexed like `MoveAxis::ALL`
+    moving: [f32; 4],
+}
+
+impl CameraController
+{
+    pub fn new() -> CameraController
+    {
+        return CameraController{
+            mode: CameraMode::Orbit,
+            auto_rotate: true,
+            mouse_sensitivity: 0.01,
+            key_speed: 1.0,
+            zoom_step: 0.1,
+            move_speed: 2.0,
+            held: [0.0; 6],
+            moving: [0.0; 4],
+        };
+    }
+
+    /// Starts turning a plane while its key is held, direction is 1 or -1, or 0 when the key is released
+    pub fn hold(&mut self, plane: RotationPlane, direction: f32)
+    {
+        self.held[plane.index()] = direction;
+    }
+
+    /// Whether any key is turning the camera
+    pub fn is_turning(&self) -> bool
+    {
+        return self.held.iter().any(|direction| *direction != 0.0);
+    }
+
+    /// Rotation from the keys held over a frame, None when no keys are held
+    pub fn key_rotation4(&self, delta_time: f32) -> Option<Rotor4>
+    {
+        if !self.is_turning()
+        {
+            return None;
+        }
+        let h: [f32; 6] = self.held;
+        let plane: Bivector4 = Bivector4::new(h[2], h[1], h[0], h[3], h[4], h[5]);
+        return Some(Rotor4::bivector_angle(&plane, self.key_speed * delta_time * plane.length()));
+    }
+
+    /// `key_rotation4` for 3D scenes, keys for planes that use w are ignored
+    pub fn key_rotation3(&self, delta_time: f32) -> Option<Rotor3>
+    {
+        let h: [f32; 6] = self.held;
+        let plane: Bivector3 = Bivector3::new(h[2], h[1], h[0]);
+        if plane.length() == 0.0
+        {
+            return None;
+        }
+        return Some(Rotor3::bivector_angle(&plane, self.key_speed * delta_time * plane.length()));
+    }
+
+    /// The planes a mouse drag turns, left and right then up and down
+    /// Dragging normally orbits in 3D, with ana_kata it turns x and y towards w instead
+    pub fn drag_planes(ana_kata: bool) -> (RotationPlane, RotationPlane)
+    {
+        if ana_kata
+        {
+            return (RotationPlane::XW, RotationPlane::YW);
+        }
+        return (RotationPlane::XZ, RotationPlane::YZ);
+    }
+
+    /// Rotation from dragging the mouse by (dx, dy) pixels
+    pub fn drag_rotation4(&self, dx: f32, dy: f32, ana_kata: bool) -> Rotor4
+    {
+        let (horizontal, vertical): (RotationPlane, RotationPlane) = CameraController::drag_planes(ana_kata);
+        let turn: Rotor4 = Rotor4::bivector_angle(&horizontal.bivector4(), dx * self.mouse_sensitivity);
+        return turn * Rotor4::bivector_angle(&vertical.bivector4(), dy * self.mouse_sensitivity);
+    }
+
+    /// `drag_rotation4` for 3D scenes, which only orbit
+    pub fn drag_rotation3(&self, dx: f32, dy: f32) -> Rotor3
+    {
+        let (horizontal, vertical): (RotationPlane, RotationPlane) = CameraController::drag_planes(false);
+        let turn: Rotor3 = Rotor3::bivector_angle(&horizontal.bivector3().unwrap_or_default(), dx * self.mouse_sensitivity);
+        return turn * Rotor3::bivector_angle(&vertical.bivector3().unwrap_or_default(), dy * self.mouse_sensitivity);
+    }
+
+    /// Switches between orbiting and free flying, keys held for the old mode stop
+    pub fn toggle_mode(&mut self)
+    {
+        self.mode = if self.mode == CameraMode::Orbit { CameraMode::FreeFly } else { CameraMode::Orbit };
+        self.moving = [0.0; 4];
+    }
+
+    /// Starts moving along an axis while its key is held, direction is 1 or -1, or 0 when the key is released
+    pub fn hold_move(&mut self, axis: MoveAxis, direction: f32)
+    {
+        self.moving[axis.index()] = direction;
+    }
+
+    /// Sum of the local axes being moved along, None when not free flying or not moving
+    fn local_movement(&self) -> Option<Float4>
+    {
+        if self.mode != CameraMode::FreeFly || self.moving.iter().all(|direction| *direction == 0.0)
+        {
+            return None;
+        }
+        let mut local: Float4 = Float4::new(0.0, 0.0, 0.0, 0.0);
+        for (axis, direction) in MoveAxis::ALL.iter().zip(self.moving)
+        {
+            local += axis.local4() * direction;
+        }
+        return Some(local);
+    }
+
+    /// World space distance moved over a frame by a free flying camera with this rotation, None when not moving
+    /// Moving diagonally is no faster than along one axis
+    pub fn movement4(&self, rotation: Rotor4, delta_time: f32) -> Option<Float4>
+    {
+        let local: Float4 = self.local_movement().filter(|local| local.length() > 0.0)?;
+        return Some(rotation * (local.normalized() * self.move_speed * delta_time));
+    }
+
+    /// `movement4` for 3D scenes, moving along w is ignored
+    pub fn movement3(&self, rotation: Rotor3, delta_time: f32) -> Option<Float3>
+    {
+        let local: Float3 = self.local_movement().map(Float3::from).filter(|local| local.length() > 0.0)?;
+        return Some(rotation * (local.normalized() * self.move_speed * delta_time));
+    }
+
+    /// Scrolling speeds the free flying camera up, or slows it down for negative steps
+    pub fn change_speed(&mut self, steps: f32)
+    {
+        self.move_speed = f32::clamp(self.move_speed * f32::powf(1.0 + self.zoom_step, steps), MIN_MOVE_SPEED, MAX_MOVE_SPEED);
+    }
+
+    /// The camera's offset from its pivot after scrolling, positive steps zoom in
+    pub fn zoom<V: Vector>(&self, offset_position: V, steps: f32) -> V
+    {
+        let distance: f32 = offset_position.length();
+        if distance == 0.0
+        {
+            return offset_position;
+        }
+        let zoomed: f32 = f32::max(distance * f32::powf(1.0 - self.zoom_step, steps), MIN_DISTANCE);
+        return offset_position * (zoomed / distance);
+    }
+}
+
+impl Default for CameraController
+{
+    fn default() -> Self
+    {
+        return CameraController::new();
+    }
+}
+
+#[cfg(test)]
+#[path = "camera_controller_tests.rs"]
+mod tests;
@@ -0,0 +1,144 @@
+use crate::camera::{Camera, Camera4};
+use crate::camera_controller::*;
+use crate::mathematics::approx_equal;
+use crate::mathematics::float2::Float2;
+use crate::mathematics::float3::Float3;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::multivectors::Magnitude;
+use crate::mathematics::rotor3::Rotor3;
+use crate::mathematics::rotor4::Rotor4;
+
+const X: Float4 = Float4{ x: 1.0, y: 0.0, z: 0.0, w: 0.0 };
+const Y: Float4 = Float4{ x: 0.0, y: 1.0, z: 0.0, w: 0.0 };
+const Z: Float4 = Float4{ x: 0.0, y: 0.0, z: 1.0, w: 0.0 };
+const W: Float4 = Float4{ x: 0.0, y: 0.0, z: 0.0, w: 1.0 };
+
+#[test]
+fn held_keys_turn_in_their_plane()
+{
+    let mut controller: CameraController = CameraController::new();
+    assert!(controller.key_rotation4(0.1).is_none());
+
+    controller.hold(RotationPlane::XW, 1.0);
+    assert!(controller.is_turning());
+    let r: Rotor4 = controller.key_rotation4(0.1).unwrap();
+    let x: Float4 = r * X;
+    assert!(x.w != 0.0 && approx_equal(x.y, 0.0) && approx_equal(x.z, 0.0));
+    assert_eq!(r * Y, Y);
+    assert_eq!(r * Z, Z);
+
+    // 3D scenes have no w to turn towards
+    assert!(controller.key_rotation3(0.1).is_none());
+
+    controller.hold(RotationPlane::XW, 0.0);
+    assert!(!controller.is_turning());
+}
+
+#[test]
+fn dragging_orbits_or_turns_towards_w()
+{
+    let controller: CameraController = CameraController::new();
+
+    let orbit: Rotor4 = controller.drag_rotation4(20.0, 0.0, false);
+    assert_eq!(orbit * Y, Y);
+    assert_eq!(orbit * W, W);
+    assert!(orbit * X != X);
+
+    let ana_kata: Rotor4 = controller.drag_rotation4(20.0, 0.0, true);
+    assert_eq!(ana_kata * Y, Y);
+    assert_eq!(ana_kata * Z, Z);
+    assert!(f32::abs((ana_kata * X).w) > 0.0);
+
+    let pitch: Rotor4 = controller.drag_rotation4(0.0, 20.0, false);
+    assert_eq!(pitch * X, X);
+    assert_eq!(pitch * W, W);
+}
+
+#[test]
+fn only_planes_without_w_exist_in_3d()
+{
+    let planes: Vec<RotationPlane> = RotationPlane::ALL.into_iter().filter(|plane| plane.bivector3().is_some()).collect();
+    assert_eq!(planes, vec![RotationPlane::XY, RotationPlane::XZ, RotationPlane::YZ]);
+    for plane in RotationPlane::ALL
+    {
+        assert!(approx_equal(plane.bivector4().length(), 1.0));
+    }
+}
+
+#[test]
+fn zoom_keeps_the_direction()
+{
+    let controller: CameraController = CameraController::new();
+    let offset: Float3 = Float3::new(0.0, 0.0, 4.0);
+
+    let closer: Float3 = controller.zoom(offset, 1.0);
+    assert!(approx_equal(closer.z, 4.0 * (1.0 - controller.zoom_step)));
+    assert_eq!(controller.zoom(closer, -1.0), offset);
+
+    // Never zooms through the pivot
+    assert_eq!(controller.zoom(offset, 100.0), Float3::new(0.0, 0.0, MIN_DISTANCE));
+}
+
+#[test]
+fn free_flying_moves_along_the_view()
+{
+    let mut controller: CameraController = CameraController::new();
+    controller.hold_move(MoveAxis::Forward, 1.0);
+    // Orbit cameras do not fly
+    assert!(controller.movement4(Rotor4::IDENTITY, 1.0).is_none());
+
+    controller.toggle_mode();
+    assert_eq!(controller.mode, CameraMode::FreeFly);
+    controller.hold_move(MoveAxis::Forward, 1.0);
+    assert_eq!(controller.movement4(Rotor4::IDENTITY, 0.5), Some(Float4::new(0.0, 0.0, -0.5 * controller.move_speed, 0.0)));
+
+    // Diagonals are no faster, and turning the camera turns the movement with it
+    controller.hold_move(MoveAxis::Ana, 1.0);
+    let turned: Rotor4 = controller.drag_rotation4(50.0, 30.0, true);
+    let moved: Float4 = controller.movement4(turned, 1.0).unwrap();
+    assert!(approx_equal(moved.length(), controller.move_speed));
+    assert_eq!(moved, turned * (Float4::new(0.0, 0.0, -1.0, 1.0).normalized() * controller.move_speed));
+
+    // 3D scenes have no ana or kata
+    controller.hold_move(MoveAxis::Forward, 0.0);
+    assert!(controller.movement3(Rotor3::IDENTITY, 1.0).is_none());
+
+    controller.toggle_mode();
+    assert!(controller.movement4(Rotor4::IDENTITY, 1.0).is_none());
+}
+
+#[test]
+fn scrolling_changes_the_flying_speed()
+{
+    let mut controller: CameraController = CameraController::new();
+    let speed: f32 = controller.move_speed;
+    controller.change_speed(1.0);
+    assert!(controller.move_speed > speed);
+    controller.change_speed(-1000.0);
+    assert_eq!(controller.move_speed, MIN_MOVE_SPEED);
+    controller.change_speed(1000.0);
+    assert_eq!(controller.move_speed, MAX_MOVE_SPEED);
+}
+
+#[test]
+fn free_flying_camera_turns_in_place()
+{
+    let mut camera: Camera4 = Camera4::new(Float4::new(0.0, 0.0, 0.0, 0.0), Float4::new(0.0, 0.0, 4.0, 0.0), Rotor4::IDENTITY, 1.0, 70.0, 1.0);
+    let position: Float4 = camera.get_camera_position();
+    let turn: Rotor4 = CameraController::new().drag_rotation4(40.0, 10.0, false);
+
+    camera.turn_in_place(turn);
+    assert_eq!(camera.get_camera_position(), position);
+    assert_eq!(camera.get_camera_rotation(), turn);
+    // The view turned, and forward turned with it
+    let forward: Float4 = turn * MoveAxis::Forward.local4();
+    assert!(forward != MoveAxis::Forward.local4());
+    assert_eq!(camera.get_ray_direction(Float2::new(0.5, 0.5)).normalized(), forward);
+
+    camera.move_camera(Float4::new(0.0, 0.0, 0.0, 2.0));
+    assert_eq!(camera.get_camera_position(), position + Float4::new(0.0, 0.0, 0.0, 2.0));
+
+    // Orbiting does move the camera
+    camera.rotate_camera(turn);
+    assert!(camera.get_camera_position() != position + Float4::new(0.0, 0.0, 0.0, 2.0));
+}
@@ -0,0 +1,128 @@
+//! Constructive solid geometry on signed distances.
+//!
+//! Every operator takes the distances to two shapes and returns the combined distance along with
+//! the operand whose surface is closest, so a material can be picked for each object. The
+//! operators only act on distances so they work the same for 3D and 4D scenes.
+
+use crate::mathematics::f32x8::{F32x8, Mask8, LANES};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Operand
+{
+    First,
+    Second,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CsgOperation
+{
+    Union,
+    Intersection,
+    /// The second shape is removed from the first
+    Subtraction,
+    /// Either shape but not both
+    Xor,
+}
+
+/// How the surfaces of two shapes meet where they cross
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Blend
+{
+    Sharp,
+    /// Polynomial smooth min, k is the width of the blend
+    Polynomial(f32),
+    /// Exponential smooth min, k is the width of the blend
+    Exponential(f32),
+    /// A 45 degree bevel of the given size
+    Chamfer(f32),
+    /// A staircase of the given size and number of steps
+    Stairs{ radius: f32, steps: f32 },
+}
+
+/// Blended minimum of a and b
+pub fn blend_min(a: f32, b: f32, blend: Blend) -> f32
+{
+    return match blend
+    {
+        Blend::Sharp => f32::min(a, b),
+        Blend::Polynomial(k) =>
+        {
+            let h: f32 = f32::max(k - f32::abs(a - b), 0.0) / k;
+            f32::min(a, b) - h * h * k * 0.25
+        }
+        Blend::Exponential(k) =>
+        {
+            // Shifted by the minimum so the powers never underflow to 0 far from both surfaces
+            let m: f32 = f32::min(a, b);
+            let res: f32 = f32::exp2(-(a - m) / k) + f32::exp2(-(b - m) / k);
+            m - f32::log2(res) * k
+        }
+        Blend::Chamfer(radius) => f32::min(f32::min(a, b), (a - radius + b) * f32::sqrt(0.5)),
+        Blend::Stairs{ radius, steps } =>
+        {
+            // From hg_sdf, https://mercury.sexy/hg_sdf/
+            let s: f32 = radius / steps;
+            let u: f32 = b - radius;
+            f32::min(f32::min(a, b), 0.5 * (u + a + f32::abs(modulo(u - a + s, 2.0 * s) - s)))
+        }
+    };
+}
+
+/// Blended maximum of a and b
+pub fn blend_max(a: f32, b: f32, blend: Blend) -> f32
+{
+    return -blend_min(-a, -b, blend);
+}
+
+pub fn union(a: f32, b: f32, blend: Blend) -> (f32, Operand)
+{
+    let operand: Operand = if a <= b { Operand::First } else { Operand::Second };
+    return (blend_min(a, b, blend), operand);
+}
+
+pub fn intersection(a: f32, b: f32, blend: Blend) -> (f32, Operand)
+{
+    let operand: Operand = if a >= b { Operand::First } else { Operand::Second };
+    return (blend_max(a, b, blend), operand);
+}
+
+/// Removes b from a
+pub fn subtraction(a: f32, b: f32, blend: Blend) -> (f32, Operand)
+{
+    let operand: Operand = if a >= -b { Operand::First } else { Operand::Second };
+    return (blend_max(a, -b, blend), operand);
+}
+
+pub fn xor(a: f32, b: f32, blend: Blend) -> (f32, Operand)
+{
+    let operand: Operand = if f32::abs(a) <= f32::abs(b) { Operand::First } else { Operand::Second };
+    return (blend_max(blend_min(a, b, blend), -blend_max(a, b, blend), blend), operand);
+}
+
+pub fn combine(operation: CsgOperation, a: f32, b: f32, blend: Blend) -> (f32, Operand)
+{
+    return match operation
+    {
+        CsgOperation::Union => union(a, b, blend),
+        CsgOperation::Intersection => intersection(a, b, blend),
+        CsgOperation::Subtraction => subtraction(a, b, blend),
+        CsgOperation::Xor => xor(a, b, blend),
+    };
+}
+
+/// Combines each lane of two packets, the mask is set in lanes where the second operand is closest
+pub fn combine_packet(operation: CsgOperation, a: F32x8, b: F32x8, blend: Blend) -> (F32x8, Mask8)
+{
+    let lanes: [(f32, Operand); LANES] = std::array::from_fn(|i| combine(operation, a.lane(i), b.lane(i), blend));
+    return (F32x8::from_fn(|i| lanes[i].0), Mask8::from_fn(|i| lanes[i].1 == Operand::Second));
+}
+
+/// GLSL style modulo, the result has the sign of y
+fn modulo(x: f32, y: f32) -> f32
+{
+    return x - y * f32::floor(x / y);
+}
+
+#[cfg(test)]
+#[path = "csg_tests.rs"]
+mod tests;
@@ -0,0 +1,61 @@
+use crate::csg::*;
+use crate::mathematics::approx_equal;
+
+const BLENDS: [Blend; 5] = [
+    Blend::Sharp,
+    Blend::Polynomial(0.5),
+    Blend::Exponential(0.1),
+    Blend::Chamfer(0.5),
+    Blend::Stairs{ radius: 0.5, steps: 4.0 },
+];
+
+#[test]
+fn sharp_operators()
+{
+    assert_eq!(union(1.0, 2.0, Blend::Sharp), (1.0, Operand::First));
+    assert_eq!(union(3.0, 2.0, Blend::Sharp), (2.0, Operand::Second));
+    assert_eq!(intersection(1.0, 2.0, Blend::Sharp), (2.0, Operand::Second));
+    assert_eq!(subtraction(1.0, -2.0, Blend::Sharp), (2.0, Operand::Second));
+    assert_eq!(subtraction(1.0, 2.0, Blend::Sharp), (1.0, Operand::First));
+    assert_eq!(xor(-1.0, -0.5, Blend::Sharp), (0.5, Operand::Second));
+    assert_eq!(combine(CsgOperation::Union, 1.0, 2.0, Blend::Sharp), union(1.0, 2.0, Blend::Sharp));
+}
+
+#[test]
+fn blends_only_change_the_seam()
+{
+    for blend in BLENDS
+    {
+        // Far from the other surface every blend is the plain minimum
+        assert!(approx_equal(blend_min(1.0, 5.0, blend), 1.0), "{:?}", blend);
+        assert!(approx_equal(blend_max(-1.0, -5.0, blend), -1.0), "{:?}", blend);
+
+        // Where the surfaces meet the blend adds material
+        assert!(blend_min(0.2, 0.2, blend) <= 0.2, "{:?}", blend);
+    }
+
+    assert!(approx_equal(blend_min(0.0, 0.0, Blend::Polynomial(1.0)), -0.25));
+    assert!(approx_equal(blend_min(0.0, 0.0, Blend::Chamfer(1.0)), -f32::sqrt(0.5)));
+}
+
+#[test]
+fn blends_report_the_closest_operand()
+{
+    for blend in BLENDS
+    {
+        assert_eq!(union(0.1, 0.3, blend).1, Operand::First);
+        assert_eq!(union(0.3, 0.1, blend).1, Operand::Second);
+    }
+}
+
+#[test]
+fn exponential_blend_stays_finite_far_away()
+{
+    assert!(approx_equal(blend_min(7.5, 7.5, Blend::Exponential(0.05)), 7.5 - 0.05));
+    assert!(approx_equal(blend_min(20.0, 25.0, Blend::Exponential(0.1)), 20.0));
+    assert!(approx_equal(blend_max(-20.0, -25.0, Blend::Exponential(0.1)), -20.0));
+    for operation in [CsgOperation::Union, CsgOperation::Intersection, CsgOperation::Subtraction, CsgOperation::Xor]
+    {
+        assert!(combine(operation, 300.0, 200.0, Blend::Exponential(0.05)).0.is_finite(), "{:?}", operation);
+    }
+}
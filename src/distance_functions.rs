@@ -1,8 +1,13 @@
 
+use crate::mathematics;
+use crate::mathematics::float2::Float2;
 use crate::mathematics::float3::Float3;
+use crate::mathematics::f32x8::F32x8;
+use crate::mathematics::float3x8::Float3x8;
 use crate::mathematics::float4::Float4;
+use crate::mathematics::float4x8::Float4x8;
 use crate::mathematics::multivectors::Magnitude;
-use crate::mathematics::multivectors::Vector;
+use crate::mathematics::multivectors::{Vector, VectorPacket};
 
 #[allow(dead_code)]
 pub fn sdf_sphere<T: Vector>(p: T, centre: T, radius: f32) -> f32
@@ -42,4 +47,217 @@ pub fn sdf_box4(p: Float4, centre: Float4, size: Float4, edge_radius: f32) -> f3
             ),
             0.0
         ) - edge_radius;
-}
\ No newline at end of file
+}
+
+/// Distance to the Cartesian product of two shapes lying in orthogonal subspaces, given the distance to each shape in its own subspace
+/// Exact when both distances are exact
+#[allow(dead_code)]
+pub fn sdf_product(d1: f32, d2: f32) -> f32
+{
+    return Float2::new(f32::max(d1, 0.0), f32::max(d2, 0.0)).length() + f32::min(f32::max(d1, d2), 0.0);
+}
+
+/// Distance in the half plane (radial, axial) to a solid cone with its apex at the origin and its base of the given radius at axial = -height
+/// Revolving this half plane gives an exact distance to the revolved cone in any dimension
+fn sdf_revolved_cone(radial: f32, axial: f32, radius: f32, height: f32) -> f32
+{
+    let w: Float2 = Float2::new(radial, axial);
+    let q: Float2 = Float2::new(radius, -height);
+
+    let a: Float2 = w - q * mathematics::clamp(Float2::dot(w, q) / Float2::dot(q, q), 0.0, 1.0);
+    let b: Float2 = w - Float2::new(q.x * mathematics::clamp(w.x / q.x, 0.0, 1.0), q.y);
+
+    let d: f32 = f32::min(Float2::dot(a, a), Float2::dot(b, b));
+    let s: f32 = f32::max(-(w.x * q.y - w.y * q.x), -(w.y - q.y));
+
+    return f32::sqrt(d) * f32::signum(s);
+}
+
+/// Product of a disk of radii.x in the XY plane and a disk of radii.y in the ZW plane
+#[allow(dead_code)]
+pub fn sdf_duocylinder(p: Float4, centre: Float4, radii: Float2) -> f32
+{
+    let p: Float4 = p-centre;
+    return sdf_product(
+        Float2::new(p.x, p.y).length() - radii.x,
+        Float2::new(p.z, p.w).length() - radii.y
+    );
+}
+
+/// A ball in XYZ extruded along W, height is half the length along W
+#[allow(dead_code)]
+pub fn sdf_spherinder(p: Float4, centre: Float4, radius: f32, height: f32) -> f32
+{
+    let p: Float4 = p-centre;
+    return sdf_product(
+        Float3::new(p.x, p.y, p.z).length() - radius,
+        f32::abs(p.w) - height
+    );
+}
+
+/// Product of a disk in the XY plane and a square in the ZW plane, size is half the side of the square
+#[allow(dead_code)]
+pub fn sdf_cubinder(p: Float4, centre: Float4, radius: f32, size: f32) -> f32
+{
+    let p: Float4 = p-centre;
+    return sdf_product(
+        Float2::new(p.x, p.y).length() - radius,
+        sdf_product(f32::abs(p.z) - size, f32::abs(p.w) - size)
+    );
+}
+
+/// A cylinder around the Y axis extruded along W
+/// height and length are the half extents along Y and W
+#[allow(dead_code)]
+pub fn sdf_cylinder_prism(p: Float4, centre: Float4, radius: f32, height: f32, length: f32) -> f32
+{
+    let p: Float4 = p-centre;
+    return sdf_product(
+        Float2::new(p.x, p.z).length() - radius,
+        sdf_product(f32::abs(p.y) - height, f32::abs(p.w) - length)
+    );
+}
+
+/// A cone around the Y axis extruded along W, the base is at the centre and the apex at centre + height along Y
+/// length is the half extent along W
+#[allow(dead_code)]
+pub fn sdf_cone4(p: Float4, centre: Float4, radius: f32, height: f32, length: f32) -> f32
+{
+    let p: Float4 = p-centre;
+    return sdf_product(
+        sdf_revolved_cone(Float2::new(p.x, p.z).length(), p.y - height, radius, height),
+        f32::abs(p.w) - length
+    );
+}
+
+/// A cone whose base is a ball in XYZ at the centre, with the apex at centre + height along W
+#[allow(dead_code)]
+pub fn sdf_spherone(p: Float4, centre: Float4, radius: f32, height: f32) -> f32
+{
+    let p: Float4 = p-centre;
+    return sdf_revolved_cone(Float3::new(p.x, p.y, p.z).length(), p.w - height, radius, height);
+}
+
+/// Product of a circle of radii.x in the XY plane and a circle of radii.y in the ZW plane, thickened by radius
+#[allow(dead_code)]
+pub fn sdf_tiger(p: Float4, centre: Float4, radii: Float2, radius: f32) -> f32
+{
+    let p: Float4 = p-centre;
+    return Float2::new(
+        Float2::new(p.x, p.y).length() - radii.x,
+        Float2::new(p.z, p.w).length() - radii.y
+    ).length() - radius;
+}
+
+/// A torus (major radius radii.x in XY, minor radius radii.y) revolved through W, thickened by radius
+#[allow(dead_code)]
+pub fn sdf_ditorus(p: Float4, centre: Float4, radii: Float2, radius: f32) -> f32
+{
+    let p: Float4 = p-centre;
+    let torus: f32 = Float2::new(Float2::new(p.x, p.y).length() - radii.x, p.z).length() - radii.y;
+    return Float2::new(torus, p.w).length() - radius;
+}
+
+/// A circle of major_radius in the XY plane thickened into a 4D ball of minor_radius
+#[allow(dead_code)]
+pub fn sdf_torisphere(p: Float4, centre: Float4, major_radius: f32, minor_radius: f32) -> f32
+{
+    let p: Float4 = p-centre;
+    return Float3::new(Float2::new(p.x, p.y).length() - major_radius, p.z, p.w).length() - minor_radius;
+}
+
+/// A sphere of major_radius in XYZ thickened into a 4D ball of minor_radius
+#[allow(dead_code)]
+pub fn sdf_spheritorus(p: Float4, centre: Float4, major_radius: f32, minor_radius: f32) -> f32
+{
+    let p: Float4 = p-centre;
+    return Float2::new(Float3::new(p.x, p.y, p.z).length() - major_radius, p.w).length() - minor_radius;
+}
+
+/// Unbounded hyperplane with no thickness, normal must be normalized
+#[allow(dead_code)]
+pub fn sdf_hyperplane<T: Vector>(p: T, normal: T, offset: f32) -> f32
+{
+    return f32::abs(T::dot(p, normal) - offset);
+}
+
+/// Everything on the opposite side of the hyperplane to the normal, normal must be normalized
+#[allow(dead_code)]
+pub fn sdf_half_space<T: Vector>(p: T, normal: T, offset: f32) -> f32
+{
+    return T::dot(p, normal) - offset;
+}
+
+/// Line segment from a to b thickened by radius, giving round caps at either end
+#[allow(dead_code)]
+pub fn sdf_capsule<T: Vector>(p: T, a: T, b: T, radius: f32) -> f32
+{
+    let pa: T = p - a;
+    let ba: T = b - a;
+    let h: f32 = mathematics::clamp(T::dot(pa, ba) / T::dot(ba, ba), 0.0, 1.0);
+    return (pa - ba * h).length() - radius;
+}
+
+// Packets
+// Each matches its scalar version above lane for lane
+
+pub fn sdf_sphere_packet<P: VectorPacket>(p: P, centre: P::Scalar, radius: f32) -> F32x8
+{
+    return (p - P::splat(centre)).length() - radius;
+}
+
+pub fn sdf_box3_packet(p: Float3x8, centre: Float3, size: Float3, edge_radius: f32) -> F32x8
+{
+    let p: Float3x8 = p - Float3x8::splat(centre);
+    let q: Float3x8 = Float3x8::new(p.x.abs() - size.x + edge_radius, p.y.abs() - size.y + edge_radius, p.z.abs() - size.z + edge_radius);
+    let zero: F32x8 = F32x8::ZERO;
+
+    return 
+        Float3x8::new(F32x8::max(q.x, zero), F32x8::max(q.y, zero), F32x8::max(q.z, zero)).length() + 
+        F32x8::min(
+            F32x8::max(q.x, 
+                F32x8::max(q.y, q.z)
+            ),
+            zero
+        ) - edge_radius;
+}
+
+pub fn sdf_box4_packet(p: Float4x8, centre: Float4, size: Float4, edge_radius: f32) -> F32x8
+{
+    let p: Float4x8 = p - Float4x8::splat(centre);
+    let q: Float4x8 = Float4x8::new(p.x.abs() - size.x + edge_radius, p.y.abs() - size.y + edge_radius, p.z.abs() - size.z + edge_radius, p.w.abs() - size.w + edge_radius);
+    let zero: F32x8 = F32x8::ZERO;
+
+    return 
+        Float4x8::new(F32x8::max(q.x, zero), F32x8::max(q.y, zero), F32x8::max(q.z, zero), F32x8::max(q.w, zero)).length() + 
+        F32x8::min(
+            F32x8::max(q.x, 
+                F32x8::max(q.y, 
+                    F32x8::max(q.z, q.w)
+                )
+            ),
+            zero
+        ) - edge_radius;
+}
+
+pub fn sdf_hyperplane_packet<P: VectorPacket>(p: P, normal: P::Scalar, offset: f32) -> F32x8
+{
+    return (P::dot(p, P::splat(normal)) - offset).abs();
+}
+
+pub fn sdf_half_space_packet<P: VectorPacket>(p: P, normal: P::Scalar, offset: f32) -> F32x8
+{
+    return P::dot(p, P::splat(normal)) - offset;
+}
+
+pub fn sdf_capsule_packet<P: VectorPacket>(p: P, a: P::Scalar, b: P::Scalar, radius: f32) -> F32x8
+{
+    let pa: P = p - P::splat(a);
+    let ba: P = P::splat(b - a);
+    let h: F32x8 = (P::dot(pa, ba) / P::dot(ba, ba)).clamp(0.0, 1.0);
+    return (pa - ba * h).length() - radius;
+}
+
+#[cfg(test)]
+#[path = "distance_functions_tests.rs"]
+mod tests;
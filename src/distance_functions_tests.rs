@@ -0,0 +1,144 @@
+use crate::distance_functions::*;
+use crate::mathematics::float2::Float2;
+use crate::mathematics::float3::Float3;
+use crate::mathematics::float4::Float4;
+
+const ORIGIN: Float4 = Float4{ x: 0.0, y: 0.0, z: 0.0, w: 0.0 };
+
+fn assert_distance(distance: f32, expected: f32)
+{
+    assert!(f32::abs(distance - expected) < 1e-5, "distance {} expected {}", distance, expected);
+}
+
+#[test]
+fn duocylinder()
+{
+    let radii: Float2 = Float2::new(1.0, 2.0);
+
+    assert_distance(sdf_duocylinder(ORIGIN, ORIGIN, radii), -1.0);
+    assert_distance(sdf_duocylinder(Float4::new(3.0, 0.0, 0.0, 0.0), ORIGIN, radii), 2.0);
+    assert_distance(sdf_duocylinder(Float4::new(0.0, 0.0, 3.0, 0.0), ORIGIN, radii), 1.0);
+    assert_distance(sdf_duocylinder(Float4::new(2.0, 0.0, 3.0, 0.0), ORIGIN, radii), f32::sqrt(2.0));
+
+    // Both circles meet at the ridge where the two cylindrical cells join
+    assert_distance(sdf_duocylinder(Float4::new(0.0, 1.0, 0.0, 2.0), ORIGIN, radii), 0.0);
+}
+
+#[test]
+fn spherinder()
+{
+    assert_distance(sdf_spherinder(ORIGIN, ORIGIN, 1.0, 2.0), -1.0);
+    assert_distance(sdf_spherinder(Float4::new(0.0, 0.0, 0.0, 1.5), ORIGIN, 1.0, 2.0), -0.5);
+    assert_distance(sdf_spherinder(Float4::new(0.0, 0.0, 0.0, 3.0), ORIGIN, 1.0, 2.0), 1.0);
+    assert_distance(sdf_spherinder(Float4::new(0.0, 2.0, 0.0, 0.0), ORIGIN, 1.0, 2.0), 1.0);
+
+    let centre: Float4 = Float4::new(0.0, 0.0, 0.0, 5.0);
+    assert_distance(sdf_spherinder(Float4::new(0.0, 0.0, 0.0, 5.0), centre, 1.0, 2.0), -1.0);
+}
+
+#[test]
+fn cubinder()
+{
+    assert_distance(sdf_cubinder(ORIGIN, ORIGIN, 1.0, 1.0), -1.0);
+    assert_distance(sdf_cubinder(Float4::new(2.0, 0.0, 0.0, 0.0), ORIGIN, 1.0, 1.0), 1.0);
+    assert_distance(sdf_cubinder(Float4::new(0.0, 0.0, 3.0, 3.0), ORIGIN, 1.0, 1.0), 2.0 * f32::sqrt(2.0));
+    assert!(sdf_cubinder(Float4::new(0.5, 0.5, 0.9, 0.9), ORIGIN, 1.0, 1.0) < 0.0);
+}
+
+#[test]
+fn cylinder_prism()
+{
+    assert_distance(sdf_cylinder_prism(ORIGIN, ORIGIN, 1.0, 2.0, 3.0), -1.0);
+    assert_distance(sdf_cylinder_prism(Float4::new(0.0, 0.0, 2.0, 0.0), ORIGIN, 1.0, 2.0, 3.0), 1.0);
+    assert_distance(sdf_cylinder_prism(Float4::new(0.0, 3.0, 0.0, 0.0), ORIGIN, 1.0, 2.0, 3.0), 1.0);
+    assert_distance(sdf_cylinder_prism(Float4::new(0.0, 0.0, 0.0, 4.0), ORIGIN, 1.0, 2.0, 3.0), 1.0);
+}
+
+#[test]
+fn cone4()
+{
+    // Apex at y = 1, base of radius 1 at y = 0
+    assert_distance(sdf_cone4(Float4::new(0.0, 2.0, 0.0, 0.0), ORIGIN, 1.0, 1.0, 1.0), 1.0);
+    assert_distance(sdf_cone4(Float4::new(0.0, -1.0, 0.0, 0.0), ORIGIN, 1.0, 1.0, 1.0), 1.0);
+    assert_distance(sdf_cone4(Float4::new(0.0, 0.5, 0.0, 0.0), ORIGIN, 1.0, 1.0, 1.0), -0.5 / f32::sqrt(2.0));
+    assert_distance(sdf_cone4(Float4::new(0.0, 0.5, 0.0, 2.0), ORIGIN, 1.0, 1.0, 1.0), 1.0);
+    assert!(sdf_cone4(Float4::new(0.9, 0.5, 0.0, 0.0), ORIGIN, 1.0, 1.0, 1.0) > 0.0);
+}
+
+#[test]
+fn spherone()
+{
+    // Apex at w = 1, base ball of radius 1 at w = 0
+    assert_distance(sdf_spherone(Float4::new(0.0, 0.0, 0.0, 2.0), ORIGIN, 1.0, 1.0), 1.0);
+    assert_distance(sdf_spherone(Float4::new(0.0, 2.0, 0.0, 0.0), ORIGIN, 1.0, 1.0), 1.0);
+    assert_distance(sdf_spherone(Float4::new(0.0, 0.0, 0.0, 0.5), ORIGIN, 1.0, 1.0), -0.5 / f32::sqrt(2.0));
+    assert_distance(sdf_spherone(Float4::new(0.0, 0.0, 0.0, -0.5), ORIGIN, 1.0, 1.0), 0.5);
+
+    // The base is a ball so every direction in XYZ is the same
+    let a: f32 = sdf_spherone(Float4::new(0.3, 0.0, 0.0, 0.2), ORIGIN, 1.0, 1.0);
+    let b: f32 = sdf_spherone(Float4::new(0.0, 0.0, 0.3, 0.2), ORIGIN, 1.0, 1.0);
+    assert_distance(a, b);
+}
+
+#[test]
+fn tiger()
+{
+    let radii: Float2 = Float2::new(2.0, 2.0);
+
+    assert_distance(sdf_tiger(Float4::new(2.0, 0.0, 2.0, 0.0), ORIGIN, radii, 0.5), -0.5);
+    assert_distance(sdf_tiger(Float4::new(0.0, 2.0, 0.0, 2.0), ORIGIN, radii, 0.5), -0.5);
+    assert_distance(sdf_tiger(Float4::new(3.0, 0.0, 2.0, 0.0), ORIGIN, radii, 0.5), 0.5);
+    assert_distance(sdf_tiger(ORIGIN, ORIGIN, radii, 0.5), 2.0 * f32::sqrt(2.0) - 0.5);
+}
+
+#[test]
+fn ditorus()
+{
+    let radii: Float2 = Float2::new(2.0, 1.0);
+
+    assert_distance(sdf_ditorus(Float4::new(3.0, 0.0, 0.0, 0.0), ORIGIN, radii, 0.25), -0.25);
+    assert_distance(sdf_ditorus(Float4::new(0.0, 2.0, 1.0, 0.0), ORIGIN, radii, 0.25), -0.25);
+    assert_distance(sdf_ditorus(Float4::new(3.0, 0.0, 0.0, 1.0), ORIGIN, radii, 0.25), 0.75);
+    assert_distance(sdf_ditorus(ORIGIN, ORIGIN, radii, 0.25), 0.75);
+}
+
+#[test]
+fn torisphere_and_spheritorus()
+{
+    assert_distance(sdf_torisphere(Float4::new(2.0, 0.0, 0.0, 0.0), ORIGIN, 2.0, 0.5), -0.5);
+    assert_distance(sdf_torisphere(Float4::new(2.0, 0.0, 0.0, 1.0), ORIGIN, 2.0, 0.5), 0.5);
+    assert_distance(sdf_torisphere(ORIGIN, ORIGIN, 2.0, 0.5), 1.5);
+
+    assert_distance(sdf_spheritorus(Float4::new(0.0, 0.0, 2.0, 0.0), ORIGIN, 2.0, 0.5), -0.5);
+    assert_distance(sdf_spheritorus(Float4::new(0.0, 0.0, 2.0, 1.0), ORIGIN, 2.0, 0.5), 0.5);
+    assert_distance(sdf_spheritorus(ORIGIN, ORIGIN, 2.0, 0.5), 1.5);
+}
+
+#[test]
+fn hyperplane_and_half_space()
+{
+    let normal: Float4 = Float4::new(0.0, 0.0, 0.0, 1.0);
+
+    assert_distance(sdf_hyperplane(Float4::new(5.0, 0.0, 0.0, 3.0), normal, 1.0), 2.0);
+    assert_distance(sdf_hyperplane(Float4::new(0.0, 0.0, 0.0, -1.0), normal, 1.0), 2.0);
+
+    assert_distance(sdf_half_space(Float4::new(0.0, 0.0, 0.0, 3.0), normal, 1.0), 2.0);
+    assert_distance(sdf_half_space(ORIGIN, normal, 1.0), -1.0);
+
+    let normal: Float3 = Float3::new(0.0, 1.0, 0.0);
+    assert_distance(sdf_half_space(Float3::new(1.0, -2.0, 3.0), normal, 0.0), -2.0);
+}
+
+#[test]
+fn capsule()
+{
+    let a: Float4 = Float4::new(0.0, 0.0, 0.0, -1.0);
+    let b: Float4 = Float4::new(0.0, 0.0, 0.0, 1.0);
+
+    assert_distance(sdf_capsule(ORIGIN, a, b, 0.5), -0.5);
+    assert_distance(sdf_capsule(Float4::new(1.0, 0.0, 0.0, 0.0), a, b, 0.5), 0.5);
+    assert_distance(sdf_capsule(Float4::new(0.0, 0.0, 0.0, 3.0), a, b, 0.5), 1.5);
+    assert_distance(sdf_capsule(Float4::new(0.0, 3.0, 0.0, 5.0), a, b, 0.5), 4.5);
+
+    assert_distance(sdf_capsule(Float3::new(2.0, 0.0, 0.0), Float3::new(0.0, -1.0, 0.0), Float3::new(0.0, 1.0, 0.0), 1.0), 1.0);
+}
@@ -0,0 +1,68 @@
+//! Domain operators that warp space before a distance function is sampled.
+//!
+//! Each function maps a sample point into the space of the shape being warped, so they compose with
+//! any of the primitives in `distance_functions`. The matching `sdf` nodes wrap a child node.
+//! Axes are numbered from 0 for x to 3 for w.
+
+use crate::mathematics::multivectors::{Bivector, Rotor, Vector};
+
+/// Infinite repetition with the given period on each axis, a period of 0 leaves that axis alone
+/// The shape should fit inside a single cell centred on the origin
+pub fn repeat<V: Vector>(p: V, period: V) -> V
+{
+    return V::zip_map(p, period, |x, period| {
+        return if period == 0.0 { x } else { x - period * f32::round(x / period) };
+    });
+}
+
+/// Repetition with count copies either side of the origin on each axis
+pub fn repeat_limited<V: Vector>(p: V, period: V, count: V) -> V
+{
+    let cell: V = V::zip_map(p, period, |x, period| if period == 0.0 { 0.0 } else { f32::round(x / period) });
+    let cell: V = V::zip_map(cell, count, |cell, count| f32::clamp(cell, -count, count));
+    return p - V::zip_map(cell, period, |cell, period| cell * period);
+}
+
+/// Reflects the side of the plane behind the normal onto the side in front of it
+pub fn mirror<V: Vector>(p: V, normal: V, offset: f32) -> V
+{
+    let d: f32 = V::dot(p, normal) - offset;
+    return if d < 0.0 { p - normal * (2.0 * d) } else { p };
+}
+
+/// Mirrors every axis with a non zero component in axes, so the shape is symmetric across them
+pub fn symmetry<V: Vector>(p: V, axes: V) -> V
+{
+    return V::zip_map(p, axes, |x, axis| if axis != 0.0 { f32::abs(x) } else { x });
+}
+
+/// Stretches the shape by pulling it apart at the origin, size is the half length added on each axis
+pub fn elongate<V: Vector>(p: V, size: V) -> V
+{
+    return p - V::zip_map(p, size, |x, size| f32::clamp(x, -size, size));
+}
+
+/// Rotates in the plane by an angle proportional to the position along axis
+/// This is a twist when the axis is orthogonal to the plane and a bend when it lies in the plane
+/// Neither preserves distances so strong warps can overstep the surface
+pub fn twist<V: Vector, B: Bivector, R: Rotor<V, B>>(p: V, plane: &B, axis: usize, rate: f32) -> V
+{
+    let rotor: R = R::bivector_angle(plane, -rate * p.component(axis));
+    return rotor.rotate_vector(p);
+}
+
+/// Shrinks or grows the surface by radius, rounding every edge
+pub fn round(distance: f32, radius: f32) -> f32
+{
+    return distance - radius;
+}
+
+/// Hollows the shape into a shell of the given thickness either side of the surface
+pub fn onion(distance: f32, thickness: f32) -> f32
+{
+    return f32::abs(distance) - thickness;
+}
+
+#[cfg(test)]
+#[path = "domain_tests.rs"]
+mod tests;
@@ -0,0 +1,64 @@
+use crate::domain::*;
+use crate::mathematics::{PI, approx_equal4};
+use crate::mathematics::bivector3::Bivector3;
+use crate::mathematics::bivector4::Bivector4;
+use crate::mathematics::float3::Float3;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::rotor3::Rotor3;
+use crate::mathematics::rotor4::Rotor4;
+
+#[test]
+fn repetition()
+{
+    let period: Float4 = Float4::new(0.0, 0.0, 0.0, 4.0);
+    assert_eq!(repeat(Float4::new(1.0, 2.0, 3.0, 9.0), period), Float4::new(1.0, 2.0, 3.0, 1.0));
+    assert_eq!(repeat(Float4::new(1.0, 2.0, 3.0, -7.0), period), Float4::new(1.0, 2.0, 3.0, 1.0));
+
+    // Past the last copy the point stays relative to that copy
+    let count: Float4 = Float4::new(0.0, 0.0, 0.0, 2.0);
+    assert_eq!(repeat_limited(Float4::new(0.0, 0.0, 0.0, 9.0), period, count), Float4::new(0.0, 0.0, 0.0, 1.0));
+    assert_eq!(repeat_limited(Float4::new(0.0, 0.0, 0.0, 21.0), period, count), Float4::new(0.0, 0.0, 0.0, 13.0));
+
+    let period: Float3 = Float3::new(2.0, 0.0, 0.0);
+    assert_eq!(repeat(Float3::new(3.5, 1.0, 1.0), period), Float3::new(-0.5, 1.0, 1.0));
+}
+
+#[test]
+fn folds()
+{
+    let normal: Float4 = Float4::new(0.0, 0.0, 0.0, 1.0);
+    assert_eq!(mirror(Float4::new(1.0, 0.0, 0.0, -2.0), normal, 0.0), Float4::new(1.0, 0.0, 0.0, 2.0));
+    assert_eq!(mirror(Float4::new(1.0, 0.0, 0.0, 0.0), normal, 1.0), Float4::new(1.0, 0.0, 0.0, 2.0));
+    assert_eq!(mirror(Float4::new(1.0, 0.0, 0.0, 3.0), normal, 1.0), Float4::new(1.0, 0.0, 0.0, 3.0));
+
+    let axes: Float4 = Float4::new(1.0, 0.0, 0.0, 1.0);
+    assert_eq!(symmetry(Float4::new(-1.0, -2.0, -3.0, -4.0), axes), Float4::new(1.0, -2.0, -3.0, 4.0));
+}
+
+#[test]
+fn elongation_and_distance_modifiers()
+{
+    let size: Float4 = Float4::new(2.0, 0.0, 0.0, 0.0);
+    assert_eq!(elongate(Float4::new(1.5, 1.0, 0.0, 0.0), size), Float4::new(0.0, 1.0, 0.0, 0.0));
+    assert_eq!(elongate(Float4::new(-3.0, 1.0, 0.0, 0.0), size), Float4::new(-1.0, 1.0, 0.0, 0.0));
+
+    assert_eq!(round(1.0, 0.25), 0.75);
+    assert_eq!(onion(-1.0, 0.25), 0.75);
+    assert_eq!(onion(0.1, 0.25), -0.15);
+}
+
+#[test]
+fn twisting()
+{
+    // Twisting in XY around Z: no rotation at z = 0, a quarter turn at z = 1
+    let plane: Bivector3 = Bivector3::new(0.0, 0.0, 1.0);
+    let p: Float3 = twist::<Float3, Bivector3, Rotor3>(Float3::new(1.0, 0.0, 0.0), &plane, 2, PI / 2.0);
+    assert_eq!(p, Float3::new(1.0, 0.0, 0.0));
+    let p: Float3 = twist::<Float3, Bivector3, Rotor3>(Float3::new(1.0, 0.0, 1.0), &plane, 2, PI / 2.0);
+    assert!(f32::abs(p.x) < 1e-5 && f32::abs(f32::abs(p.y) - 1.0) < 1e-5 && f32::abs(p.z - 1.0) < 1e-5);
+
+    // Twisting in ZW around X leaves X alone and keeps the distance from the axis
+    let plane: Bivector4 = Bivector4::new(0.0, 0.0, 0.0, 0.0, 0.0, 1.0);
+    let p: Float4 = twist::<Float4, Bivector4, Rotor4>(Float4::new(2.0, 0.0, 1.0, 0.0), &plane, 0, PI / 4.0);
+    assert!(approx_equal4(p, Float4::new(2.0, 0.0, 0.0, 1.0)) || approx_equal4(p, Float4::new(2.0, 0.0, 0.0, -1.0)));
+}
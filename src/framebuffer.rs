@@ -0,0 +1,129 @@
+//! A linear RGBA float framebuffer shared between the render workers.
+//!
+//! Pixels are stored row by row from the top of the image, the same layout as SDL textures and
+//! image files. Each channel is an atomic so workers can write their tiles straight into the
+//! buffer through a shared reference, tiles never overlap so the writes never contend.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::mathematics::float3::Float3;
+
+const CHANNELS: usize = 4;
+
+pub struct Framebuffer
+{
+    width: u32,
+    height: u32,
+    /// f32 bits of each channel, RGBA
+    data: Vec<AtomicU32>,
+}
+
+impl Framebuffer
+{
+    /// A framebuffer cleared to transparent black
+    pub fn new(width: u32, height: u32) -> Framebuffer
+    {
+        let size: usize = width as usize * height as usize * CHANNELS;
+        return Framebuffer{ width: width, height: height, data: (0..size).map(|_| AtomicU32::new(0)).collect() };
+    }
+
+    pub fn width(&self) -> u32
+    {
+        return self.width;
+    }
+
+    pub fn height(&self) -> u32
+    {
+        return self.height;
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize
+    {
+        assert!(x < self.width && y < self.height, "Pixel ({}, {}) is outside the {}x{} framebuffer", x, y, self.width, self.height);
+        return (y as usize * self.width as usize + x as usize) * CHANNELS;
+    }
+
+    /// Sets an opaque pixel, y counts down from the top row
+    pub fn set(&self, x: u32, y: u32, colour: Float3)
+    {
+        self.set_rgba(x, y, [colour.x, colour.y, colour.z, 1.0]);
+    }
+
+    pub fn set_rgba(&self, x: u32, y: u32, rgba: [f32; 4])
+    {
+        let index: usize = self.index(x, y);
+        for (channel, value) in rgba.iter().enumerate()
+        {
+            self.data[index + channel].store(value.to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    pub fn get(&self, x: u32, y: u32) -> Float3
+    {
+        let [r, g, b, _] = self.get_rgba(x, y);
+        return Float3::new(r, g, b);
+    }
+
+    pub fn get_rgba(&self, x: u32, y: u32) -> [f32; 4]
+    {
+        let index: usize = self.index(x, y);
+        return [0, 1, 2, 3].map(|channel| f32::from_bits(self.data[index + channel].load(Ordering::Relaxed)));
+    }
+
+    pub fn clear(&self)
+    {
+        for channel in &self.data
+        {
+            channel.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Every pixel's colour, row by row from the top
+    pub fn colours(&self) -> Vec<Float3>
+    {
+        return (0..self.height).flat_map(|y| (0..self.width).map(move |x| (x, y))).map(|(x, y)| self.get(x, y)).collect();
+    }
+
+    /// Converts to 8 bit RGBA, written into rows of pitch bytes such as a locked SDL texture
+    pub fn write_rgba8(&self, buffer: &mut [u8], pitch: usize)
+    {
+        self.write_rgba8_area(buffer, pitch, self.width, self.height);
+    }
+
+    /// Converts the width by height area in the top left corner, for frames rendered smaller than the framebuffer
+    pub fn write_rgba8_area(&self, buffer: &mut [u8], pitch: usize, width: u32, height: u32)
+    {
+        for y in 0..height
+        {
+            let row: &mut [u8] = &mut buffer[y as usize * pitch..];
+            for x in 0..width
+            {
+                let rgba: [f32; 4] = self.get_rgba(x, y);
+                let offset: usize = x as usize * CHANNELS;
+                for channel in 0..CHANNELS
+                {
+                    row[offset + channel] = to_u8(rgba[channel]);
+                }
+            }
+        }
+    }
+
+    /// Tightly packed 8 bit RGBA
+    pub fn to_rgba8(&self) -> Vec<u8>
+    {
+        let pitch: usize = self.width as usize * CHANNELS;
+        let mut buffer: Vec<u8> = vec![0; pitch * self.height as usize];
+        self.write_rgba8(&mut buffer, pitch);
+        return buffer;
+    }
+}
+
+/// Maps 0 to 1 onto 0 to 255, values outside are clamped
+pub fn to_u8(value: f32) -> u8
+{
+    return (255.0 * value) as u8;
+}
+
+#[cfg(test)]
+#[path = "framebuffer_tests.rs"]
+mod tests;
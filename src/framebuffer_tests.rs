@@ -0,0 +1,74 @@
+use crate::framebuffer::{to_u8, Framebuffer};
+use crate::mathematics::float3::Float3;
+
+#[test]
+fn set_and_get_pixels()
+{
+    let framebuffer: Framebuffer = Framebuffer::new(3, 2);
+    assert_eq!(framebuffer.width(), 3);
+    assert_eq!(framebuffer.height(), 2);
+    assert_eq!(framebuffer.get_rgba(2, 1), [0.0, 0.0, 0.0, 0.0]);
+
+    framebuffer.set(2, 1, Float3::new(0.25, 0.5, 1.0));
+    assert_eq!(framebuffer.get(2, 1), Float3::new(0.25, 0.5, 1.0));
+    assert_eq!(framebuffer.get_rgba(2, 1)[3], 1.0);
+
+    framebuffer.clear();
+    assert_eq!(framebuffer.get_rgba(2, 1), [0.0, 0.0, 0.0, 0.0]);
+}
+
+#[test]
+#[should_panic]
+fn pixels_outside_panic()
+{
+    let framebuffer: Framebuffer = Framebuffer::new(3, 2);
+    framebuffer.set(3, 0, Float3::new(1.0, 1.0, 1.0));
+}
+
+#[test]
+fn colours_are_ordered_from_the_top_row()
+{
+    let framebuffer: Framebuffer = Framebuffer::new(2, 2);
+    framebuffer.set(1, 0, Float3::new(1.0, 0.0, 0.0));
+    framebuffer.set(0, 1, Float3::new(0.0, 1.0, 0.0));
+
+    let colours: Vec<Float3> = framebuffer.colours();
+    assert_eq!(colours.len(), 4);
+    assert_eq!(colours[1], Float3::new(1.0, 0.0, 0.0));
+    assert_eq!(colours[2], Float3::new(0.0, 1.0, 0.0));
+}
+
+#[test]
+fn rgba8_rows_follow_the_pitch()
+{
+    let framebuffer: Framebuffer = Framebuffer::new(2, 2);
+    framebuffer.set(0, 0, Float3::new(1.0, 0.5, 0.0));
+    framebuffer.set(1, 1, Float3::new(2.0, -1.0, 1.0));
+
+    // Locked textures can pad each row past the end of the image
+    let pitch: usize = 12;
+    let mut buffer: Vec<u8> = vec![7; pitch * 2];
+    framebuffer.write_rgba8(&mut buffer, pitch);
+
+    assert_eq!(&buffer[0..4], &[255, 127, 0, 255]);
+    assert_eq!(&buffer[4..8], &[0, 0, 0, 0]);
+    assert_eq!(&buffer[8..12], &[7, 7, 7, 7]);
+    assert_eq!(&buffer[pitch + 4..pitch + 8], &[255, 0, 255, 255]);
+
+    assert_eq!(framebuffer.to_rgba8().len(), 2 * 2 * 4);
+    assert_eq!(to_u8(0.5), 127);
+}
+
+#[test]
+fn rgba8_areas_start_at_the_top_left()
+{
+    let framebuffer: Framebuffer = Framebuffer::new(4, 4);
+    framebuffer.set(1, 1, Float3::new(1.0, 1.0, 1.0));
+    framebuffer.set(3, 3, Float3::new(1.0, 0.0, 0.0));
+
+    let pitch: usize = 2 * 4;
+    let mut buffer: Vec<u8> = vec![0; pitch * 2];
+    framebuffer.write_rgba8_area(&mut buffer, pitch, 2, 2);
+    assert_eq!(&buffer[pitch + 4..pitch + 8], &[255, 255, 255, 255]);
+    assert_eq!(buffer.iter().filter(|byte| **byte != 0).count(), 4);
+}
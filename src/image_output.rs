@@ -0,0 +1,124 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::framebuffer::Framebuffer;
+
+/// Writes a framebuffer to disk, the format is chosen from the file extension (.ppm or .png)
+pub fn save_image(path: &Path, framebuffer: &Framebuffer) -> Result<(), String>
+{
+    let width: u32 = framebuffer.width();
+    let height: u32 = framebuffer.height();
+    let rgb: Vec<u8> = framebuffer.to_rgba8().chunks_exact(4).flat_map(|rgba| [rgba[0], rgba[1], rgba[2]]).collect();
+
+    let extension: String = path.extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    let bytes: Vec<u8> = match extension.as_str()
+    {
+        "ppm" => encode_ppm(width, height, &rgb),
+        "png" => encode_png(width, height, &rgb),
+        _ => return Err(format!("Unsupported image format \"{}\", expected .ppm or .png", path.display()))
+    };
+
+    let file: File = File::create(path).map_err(|e| format!("Unable to create {}: {}", path.display(), e))?;
+    let mut writer: BufWriter<File> = BufWriter::new(file);
+    writer.write_all(&bytes).map_err(|e| format!("Unable to write {}: {}", path.display(), e))?;
+    writer.flush().map_err(|e| format!("Unable to write {}: {}", path.display(), e))?;
+
+    Ok(())
+}
+
+/// Binary PPM (P6)
+fn encode_ppm(width: u32, height: u32, rgb: &[u8]) -> Vec<u8>
+{
+    let mut bytes: Vec<u8> = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+    bytes.extend_from_slice(rgb);
+    return bytes;
+}
+
+/// 8 bit RGB PNG. The image data is stored in uncompressed deflate blocks so no compression library is needed
+fn encode_png(width: u32, height: u32, rgb: &[u8]) -> Vec<u8>
+{
+    let mut bytes: Vec<u8> = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
+    let mut header: Vec<u8> = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    header.extend_from_slice(&[8, 2, 0, 0, 0]); // Bit depth, colour type RGB, compression, filter, interlace
+    write_png_chunk(&mut bytes, b"IHDR", &header);
+
+    // Every scanline starts with its filter type, 0 being no filter
+    let row_length: usize = width as usize * 3;
+    let mut scanlines: Vec<u8> = Vec::with_capacity((row_length + 1) * height as usize);
+    for row in rgb.chunks(row_length)
+    {
+        scanlines.push(0);
+        scanlines.extend_from_slice(row);
+    }
+
+    // zlib stream of stored deflate blocks, each holding at most 65535 bytes
+    let mut data: Vec<u8> = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = scanlines.chunks(u16::MAX as usize).collect();
+    for (i, block) in blocks.iter().enumerate()
+    {
+        let is_final: bool = i == blocks.len() - 1;
+        let length: u16 = block.len() as u16;
+        data.push(is_final as u8);
+        data.extend_from_slice(&length.to_le_bytes());
+        data.extend_from_slice(&(!length).to_le_bytes());
+        data.extend_from_slice(block);
+    }
+    data.extend_from_slice(&adler32(&scanlines).to_be_bytes());
+    write_png_chunk(&mut bytes, b"IDAT", &data);
+
+    write_png_chunk(&mut bytes, b"IEND", &[]);
+
+    return bytes;
+}
+
+fn write_png_chunk(bytes: &mut Vec<u8>, chunk_type: &[u8; 4], data: &[u8])
+{
+    bytes.extend_from_slice(&(data.len() as u32).to_be_bytes());
+
+    let start: usize = bytes.len();
+    bytes.extend_from_slice(chunk_type);
+    bytes.extend_from_slice(data);
+    let crc: u32 = crc32(&bytes[start..]);
+
+    bytes.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn crc32(data: &[u8]) -> u32
+{
+    let mut crc: u32 = 0xFFFFFFFF;
+    for byte in data
+    {
+        crc ^= *byte as u32;
+        for _bit in 0..8
+        {
+            let mask: u32 = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB88320 & mask);
+        }
+    }
+    return !crc;
+}
+
+fn adler32(data: &[u8]) -> u32
+{
+    const MOD_ADLER: u32 = 65521;
+    let mut a: u32 = 1;
+    let mut b: u32 = 0;
+    for byte in data
+    {
+        a = (a + *byte as u32) % MOD_ADLER;
+        b = (b + a) % MOD_ADLER;
+    }
+    return (b << 16) | a;
+}
+
+#[cfg(test)]
+#[path = "image_output_tests.rs"]
+mod tests;
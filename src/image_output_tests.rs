@@ -0,0 +1,79 @@
+use crate::image_output::*;
+
+/// Splits a PNG after its signature into (type, data, crc) for each chunk
+fn chunks(png: &[u8]) -> Vec<([u8; 4], Vec<u8>, u32)>
+{
+    let mut chunks: Vec<([u8; 4], Vec<u8>, u32)> = Vec::new();
+    let mut rest: &[u8] = &png[8..];
+    while !rest.is_empty()
+    {
+        let length: usize = u32::from_be_bytes(rest[0..4].try_into().unwrap()) as usize;
+        let chunk_type: [u8; 4] = rest[4..8].try_into().unwrap();
+        let data: Vec<u8> = rest[8..8 + length].to_vec();
+        let crc: u32 = u32::from_be_bytes(rest[8 + length..12 + length].try_into().unwrap());
+        chunks.push((chunk_type, data, crc));
+        rest = &rest[12 + length..];
+    }
+    return chunks;
+}
+
+#[test]
+fn ppm_is_a_header_then_the_pixels()
+{
+    let rgb: [u8; 6] = [255, 0, 0, 0, 128, 255];
+    let ppm: Vec<u8> = encode_ppm(2, 1, &rgb);
+    assert_eq!(&ppm[..11], b"P6\n2 1\n255\n");
+    assert_eq!(&ppm[11..], &rgb);
+}
+
+#[test]
+fn checksums_match_the_reference_values()
+{
+    assert_eq!(crc32(b"123456789"), 0xCBF43926);
+    assert_eq!(crc32(b"IEND"), 0xAE426082);
+    assert_eq!(adler32(b"Wikipedia"), 0x11E60398);
+}
+
+#[test]
+fn png_chunks_carry_their_crc()
+{
+    let png: Vec<u8> = encode_png(3, 2, &[7; 18]);
+    assert_eq!(&png[..8], &[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n']);
+
+    let chunks: Vec<([u8; 4], Vec<u8>, u32)> = chunks(&png);
+    let types: Vec<&[u8; 4]> = chunks.iter().map(|(chunk_type, _, _)| chunk_type).collect();
+    assert_eq!(types, vec![b"IHDR", b"IDAT", b"IEND"]);
+    for (chunk_type, data, crc) in &chunks
+    {
+        let mut checked: Vec<u8> = chunk_type.to_vec();
+        checked.extend_from_slice(data);
+        assert_eq!(crc32(&checked), *crc, "Wrong crc on {:?}", chunk_type);
+    }
+    assert_eq!(chunks[0].1, vec![0, 0, 0, 3, 0, 0, 0, 2, 8, 2, 0, 0, 0]);
+}
+
+#[test]
+fn large_images_split_into_several_stored_blocks()
+{
+    // Each scanline is a filter byte and 600 colour bytes, 110 of them do not fit one block
+    let (width, height): (u32, u32) = (200, 110);
+    let rgb: Vec<u8> = (0..width * height * 3).map(|i| (i % 251) as u8).collect();
+    let png: Vec<u8> = encode_png(width, height, &rgb);
+    let data: Vec<u8> = chunks(&png).into_iter().find(|(chunk_type, _, _)| chunk_type == b"IDAT").unwrap().1;
+
+    let mut scanlines: Vec<u8> = Vec::new();
+    let mut finals: Vec<bool> = Vec::new();
+    let mut rest: &[u8] = &data[2..data.len() - 4];
+    while !rest.is_empty()
+    {
+        let length: u16 = u16::from_le_bytes([rest[1], rest[2]]);
+        assert_eq!(!length, u16::from_le_bytes([rest[3], rest[4]]));
+        finals.push(rest[0] == 1);
+        scanlines.extend_from_slice(&rest[5..5 + length as usize]);
+        rest = &rest[5 + length as usize..];
+    }
+    assert_eq!(finals, vec![false, true]);
+    assert_eq!(scanlines.len(), 601 * 110);
+    assert_eq!(&scanlines[..5], &[0, 0, 1, 2, 3]);
+    assert_eq!(&data[data.len() - 4..], &adler32(&scanlines).to_be_bytes());
+}
@@ -0,0 +1,30 @@
+//! A 4D raymarcher using Rotors of Geometric Algebra for rotation.
+//!
+//! The renderer runs entirely on the CPU and has no dependency on SDL2, the interactive window
+//! lives in the binary and is only built with the `sdl` feature.
+
+pub mod anti_aliasing;
+pub mod application;
+pub mod camera;
+pub mod camera_controller;
+pub mod csg;
+pub mod distance_functions;
+pub mod domain;
+pub mod framebuffer;
+pub mod image_output;
+pub mod mathematics;
+pub mod multi_view;
+pub mod polychora;
+pub mod progressive;
+pub mod raymarching;
+pub mod render_mode;
+pub mod renderer;
+pub mod resolution;
+pub mod scene;
+pub mod scene_file;
+pub mod sdf;
+pub mod slice;
+pub mod transform;
+pub mod volume;
+pub mod w_shading;
+pub mod worker_pool;
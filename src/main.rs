@@ -1,253 +1,192 @@
-use std::path::Path;
-use std::thread;
-use std::time::Instant;
-use std::sync::Mutex;
-
-use clap::Parser;
-
-extern crate sdl2;
-use sdl2::event::Event;
-use sdl2::pixels::Color;
-use sdl2::render::{Texture, WindowCanvas};
-use sdl2::rect::{Point, Rect};
-use sdl2::surface::Surface;
-use sdl2::ttf::Font;
-use sdl2::image::LoadSurface;
-
-mod application;
-use application::Application;
-
-mod camera;
-use camera::*;
-
-mod distance_functions;
-use distance_functions::*;
-
-mod mathematics;
-use mathematics::bivector3::Bivector3;
-use mathematics::bivector4::Bivector4;
-use mathematics::float2::Float2;
-use mathematics::float3::Float3;
-use mathematics::float4::Float4;
-use mathematics::multivectors::{Magnitude, Rotor, Vector};
-use mathematics::rotor3::Rotor3;
-use mathematics::rotor4::Rotor4;
-
-mod raymarching;
-
-fn get_dist3(p: Float3) -> f32
-{
-    //return sdf_sphere::<Float3>(p, Float3::new(0.0,0.0,0.0), 1.5);
-    return sdf_box3(p, Float3::new(0.0,0.0,0.0), Float3::new(1.0,1.0,1.0), 0.01)
-}
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+use clap::{ArgAction, Parser};
+
+use raymarching::anti_aliasing::SamplePattern;
+use raymarching::application::Application;
+use raymarching::camera::*;
+use raymarching::framebuffer::Framebuffer;
+use raymarching::image_output;
+use raymarching::mathematics::float3::Float3;
+use raymarching::mathematics::float4::Float4;
+use raymarching::mathematics::multivectors::Magnitude;
+use raymarching::mathematics::rotor3::Rotor3;
+use raymarching::mathematics::rotor4::Rotor4;
+use raymarching::multi_view;
+use raymarching::progressive::ProgressiveRenderer;
+use raymarching::raymarching::{MarchMethod, RaymarchSettings};
+use raymarching::render_mode::RenderMode;
+use raymarching::renderer;
+use raymarching::scene::{Scene, SubScene4};
+use raymarching::scene_file;
+use raymarching::slice::Slice;
+use raymarching::volume::{Volume, VolumeAxis};
+use raymarching::w_shading::{ColourRamp, Ghosting, WMeasure, WTint};
+use raymarching::worker_pool::WorkerPool;
+
+#[cfg(feature = "sdl")]
+mod window;
 
-fn get_dist4(p: Float4) -> f32
-{
-    //return sdf_sphere::<Float4>(p, Float4::new(0.0,0.0,0.0, 0.0), 1.5);
-    return sdf_box4(p, Float4::new(0.0,0.0,0.0, 0.0), Float4::new(1.0,1.0,1.0, 1.0), 0.01)
-}
+#[derive(Parser, Debug)]
+struct Args {
+    // Use Anti Aliasing, only pixels on edges are supersampled unless --aa-full is given
+    #[arg(long)]
+    aa: bool,
 
+    // Anti aliasing sample pattern: grid, rotated, stratified or halton
+    #[arg(long, default_value = "grid")]
+    aa_pattern: String,
 
-fn get_pixel_colour(uv: &Float2, scene: &Scene) -> Float3
-{
-    let mut colour: Float3 = Float3::new(0.0, 0.0, 0.0);
+    // Anti aliasing samples per pixel, grid based patterns round to a square number
+    #[arg(long, default_value_t = 9)]
+    aa_samples: u32,
 
+    // Supersample every pixel instead of only those on edges
+    #[arg(long)]
+    aa_full: bool,
 
-    if !scene.is4d
-    {
-        let scene_3d: &SubScene3 = scene.scene_3d.as_ref().expect("Trying to render unassigned scene"); 
-        let ro: Float3 = scene_3d.camera.get_camera_position();
-        let rd: Float3 = scene_3d.camera.get_ray_direction(*uv);
+    // Colour difference between neighbouring pixels that counts as an edge
+    #[arg(long)]
+    aa_threshold: Option<f32>,
 
-        let distance: f32 = raymarching::raymarch(&ro, &rd, get_dist3);  
-    
-        if distance <= raymarching::MAX_DIST
-        {
-            let p: Float3 = ro + (distance * rd);
-            let n: Float3 = raymarching::normal3(p, get_dist3);
-            
-            let diffuse: f32 = Float3::dot(n, (scene_3d.light_source - p).normalized()) * 0.5 + 0.5;
-            colour = Float3::new(diffuse, diffuse, diffuse);
-        }
-    }
-    else 
-    {
-        let scene_4d: &SubScene4 = scene.scene_4d.as_ref().expect("Trying to render unassigned scene"); 
-        let ro: Float4 = scene_4d.camera.get_camera_position();
-        let rd: Float4 = scene_4d.camera.get_ray_direction(*uv);
+    // Render 3D Scene
+    #[arg(long)]
+    d: bool,
 
-        let distance: f32 = raymarching::raymarch(&ro, &rd, get_dist4);  
-    
-        if distance <= raymarching::MAX_DIST
-        {
-            let p: Float4 = ro + (distance * rd);
-            let n: Float4 = raymarching::normal4(p, get_dist4);
-            
-            let diffuse: f32 = Float4::dot(n, (scene_4d.light_source - p).normalized()) * 0.5 + 0.5;
-            colour = Float3::new(diffuse, diffuse, diffuse);
-        }
-    }
+    // Render a single frame to a .ppm or .png file without opening a window
+    #[arg(long)]
+    output: Option<PathBuf>,
 
-    return colour;
-}
+    // Load the scene from a scene description file instead of the default scene
+    #[arg(long)]
+    scene: Option<PathBuf>,
 
-fn format_colour(pixel_colour: Float3) -> Color
-{
-    let draw_colour: Color = Color{
-        r: (255f32 * pixel_colour.x) as u8,
-        g: (255f32 * pixel_colour.y) as u8,
-        b: (255f32 * pixel_colour.z) as u8,
-        a: 1
-    };
+    // Raymarching quality preset: preview, default or final, replaces the scene's settings
+    #[arg(long)]
+    quality: Option<String>,
 
-    return draw_colour;
-}
+    // Override the maximum number of raymarching steps
+    #[arg(long)]
+    max_steps: Option<u32>,
 
-#[derive(Copy, Clone)]
-struct Pixel
-{
-    pub colour: Color,
-    pub x: u32,
-    pub y: u32,
-}
+    // Override the distance after which a ray is a miss
+    #[arg(long)]
+    max_distance: Option<f32>,
 
-fn render_pixel(x: u32, y: u32, scene: &Scene, application: &Application) -> Pixel
-{
-    let mut colour: Float3 = Float3::new(0.0, 0.0, 0.0);
-    
-    if !application.anti_aliasing
-    {
-        let uv: Float2 = Float2::new(
-            x as f32 / (application.width - 1) as f32,
-            y as f32 / (application.height - 1) as f32
-        );
-        colour = get_pixel_colour(&uv, &scene);
-    }
-    else 
-    {
-        let sample_offsets: [Float2; 9] = [
-            Float2::new(0.5, 0.5),
-            Float2::new(0.0, 0.5),
-            Float2::new(-0.5, 0.5),
-            Float2::new(0.5, 0.0),
-            Float2::new(0.0, 0.0),
-            Float2::new(-0.5, 0.0),
-            Float2::new(0.5, -0.5),
-            Float2::new(0.0, -0.5),
-            Float2::new(-0.5, -0.5)
-        ];
-        for sample_offset in sample_offsets
-        {
-            let uv: Float2 = Float2::new(
-                (x as f32 + sample_offset.x) / (application.width - 1) as f32,
-                (y as f32 + sample_offset.y) / (application.height - 1) as f32
-            );
-            colour += get_pixel_colour(&uv, &scene);
-        }
-        colour = colour / sample_offsets.len() as f32;
-    }
+    // Override the distance to a surface that counts as a hit
+    #[arg(long)]
+    surface_distance: Option<f32>,
 
-    return Pixel{colour: format_colour(colour), x: x, y: y};
-}
+    // Override the offset used to calculate normals
+    #[arg(long)]
+    normal_epsilon: Option<f32>,
 
-fn render(canvas: &mut WindowCanvas, scene: &Scene, application: &Application) -> Result<(), String>
-{
-    thread::scope(|s| {
-        
-        // Render using a new thread for each row of the image
-        // A thread for each pixel was had drastically more overhead. A single thread per core using thread::available_parallelism() was not enough
-        
-        let mut pixel_threads = Vec::with_capacity(application.height as usize);
-
-        for y in 0..application.height
-        {
-            pixel_threads.push(
-                s.spawn(move || 
-                    {
-                        let mut pixel_row = Vec::with_capacity(application.width as usize);
-
-                        for x in 0..application.width
-                        {
-                            pixel_row.push(render_pixel(x, y, &scene, &application));
-                        }
-
-                        return pixel_row;
-                    }
-                )
-            );
-        }
+    // Debug view: shaded, steps, depth, normals, objects or misses
+    #[arg(long)]
+    mode: Option<String>,
 
-        for thread_handle in pixel_threads
-        {
-            for pixel in thread_handle.join().unwrap()
-            {
-                canvas.set_draw_color(pixel.colour);
-                canvas.draw_point(Point::new(pixel.x as i32, application.height as i32 - pixel.y as i32)).expect("Unable to draw point to canvas");
-            }
-        }
-    });
+    // View a 3D cross section of a 4D scene, across the camera's own w axis unless --slice-normal is given
+    #[arg(long)]
+    slice: bool,
 
-    Ok(())
-}
+    // Normal of the cross section hyperplane as four numbers x y z w
+    #[arg(long, num_args = 4, allow_negative_numbers = true, value_names = ["X", "Y", "Z", "W"])]
+    slice_normal: Option<Vec<f32>>,
 
-fn update(delta_time: f64, scene: &mut Scene) -> Result<(), String>
-{
-    if scene.is4d
-    {
-        let r: Rotor4 = Rotor4::bivector_angle(&Bivector4::new(1.0, 1.0, 1.0, 1.0, 1.0, 1.0), delta_time as f32);
+    // Distance of the cross section hyperplane from the origin along its normal
+    #[arg(long, allow_negative_numbers = true)]
+    slice_offset: Option<f32>,
 
-        let scene_4d: &mut Box<SubScene4> = scene.scene_4d.as_mut().expect("Error: scene_4d not set scene.is4d is true"); 
-        scene_4d.camera.rotate_camera(r);
-        scene_4d.light_source = r * scene_4d.light_source;
-    }
-    else 
-    {
-        let r: Rotor3 = Rotor3::bivector_angle(&Bivector3::new(1.0, 1.0, 1.0), delta_time as f32);
+    // Split the image into the XYZ, XYW, XZW and YZW cross sections of a 4D scene
+    #[arg(long)]
+    views: bool,
 
-        let scene_3d: &mut Box<SubScene3> = scene.scene_3d.as_mut().expect("Error: scene_3d not set scene.is4d is false"); 
-        scene_3d.camera.rotate_camera(r);
-        scene_3d.light_source = r * scene_3d.light_source;
-    }
+    // Split the image into cross sections across these normals instead, given as x y z w up to four times
+    #[arg(long, num_args = 4, action = ArgAction::Append, allow_negative_numbers = true, value_names = ["X", "Y", "Z", "W"])]
+    view_normal: Vec<f32>,
 
-    Ok(())
-}
+    // Colour 4D hits by a colour ramp of their w coordinate (w) or their distance from the view's hyperplane (slice)
+    #[arg(long)]
+    w_tint: Option<String>,
 
-#[derive(Parser, Debug)]
-struct Args {
-    // Use Anti Aliasing
+    // Colour ramp of the w tint: coolwarm, heat, viridis or grey
+    #[arg(long, default_value = "coolwarm", requires = "w_tint")]
+    w_ramp: String,
+
+    // Distance along w either side of 0 the whole colour ramp covers
+    #[arg(long, requires = "w_tint")]
+    w_range: Option<f32>,
+
+    // Show faint ghosts of the geometry just off the view's hyperplane
     #[arg(long)]
-    aa: bool,
+    ghost: bool,
 
-    // Render 3D Scene
+    // Ghost layers on each side of the hyperplane
     #[arg(long)]
-    d: bool
-}
+    ghost_layers: Option<u32>,
 
-struct Time
-{
-    pub application_up_time: f64,
-    pub frame_delta_time: f64,
-}
+    // Distance between neighbouring ghost layers
+    #[arg(long)]
+    ghost_spacing: Option<f32>,
 
-static APPLICATION_TIME: Mutex<Time> = Mutex::new(Time{application_up_time: 0.0, frame_delta_time: 0.0});
+    // Opacity the ghosts fade out from
+    #[arg(long)]
+    ghost_opacity: Option<f32>,
 
-struct Scene
-{
-    pub is4d: bool,
-    pub scene_3d: Option<Box<SubScene3>>,
-    pub scene_4d: Option<Box<SubScene4>>,
-}
+    // X-ray view of a 4D scene, integrating the density of each object along an axis across the view
+    #[arg(long)]
+    volume: bool,
 
-struct SubScene3
-{
-    pub camera: Camera3,
-    pub light_source: Float3,
+    // Axis density is integrated along: the camera's hidden axis (hidden) or the world's w axis (w)
+    #[arg(long, default_value = "hidden", requires = "volume")]
+    volume_axis: String,
+
+    // Density is integrated from minus to plus this distance along the axis
+    #[arg(long, requires = "volume")]
+    volume_extent: Option<f32>,
+
+    // Points along the axis the density is taken from
+    #[arg(long, requires = "volume")]
+    volume_samples: Option<u32>,
+
+    // Distance between the points each ray takes the density from
+    #[arg(long, requires = "volume")]
+    volume_step: Option<f32>,
+
+    // How quickly density hides what is behind it
+    #[arg(long, requires = "volume")]
+    volume_absorption: Option<f32>,
+
+    // Raymarching method: sphere, relaxed or accelerated
+    #[arg(long)]
+    march: Option<String>,
+
+    // Relaxation factor for the relaxed and accelerated methods
+    #[arg(long)]
+    relaxation: Option<f32>,
+
+    // Number of render threads, defaults to the number of available cores
+    #[arg(long)]
+    threads: Option<usize>,
+
+    // Frame rate to hold in the window by lowering the render resolution, which is upscaled to the window
+    #[arg(long)]
+    target_fps: Option<f32>,
+
+    // Refine the image in passes while the view is still, accumulating anti aliasing samples
+    #[arg(long)]
+    progressive: bool,
+
+    // Samples per pixel progressive rendering stops at
+    #[arg(long, default_value_t = 16)]
+    samples: u32,
 }
 
-struct SubScene4
+/// Whether a flag's value is a finite number more than 0, NaN is not
+fn is_positive(value: f32) -> bool
 {
-    pub camera: Camera4,
-    pub light_source: Float4,
+    return value > 0.0 && value.is_finite();
 }
 
 fn main() -> Result<(), String>
@@ -256,106 +195,196 @@ fn main() -> Result<(), String>
     let use_anti_aliasing: bool = args.aa;
     let render_4d: bool = !args.d;
 
-    let application: Application = Application::new(16.0 / 9.0, 480, use_anti_aliasing);
+    let mut application: Application = Application::new(16.0 / 9.0, 480, use_anti_aliasing);
+    if let Some(anti_aliasing) = &mut application.anti_aliasing
+    {
+        anti_aliasing.pattern = SamplePattern::from_name(&args.aa_pattern)
+            .ok_or_else(|| format!("Unknown anti aliasing pattern \"{}\", expected {}", args.aa_pattern, SamplePattern::NAMES))?;
+        if args.aa_samples == 0
+        {
+            return Err(String::from("--aa-samples must be at least 1"));
+        }
+        anti_aliasing.samples = args.aa_samples;
+        anti_aliasing.adaptive = !args.aa_full;
+        anti_aliasing.colour_threshold = args.aa_threshold.unwrap_or(anti_aliasing.colour_threshold);
+    }
+    if args.progressive && args.samples == 0
+    {
+        return Err(String::from("--samples must be at least 1"));
+    }
+    if args.target_fps.is_some_and(|fps| fps.is_nan() || fps <= 0.0)
+    {
+        return Err(String::from("--target-fps must be greater than 0"));
+    }
+    if args.progressive && args.target_fps.is_some()
+    {
+        return Err(String::from("--progressive and --target-fps can not be used together"));
+    }
 
     // Set up Camera and other Scene components
     let z_offset: f32 = 4.0;
     let vfov: f32 = 70.0;
     let focal_length: f32 = 1.0;
 
-    let mut scene = Scene{
-        is4d: render_4d,
-        scene_3d: if !render_4d {
-            Some(Box::new(SubScene3 
-                { 
-                    camera: Camera3::new(Float3::new(0.0, 0.0, 0.0), Float3::new(0.0, 0.0, z_offset), Rotor3::IDENTITY, application.aspect_ratio, vfov, focal_length), 
-                    light_source: Float3::new(2.0, 2.0, 4.0)
-                }))
-        } else { None },
-        scene_4d: if render_4d {
-            Some(Box::new(SubScene4 
-                { 
-                    camera: Camera4::new(Float4::new(0.0, 0.0, 0.0, 0.0), Float4::new(0.0, 0.0, z_offset, 0.0), Rotor4::IDENTITY, application.aspect_ratio, vfov, focal_length), 
-                    light_source: Float4::new(2.0, 2.0, 4.0, 0.0)
-                }))
-        } else { None },
+    let mut scene: Scene = if let Some(scene_path) = &args.scene {
+        scene_file::load_scene(scene_path, application.aspect_ratio)?
+    } else if render_4d {
+        Scene::new_4d(
+            Camera4::new(Float4::new(0.0, 0.0, 0.0, 0.0), Float4::new(0.0, 0.0, z_offset, 0.0), Rotor4::IDENTITY, application.aspect_ratio, vfov, focal_length), 
+            Float4::new(2.0, 2.0, 4.0, 0.0)
+        )
+    } else {
+        Scene::new_3d(
+            Camera3::new(Float3::new(0.0, 0.0, 0.0), Float3::new(0.0, 0.0, z_offset), Rotor3::IDENTITY, application.aspect_ratio, vfov, focal_length), 
+            Float3::new(2.0, 2.0, 4.0)
+        )
     };
 
-    // Create Window
-    let sdl_context = sdl2::init().unwrap();
-    let video_subsystem = sdl_context.video().unwrap();
+    // Command line settings take priority over the scene file
+    if let Some(quality) = &args.quality
+    {
+        scene.raymarch_settings = RaymarchSettings::preset(quality)
+            .ok_or_else(|| format!("Unknown quality \"{}\", expected {}", quality, RaymarchSettings::PRESET_NAMES))?;
+    }
+    if args.slice || args.slice_normal.is_some() || args.slice_offset.is_some()
+    {
+        let scene_4d: &mut SubScene4 = scene.scene_4d.as_deref_mut().filter(|_| scene.is4d)
+            .ok_or_else(|| String::from("Cross sections only work with 4D scenes"))?;
+        let mut slice: Slice = Slice::from_camera(&scene_4d.camera);
+        if let Some(normal) = &args.slice_normal
+        {
+            let normal: Float4 = Float4::new(normal[0], normal[1], normal[2], normal[3]);
+            if normal.length() == 0.0
+            {
+                return Err(String::from("--slice-normal can not be zero"));
+            }
+            slice = Slice::new(normal, 0.0);
+        }
+        slice.offset = args.slice_offset.unwrap_or(slice.offset);
+        scene_4d.slice = Some(slice);
+    }
+    if args.views || !args.view_normal.is_empty()
+    {
+        let scene_4d: &mut SubScene4 = scene.scene_4d.as_deref_mut().filter(|_| scene.is4d)
+            .ok_or_else(|| String::from("Split views only work with 4D scenes"))?;
+        let mut turns: Vec<Rotor4> = multi_view::axis_slice_turns();
+        if !args.view_normal.is_empty()
+        {
+            let normals: Vec<Float4> = args.view_normal.chunks(4).map(|n| Float4::new(n[0], n[1], n[2], n[3])).collect();
+            if normals.len() > multi_view::MAX_VIEWS
+            {
+                return Err(format!("At most {} --view-normal can be given", multi_view::MAX_VIEWS));
+            }
+            if normals.iter().any(|normal| normal.length() == 0.0)
+            {
+                return Err(String::from("--view-normal can not be zero"));
+            }
+            turns = normals.into_iter().map(multi_view::turn_to_normal).collect();
+        }
+        scene_4d.views = multi_view::turned_views(&scene_4d.camera, &turns);
+    }
 
-    let mut window = video_subsystem.window("4D Raymarching", application.width, application.height)
-        .position_centered()
-        .build()
-        .unwrap();
+    if let Some(measure) = &args.w_tint
+    {
+        let scene_4d: &mut SubScene4 = scene.scene_4d.as_deref_mut().filter(|_| scene.is4d)
+            .ok_or_else(|| String::from("W tints only work with 4D scenes"))?;
+        let mut tint: WTint = WTint::DEFAULT;
+        tint.measure = WMeasure::from_name(measure)
+            .ok_or_else(|| format!("Unknown w tint \"{}\", expected {}", measure, WMeasure::NAMES))?;
+        tint.ramp = ColourRamp::from_name(&args.w_ramp)
+            .ok_or_else(|| format!("Unknown colour ramp \"{}\", expected {}", args.w_ramp, ColourRamp::NAMES))?;
+        tint.range = args.w_range.unwrap_or(tint.range);
+        if !is_positive(tint.range)
+        {
+            return Err(String::from("--w-range must be more than 0"));
+        }
+        scene_4d.w_tint = Some(tint);
+    }
+    if args.ghost || args.ghost_layers.is_some() || args.ghost_spacing.is_some() || args.ghost_opacity.is_some()
+    {
+        let scene_4d: &mut SubScene4 = scene.scene_4d.as_deref_mut().filter(|_| scene.is4d)
+            .ok_or_else(|| String::from("Ghosting only works with 4D scenes"))?;
+        let mut ghosting: Ghosting = Ghosting::DEFAULT;
+        ghosting.layers = args.ghost_layers.unwrap_or(ghosting.layers);
+        ghosting.spacing = args.ghost_spacing.unwrap_or(ghosting.spacing);
+        if !is_positive(ghosting.spacing)
+        {
+            return Err(String::from("--ghost-spacing must be more than 0"));
+        }
+        ghosting.opacity = f32::clamp(args.ghost_opacity.unwrap_or(ghosting.opacity), 0.0, 1.0);
+        scene_4d.ghosting = Some(ghosting);
+    }
 
-    window.set_resizable(false);
+    if args.volume
+    {
+        let scene_4d: &mut SubScene4 = scene.scene_4d.as_deref_mut().filter(|_| scene.is4d)
+            .ok_or_else(|| String::from("Volumes only work with 4D scenes"))?;
+        let mut volume: Volume = Volume::DEFAULT;
+        volume.axis = VolumeAxis::from_name(&args.volume_axis)
+            .ok_or_else(|| format!("Unknown volume axis \"{}\", expected {}", args.volume_axis, VolumeAxis::NAMES))?;
+        volume.extent = args.volume_extent.unwrap_or(volume.extent);
+        volume.samples = args.volume_samples.unwrap_or(volume.samples);
+        volume.step = args.volume_step.unwrap_or(volume.step);
+        volume.absorption = args.volume_absorption.unwrap_or(volume.absorption);
+        if volume.samples == 0 || !is_positive(volume.step) || !is_positive(volume.extent) || !is_positive(volume.absorption)
+        {
+            return Err(String::from("--volume-samples, --volume-step, --volume-extent and --volume-absorption must be more than 0"));
+        }
+        scene_4d.volume = Some(volume);
+    }
 
-    match Surface::from_file("assets/ClientIcon.ico")
+    if let Some(mode) = &args.mode
+    {
+        scene.render_mode = RenderMode::from_name(mode)
+            .ok_or_else(|| format!("Unknown render mode \"{}\", expected {}", mode, RenderMode::NAMES))?;
+    }
+    let settings: &mut RaymarchSettings = &mut scene.raymarch_settings;
+    settings.max_steps = args.max_steps.unwrap_or(settings.max_steps);
+    settings.max_distance = args.max_distance.unwrap_or(settings.max_distance);
+    settings.surface_distance = args.surface_distance.unwrap_or(settings.surface_distance);
+    settings.normal_epsilon = args.normal_epsilon.unwrap_or(settings.normal_epsilon);
+    if settings.max_steps == 0
+    {
+        return Err(String::from("--max-steps must be at least 1"));
+    }
+    if [settings.max_distance, settings.surface_distance, settings.normal_epsilon].iter().any(|value| !(*value > 0.0 && value.is_finite()))
+    {
+        return Err(String::from("--max-distance, --surface-distance and --normal-epsilon must be finite and more than 0"));
+    }
+    if let Some(march) = &args.march
     {
-        Err(msg) => { print!("Error: Unable to create Surface from specified file. {}", msg) }
-        Ok(window_icon) => { window.set_icon(window_icon) }
+        settings.method = MarchMethod::from_name(march)
+            .ok_or_else(|| format!("Unknown march method \"{}\", expected {}", march, MarchMethod::NAMES))?;
+    }
+    if let Some(relaxation) = args.relaxation
+    {
+        settings.method = settings.method.with_factor(relaxation).map_err(|error| format!("--relaxation: {}", error))?;
     }
 
-    let mut canvas = window.into_canvas().build().unwrap();
-    let texture_creator = canvas.texture_creator();
-
-    // Prepare Fonts
-    let ttf_context = sdl2::ttf::init().unwrap();
-    let font_path: &Path = Path::new(&"assets/CascadiaCode.ttf");
-    let font: Font = ttf_context.load_font(font_path, 12)?;
-
-    let mut event_pump = sdl_context.event_pump()?;
-    
-    let mut fps_text: String;
-    let mut fps_text_surface: Surface;
-    let mut fps_text_texture: Texture;
-    let mut fps_text_rect: Rect;
-
-    let applciation_start_time: Instant = Instant::now();
-    'running: loop {
-        
-        let frame_start_time: Instant = Instant::now();
-        
-        // Handle Events
-        for event in event_pump.poll_iter() {
-            match event {
-                Event::Quit {..} => {
-                    break 'running;
-                },
-                _ => {}
-            }
-        }
-        
-        // Update any other logic
-        let delta_time: f64 = APPLICATION_TIME.lock().unwrap().frame_delta_time;
-        update(delta_time, &mut scene)?;
-        
-        // Render
-        canvas.clear();
-        render(&mut canvas, &scene, &application)?;
-        
-        // Render FPS Text
-        fps_text = format!("{:.1}fps {:.6}s", 1.0f64 / delta_time, delta_time);
-        fps_text_surface = font.render(&fps_text).blended(Color::RGBA(255, 255, 255, 255)).unwrap();
-        let width: u32 = fps_text_surface.width();
-        let height: u32 = fps_text_surface.height();
-        fps_text_texture = texture_creator.create_texture_from_surface(fps_text_surface).unwrap();
-        fps_text_rect = Rect::new(application.width as i32 - width as i32,
-                                  application.height as i32 - height as i32,
-                                  width,
-                                  height);
-        canvas.copy(&fps_text_texture, None, Some(fps_text_rect))?;
-        
-        // Present full image
-        canvas.present();
-        
-        // Update time
-        let mut time = APPLICATION_TIME.lock().unwrap();
-        time.application_up_time = applciation_start_time.elapsed().as_secs_f64();
-        time.frame_delta_time = frame_start_time.elapsed().as_secs_f64();
+    let pool: WorkerPool = WorkerPool::new(args.threads.unwrap_or(0));
+    let scene: Arc<RwLock<Scene>> = Arc::new(RwLock::new(scene));
+
+    // Render a single frame to file and exit
+    if let Some(output_path) = args.output
+    {
+        let framebuffer: Framebuffer = if args.progressive {
+            let framebuffer: Arc<Framebuffer> = Arc::new(Framebuffer::new(application.width, application.height));
+            ProgressiveRenderer::new(args.samples).render_all(&pool, &scene, &application, &framebuffer);
+            Arc::try_unwrap(framebuffer).ok().expect("Framebuffer is still shared after rendering")
+        } else {
+            renderer::render_image(&pool, &scene, &application)
+        };
+        image_output::save_image(&output_path, &framebuffer)?;
+        println!("Saved {}x{} frame to {}", application.width, application.height, output_path.display());
+        return Ok(());
     }
 
-    Ok(())
+    #[cfg(feature = "sdl")]
+    return window::run(scene, application, pool, window::WindowOptions{
+        target_fps: args.target_fps,
+        progressive_samples: if args.progressive { Some(args.samples) } else { None },
+    });
+
+    #[cfg(not(feature = "sdl"))]
+    return Err(String::from("Built without the sdl feature, use --output <path> to render to a file"));
 }
@@ -4,6 +4,11 @@ pub mod float2;
 pub mod float3;
 pub mod float4;
 
+pub mod f32x8;
+pub mod float2x8;
+pub mod float3x8;
+pub mod float4x8;
+
 pub mod bivector3;
 pub mod bivector4;
 
@@ -14,10 +19,10 @@ pub mod rotor4;
 #[allow(dead_code)]
 pub const DEGREES_TO_RADIANS: f32 =  0.01745329;
 
-#[allow(dead_code)]
+#[allow(dead_code, clippy::excessive_precision)]
 pub const RADIANS_TO_DEGREES: f32 = 57.29577951;
 
-#[allow(dead_code)]
+#[allow(dead_code, clippy::approx_constant, clippy::excessive_precision)]
 pub const PI: f32 = 3.14159265358979323846264338327950;
 
 #[allow(dead_code)]
@@ -39,3 +44,9 @@ pub fn approx_equal(x: f32, y: f32) -> bool
 { 
     return f32::abs(x-y) < 1e-6; 
 }
+
+#[allow(dead_code)]
+pub fn approx_equal4(a: float4::Float4, b: float4::Float4) -> bool
+{
+    return f32::abs(a.x-b.x) < 1e-5 && f32::abs(a.y-b.y) < 1e-5 && f32::abs(a.z-b.z) < 1e-5 && f32::abs(a.w-b.w) < 1e-5;
+}
@@ -0,0 +1,255 @@
+//! Eight f32 lanes processed together, the building block of the structure of arrays packet types.
+//!
+//! The lanes are plain arrays and every operation is a fixed length loop over them, which the
+//! compiler turns into SIMD instructions without needing any platform specific code.
+
+use std::ops::{Add, AddAssign, BitAnd, BitOr, Div, Mul, MulAssign, Neg, Not, Sub, SubAssign};
+
+/// Number of lanes in a packet
+pub const LANES: usize = 8;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct F32x8(pub [f32; LANES]);
+
+/// One flag per lane, used to pick lanes and to track which rays are still marching
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Mask8(pub [bool; LANES]);
+
+impl F32x8
+{
+    pub const ZERO: F32x8 = F32x8([0.0; LANES]);
+
+    pub fn splat(value: f32) -> F32x8
+    {
+        return F32x8([value; LANES]);
+    }
+
+    pub fn from_fn(f: impl Fn(usize) -> f32) -> F32x8
+    {
+        return F32x8(std::array::from_fn(f));
+    }
+
+    pub fn lane(&self, i: usize) -> f32
+    {
+        return self.0[i];
+    }
+
+    pub fn map(self, f: impl Fn(f32) -> f32) -> F32x8
+    {
+        return F32x8(self.0.map(f));
+    }
+
+    pub fn zip_map(a: F32x8, b: F32x8, f: impl Fn(f32, f32) -> f32) -> F32x8
+    {
+        return F32x8::from_fn(|i| f(a.0[i], b.0[i]));
+    }
+
+    pub fn abs(self) -> F32x8
+    {
+        return self.map(f32::abs);
+    }
+
+    pub fn sqrt(self) -> F32x8
+    {
+        return self.map(f32::sqrt);
+    }
+
+    pub fn min(a: F32x8, b: F32x8) -> F32x8
+    {
+        return F32x8::zip_map(a, b, f32::min);
+    }
+
+    pub fn max(a: F32x8, b: F32x8) -> F32x8
+    {
+        return F32x8::zip_map(a, b, f32::max);
+    }
+
+    pub fn clamp(self, min: f32, max: f32) -> F32x8
+    {
+        return self.map(|x| f32::clamp(x, min, max));
+    }
+
+    /// Lanes of a where the mask is set, lanes of b elsewhere
+    pub fn select(mask: Mask8, a: F32x8, b: F32x8) -> F32x8
+    {
+        return F32x8::from_fn(|i| if mask.0[i] { a.0[i] } else { b.0[i] });
+    }
+
+    pub fn less_than(self, other: F32x8) -> Mask8
+    {
+        return Mask8(std::array::from_fn(|i| self.0[i] < other.0[i]));
+    }
+
+    pub fn greater_than(self, other: F32x8) -> Mask8
+    {
+        return Mask8(std::array::from_fn(|i| self.0[i] > other.0[i]));
+    }
+}
+
+impl Mask8
+{
+    pub const ALL: Mask8 = Mask8([true; LANES]);
+    pub const NONE: Mask8 = Mask8([false; LANES]);
+
+    pub fn from_fn(f: impl Fn(usize) -> bool) -> Mask8
+    {
+        return Mask8(std::array::from_fn(f));
+    }
+
+    pub fn lane(&self, i: usize) -> bool
+    {
+        return self.0[i];
+    }
+
+    pub fn any(&self) -> bool
+    {
+        return self.0.iter().any(|lane| *lane);
+    }
+
+    pub fn all(&self) -> bool
+    {
+        return self.0.iter().all(|lane| *lane);
+    }
+}
+
+// F32x8 + F32x8
+impl Add for F32x8 {
+    type Output = F32x8;
+
+    fn add(self, v: F32x8) -> F32x8 {
+        return F32x8::zip_map(self, v, |a, b| a + b);
+    }
+}
+
+// F32x8 + f32
+impl Add<f32> for F32x8 {
+    type Output = F32x8;
+
+    fn add(self, v: f32) -> F32x8 {
+        return self.map(|a| a + v);
+    }
+}
+
+// F32x8 += F32x8
+impl AddAssign for F32x8 {
+    fn add_assign(&mut self, v: F32x8) {
+        *self = *self + v;
+    }
+}
+
+// F32x8 - F32x8
+impl Sub for F32x8 {
+    type Output = F32x8;
+
+    fn sub(self, v: F32x8) -> F32x8 {
+        return F32x8::zip_map(self, v, |a, b| a - b);
+    }
+}
+
+// F32x8 - f32
+impl Sub<f32> for F32x8 {
+    type Output = F32x8;
+
+    fn sub(self, v: f32) -> F32x8 {
+        return self.map(|a| a - v);
+    }
+}
+
+// F32x8 -= F32x8
+impl SubAssign for F32x8 {
+    fn sub_assign(&mut self, v: F32x8) {
+        *self = *self - v;
+    }
+}
+
+// -F32x8
+impl Neg for F32x8 {
+    type Output = F32x8;
+
+    fn neg(self) -> F32x8 {
+        return self.map(|a| -a);
+    }
+}
+
+// F32x8 * F32x8
+impl Mul for F32x8 {
+    type Output = F32x8;
+
+    fn mul(self, v: F32x8) -> F32x8 {
+        return F32x8::zip_map(self, v, |a, b| a * b);
+    }
+}
+
+// F32x8 * f32
+impl Mul<f32> for F32x8 {
+    type Output = F32x8;
+
+    fn mul(self, v: f32) -> F32x8 {
+        return self.map(|a| a * v);
+    }
+}
+
+// f32 * F32x8
+impl Mul<F32x8> for f32 {
+    type Output = F32x8;
+
+    fn mul(self, v: F32x8) -> F32x8 {
+        return v.map(|b| self * b);
+    }
+}
+
+// F32x8 *= F32x8
+impl MulAssign for F32x8 {
+    fn mul_assign(&mut self, v: F32x8) {
+        *self = *self * v;
+    }
+}
+
+// F32x8 / F32x8
+impl Div for F32x8 {
+    type Output = F32x8;
+
+    fn div(self, v: F32x8) -> F32x8 {
+        return F32x8::zip_map(self, v, |a, b| a / b);
+    }
+}
+
+// F32x8 / f32
+impl Div<f32> for F32x8 {
+    type Output = F32x8;
+
+    fn div(self, v: f32) -> F32x8 {
+        return self.map(|a| a / v);
+    }
+}
+
+// Mask8 & Mask8
+impl BitAnd for Mask8 {
+    type Output = Mask8;
+
+    fn bitand(self, m: Mask8) -> Mask8 {
+        return Mask8::from_fn(|i| self.0[i] && m.0[i]);
+    }
+}
+
+// Mask8 | Mask8
+impl BitOr for Mask8 {
+    type Output = Mask8;
+
+    fn bitor(self, m: Mask8) -> Mask8 {
+        return Mask8::from_fn(|i| self.0[i] || m.0[i]);
+    }
+}
+
+// !Mask8
+impl Not for Mask8 {
+    type Output = Mask8;
+
+    fn not(self) -> Mask8 {
+        return Mask8(self.0.map(|lane| !lane));
+    }
+}
+
+#[cfg(test)]
+#[path = "f32x8_tests.rs"]
+mod tests;
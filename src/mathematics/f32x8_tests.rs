@@ -0,0 +1,90 @@
+use crate::mathematics::approx_equal;
+use crate::mathematics::bivector3::Bivector3;
+use crate::mathematics::bivector4::Bivector4;
+use crate::mathematics::f32x8::{F32x8, Mask8, LANES};
+use crate::mathematics::float3::Float3;
+use crate::mathematics::float3x8::Float3x8;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::float4x8::Float4x8;
+use crate::mathematics::multivectors::{Magnitude, Rotor, Vector, VectorPacket};
+use crate::mathematics::rotor3::Rotor3;
+use crate::mathematics::rotor4::Rotor4;
+
+fn sample_points4() -> [Float4; LANES]
+{
+    return std::array::from_fn(|i| Float4::new(i as f32 - 3.0, 0.5 * i as f32, 2.0 - i as f32, 0.25 * (i * i) as f32));
+}
+
+#[test]
+fn lane_arithmetic()
+{
+    let a: F32x8 = F32x8::from_fn(|i| i as f32);
+    let b: F32x8 = F32x8::splat(2.0);
+
+    assert_eq!((a + b).lane(3), 5.0);
+    assert_eq!((a - b).lane(3), 1.0);
+    assert_eq!((a * b).lane(3), 6.0);
+    assert_eq!((a / b).lane(3), 1.5);
+    assert_eq!((-a).lane(3), -3.0);
+    assert_eq!((a - 4.0).abs().lane(1), 3.0);
+    assert_eq!(F32x8::min(a, b).lane(7), 2.0);
+    assert_eq!(F32x8::max(a, b).lane(0), 2.0);
+    assert_eq!(a.clamp(1.0, 5.0), F32x8([1.0, 1.0, 2.0, 3.0, 4.0, 5.0, 5.0, 5.0]));
+}
+
+#[test]
+fn masks_select_lanes()
+{
+    let a: F32x8 = F32x8::from_fn(|i| i as f32);
+    let below: Mask8 = a.less_than(F32x8::splat(3.0));
+    let above: Mask8 = a.greater_than(F32x8::splat(5.0));
+
+    assert_eq!(below, Mask8([true, true, true, false, false, false, false, false]));
+    assert!(below.any() && !below.all());
+    assert!(!(below & above).any());
+    assert_eq!(!(below | above), Mask8([false, false, false, true, true, true, false, false]));
+    assert!((Mask8::ALL & !Mask8::NONE).all());
+
+    let selected: F32x8 = F32x8::select(below, a, F32x8::splat(-1.0));
+    assert_eq!(selected, F32x8([0.0, 1.0, 2.0, -1.0, -1.0, -1.0, -1.0, -1.0]));
+}
+
+#[test]
+fn packets_match_scalar_vectors()
+{
+    let points: [Float4; LANES] = sample_points4();
+    let packet: Float4x8 = Float4x8::from_lanes(points);
+    let offset: Float4 = Float4::new(1.0, -2.0, 0.5, 3.0);
+
+    let moved: Float4x8 = (packet - Float4x8::splat(offset)) * 2.0;
+    let lengths: F32x8 = packet.length();
+    let dots: F32x8 = Float4x8::dot(packet, Float4x8::splat(offset));
+    for (i, point) in points.iter().enumerate()
+    {
+        assert_eq!(packet.lane(i), *point);
+        assert_eq!(moved.lane(i), (*point - offset) * 2.0);
+        assert!(approx_equal(lengths.lane(i), point.length()));
+        assert!(approx_equal(dots.lane(i), Float4::dot(*point, offset)));
+        assert!(approx_equal(packet.component(3).lane(i), point.component(3)));
+    }
+}
+
+#[test]
+fn rotors_rotate_packets()
+{
+    let r4: Rotor4 = Rotor4::bivector_angle(&Bivector4::new(0.3, -0.2, 0.5, 0.7, 0.1, -0.4).normalized(), 1.1);
+    let points: [Float4; LANES] = sample_points4();
+    let rotated: Float4x8 = r4 * Float4x8::from_lanes(points);
+    for (i, point) in points.iter().enumerate()
+    {
+        assert_eq!(rotated.lane(i), r4 * *point);
+    }
+
+    let r3: Rotor3 = Rotor3::bivector_angle(&Bivector3::new(0.2, 0.9, -0.4).normalized(), 0.7);
+    let points: [Float3; LANES] = std::array::from_fn(|i| Float3::new(i as f32, 1.0 - i as f32, 0.5));
+    let rotated: Float3x8 = r3 * Float3x8::from_lanes(points);
+    for (i, point) in points.iter().enumerate()
+    {
+        assert_eq!(rotated.lane(i), r3 * *point);
+    }
+}
@@ -3,6 +3,7 @@ use std::fmt::{Display, Formatter, Result};
 use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
 
 use super::approx_equal;
+use super::float2x8::Float2x8;
 use super::float3::Float3;
 use super::float4::Float4;
 use super::multivectors::{Magnitude, Vector};
@@ -24,10 +25,29 @@ impl Float2
 
 impl Vector for Float2 
 {
+    const DIMENSIONS: usize = 2;
+
+    type Packet = Float2x8;
+
     fn dot(u: Float2, v: Float2) -> f32
     {
         return u.x * v.x + u.y * v.y;
     }
+
+    fn component(&self, i: usize) -> f32
+    {
+        return match i
+        {
+            0 => self.x,
+            1 => self.y,
+            _ => panic!("Float2 has no component {}", i),
+        };
+    }
+
+    fn zip_map(u: Float2, v: Float2, f: impl Fn(f32, f32) -> f32) -> Float2
+    {
+        return Float2::new(f(u.x, v.x), f(u.y, v.y));
+    }
 }
 
 impl Magnitude for Float2
@@ -0,0 +1,124 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use super::f32x8::{F32x8, LANES};
+use super::float2::Float2;
+use super::multivectors::VectorPacket;
+
+/// Eight Float2s stored as one lane array per component
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Float2x8
+{
+    pub x: F32x8,
+    pub y: F32x8,
+}
+
+impl Float2x8
+{
+    pub fn new(x: F32x8, y: F32x8) -> Float2x8
+    {
+        return Float2x8{ x: x, y: y };
+    }
+}
+
+impl VectorPacket for Float2x8
+{
+    type Scalar = Float2;
+
+    fn splat(v: Float2) -> Float2x8
+    {
+        return Float2x8::new(F32x8::splat(v.x), F32x8::splat(v.y));
+    }
+
+    fn from_lanes(lanes: [Float2; LANES]) -> Float2x8
+    {
+        return Float2x8::new(F32x8::from_fn(|i| lanes[i].x), F32x8::from_fn(|i| lanes[i].y));
+    }
+
+    fn lane(&self, i: usize) -> Float2
+    {
+        return Float2::new(self.x.lane(i), self.y.lane(i));
+    }
+
+    fn dot(u: Float2x8, v: Float2x8) -> F32x8
+    {
+        return u.x * v.x + u.y * v.y;
+    }
+
+    fn component(&self, i: usize) -> F32x8
+    {
+        return match i
+        {
+            0 => self.x,
+            1 => self.y,
+            _ => panic!("Float2x8 has no component {}", i),
+        };
+    }
+
+    fn zip_map(u: Float2x8, v: Float2x8, f: impl Fn(F32x8, F32x8) -> F32x8) -> Float2x8
+    {
+        return Float2x8::new(f(u.x, v.x), f(u.y, v.y));
+    }
+}
+
+// Float2x8 + Float2x8
+impl Add for Float2x8 {
+    type Output = Float2x8;
+
+    fn add(self, v: Float2x8) -> Float2x8 {
+        return Float2x8::new(self.x + v.x, self.y + v.y);
+    }
+}
+
+// Float2x8 - Float2x8
+impl Sub for Float2x8 {
+    type Output = Float2x8;
+
+    fn sub(self, v: Float2x8) -> Float2x8 {
+        return Float2x8::new(self.x - v.x, self.y - v.y);
+    }
+}
+
+// -Float2x8
+impl Neg for Float2x8 {
+    type Output = Float2x8;
+
+    fn neg(self) -> Float2x8 {
+        return Float2x8::new(-self.x, -self.y);
+    }
+}
+
+// Float2x8 * F32x8
+impl Mul<F32x8> for Float2x8 {
+    type Output = Float2x8;
+
+    fn mul(self, v: F32x8) -> Float2x8 {
+        return Float2x8::new(self.x * v, self.y * v);
+    }
+}
+
+// Float2x8 * f32
+impl Mul<f32> for Float2x8 {
+    type Output = Float2x8;
+
+    fn mul(self, v: f32) -> Float2x8 {
+        return Float2x8::new(self.x * v, self.y * v);
+    }
+}
+
+// Float2x8 / F32x8
+impl Div<F32x8> for Float2x8 {
+    type Output = Float2x8;
+
+    fn div(self, v: F32x8) -> Float2x8 {
+        return Float2x8::new(self.x / v, self.y / v);
+    }
+}
+
+// Float2x8 / f32
+impl Div<f32> for Float2x8 {
+    type Output = Float2x8;
+
+    fn div(self, v: f32) -> Float2x8 {
+        return Float2x8::new(self.x / v, self.y / v);
+    }
+}
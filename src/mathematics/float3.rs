@@ -5,6 +5,7 @@ use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
 use super::approx_equal;
 use super::bivector3::Bivector3;
 use super::float2::Float2;
+use super::float3x8::Float3x8;
 use super::float4::Float4;
 use super::multivectors::{Magnitude, Vector};
 
@@ -44,10 +45,30 @@ impl Float3
 
 impl Vector for Float3 
 {    
+    const DIMENSIONS: usize = 3;
+
+    type Packet = Float3x8;
+    
     fn dot(u: Float3, v: Float3) -> f32
     {
         return u.x * v.x + u.y * v.y + u.z * v.z;
     }
+
+    fn component(&self, i: usize) -> f32
+    {
+        return match i
+        {
+            0 => self.x,
+            1 => self.y,
+            2 => self.z,
+            _ => panic!("Float3 has no component {}", i),
+        };
+    }
+
+    fn zip_map(u: Float3, v: Float3, f: impl Fn(f32, f32) -> f32) -> Float3
+    {
+        return Float3::new(f(u.x, v.x), f(u.y, v.y), f(u.z, v.z));
+    }
 }
 
 impl Magnitude for Float3
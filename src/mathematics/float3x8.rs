@@ -0,0 +1,126 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use super::f32x8::{F32x8, LANES};
+use super::float3::Float3;
+use super::multivectors::VectorPacket;
+
+/// Eight Float3s stored as one lane array per component
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Float3x8
+{
+    pub x: F32x8,
+    pub y: F32x8,
+    pub z: F32x8,
+}
+
+impl Float3x8
+{
+    pub fn new(x: F32x8, y: F32x8, z: F32x8) -> Float3x8
+    {
+        return Float3x8{ x: x, y: y, z: z };
+    }
+}
+
+impl VectorPacket for Float3x8
+{
+    type Scalar = Float3;
+
+    fn splat(v: Float3) -> Float3x8
+    {
+        return Float3x8::new(F32x8::splat(v.x), F32x8::splat(v.y), F32x8::splat(v.z));
+    }
+
+    fn from_lanes(lanes: [Float3; LANES]) -> Float3x8
+    {
+        return Float3x8::new(F32x8::from_fn(|i| lanes[i].x), F32x8::from_fn(|i| lanes[i].y), F32x8::from_fn(|i| lanes[i].z));
+    }
+
+    fn lane(&self, i: usize) -> Float3
+    {
+        return Float3::new(self.x.lane(i), self.y.lane(i), self.z.lane(i));
+    }
+
+    fn dot(u: Float3x8, v: Float3x8) -> F32x8
+    {
+        return u.x * v.x + u.y * v.y + u.z * v.z;
+    }
+
+    fn component(&self, i: usize) -> F32x8
+    {
+        return match i
+        {
+            0 => self.x,
+            1 => self.y,
+            2 => self.z,
+            _ => panic!("Float3x8 has no component {}", i),
+        };
+    }
+
+    fn zip_map(u: Float3x8, v: Float3x8, f: impl Fn(F32x8, F32x8) -> F32x8) -> Float3x8
+    {
+        return Float3x8::new(f(u.x, v.x), f(u.y, v.y), f(u.z, v.z));
+    }
+}
+
+// Float3x8 + Float3x8
+impl Add for Float3x8 {
+    type Output = Float3x8;
+
+    fn add(self, v: Float3x8) -> Float3x8 {
+        return Float3x8::new(self.x + v.x, self.y + v.y, self.z + v.z);
+    }
+}
+
+// Float3x8 - Float3x8
+impl Sub for Float3x8 {
+    type Output = Float3x8;
+
+    fn sub(self, v: Float3x8) -> Float3x8 {
+        return Float3x8::new(self.x - v.x, self.y - v.y, self.z - v.z);
+    }
+}
+
+// -Float3x8
+impl Neg for Float3x8 {
+    type Output = Float3x8;
+
+    fn neg(self) -> Float3x8 {
+        return Float3x8::new(-self.x, -self.y, -self.z);
+    }
+}
+
+// Float3x8 * F32x8
+impl Mul<F32x8> for Float3x8 {
+    type Output = Float3x8;
+
+    fn mul(self, v: F32x8) -> Float3x8 {
+        return Float3x8::new(self.x * v, self.y * v, self.z * v);
+    }
+}
+
+// Float3x8 * f32
+impl Mul<f32> for Float3x8 {
+    type Output = Float3x8;
+
+    fn mul(self, v: f32) -> Float3x8 {
+        return Float3x8::new(self.x * v, self.y * v, self.z * v);
+    }
+}
+
+// Float3x8 / F32x8
+impl Div<F32x8> for Float3x8 {
+    type Output = Float3x8;
+
+    fn div(self, v: F32x8) -> Float3x8 {
+        return Float3x8::new(self.x / v, self.y / v, self.z / v);
+    }
+}
+
+// Float3x8 / f32
+impl Div<f32> for Float3x8 {
+    type Output = Float3x8;
+
+    fn div(self, v: f32) -> Float3x8 {
+        return Float3x8::new(self.x / v, self.y / v, self.z / v);
+    }
+}
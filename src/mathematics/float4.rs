@@ -6,6 +6,7 @@ use super::approx_equal;
 use super::bivector4::Bivector4;
 use super::float2::Float2;
 use super::float3::Float3;
+use super::float4x8::Float4x8;
 use super::multivectors::{Magnitude, Vector};
 
 #[derive(Copy, Clone, Debug, Default)]
@@ -39,10 +40,31 @@ impl Float4
 
 impl Vector for Float4 
 {
+    const DIMENSIONS: usize = 4;
+
+    type Packet = Float4x8;
+
     fn dot(u: Float4, v: Float4) -> f32
     {
         return u.x * v.x + u.y * v.y + u.z * v.z + u.w * v.w;
     }
+
+    fn component(&self, i: usize) -> f32
+    {
+        return match i
+        {
+            0 => self.x,
+            1 => self.y,
+            2 => self.z,
+            3 => self.w,
+            _ => panic!("Float4 has no component {}", i),
+        };
+    }
+
+    fn zip_map(u: Float4, v: Float4, f: impl Fn(f32, f32) -> f32) -> Float4
+    {
+        return Float4::new(f(u.x, v.x), f(u.y, v.y), f(u.z, v.z), f(u.w, v.w));
+    }
 }
 
 impl Magnitude for Float4
@@ -0,0 +1,128 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use super::f32x8::{F32x8, LANES};
+use super::float4::Float4;
+use super::multivectors::VectorPacket;
+
+/// Eight Float4s stored as one lane array per component
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Float4x8
+{
+    pub x: F32x8,
+    pub y: F32x8,
+    pub z: F32x8,
+    pub w: F32x8,
+}
+
+impl Float4x8
+{
+    pub fn new(x: F32x8, y: F32x8, z: F32x8, w: F32x8) -> Float4x8
+    {
+        return Float4x8{ x: x, y: y, z: z, w: w };
+    }
+}
+
+impl VectorPacket for Float4x8
+{
+    type Scalar = Float4;
+
+    fn splat(v: Float4) -> Float4x8
+    {
+        return Float4x8::new(F32x8::splat(v.x), F32x8::splat(v.y), F32x8::splat(v.z), F32x8::splat(v.w));
+    }
+
+    fn from_lanes(lanes: [Float4; LANES]) -> Float4x8
+    {
+        return Float4x8::new(F32x8::from_fn(|i| lanes[i].x), F32x8::from_fn(|i| lanes[i].y), F32x8::from_fn(|i| lanes[i].z), F32x8::from_fn(|i| lanes[i].w));
+    }
+
+    fn lane(&self, i: usize) -> Float4
+    {
+        return Float4::new(self.x.lane(i), self.y.lane(i), self.z.lane(i), self.w.lane(i));
+    }
+
+    fn dot(u: Float4x8, v: Float4x8) -> F32x8
+    {
+        return u.x * v.x + u.y * v.y + u.z * v.z + u.w * v.w;
+    }
+
+    fn component(&self, i: usize) -> F32x8
+    {
+        return match i
+        {
+            0 => self.x,
+            1 => self.y,
+            2 => self.z,
+            3 => self.w,
+            _ => panic!("Float4x8 has no component {}", i),
+        };
+    }
+
+    fn zip_map(u: Float4x8, v: Float4x8, f: impl Fn(F32x8, F32x8) -> F32x8) -> Float4x8
+    {
+        return Float4x8::new(f(u.x, v.x), f(u.y, v.y), f(u.z, v.z), f(u.w, v.w));
+    }
+}
+
+// Float4x8 + Float4x8
+impl Add for Float4x8 {
+    type Output = Float4x8;
+
+    fn add(self, v: Float4x8) -> Float4x8 {
+        return Float4x8::new(self.x + v.x, self.y + v.y, self.z + v.z, self.w + v.w);
+    }
+}
+
+// Float4x8 - Float4x8
+impl Sub for Float4x8 {
+    type Output = Float4x8;
+
+    fn sub(self, v: Float4x8) -> Float4x8 {
+        return Float4x8::new(self.x - v.x, self.y - v.y, self.z - v.z, self.w - v.w);
+    }
+}
+
+// -Float4x8
+impl Neg for Float4x8 {
+    type Output = Float4x8;
+
+    fn neg(self) -> Float4x8 {
+        return Float4x8::new(-self.x, -self.y, -self.z, -self.w);
+    }
+}
+
+// Float4x8 * F32x8
+impl Mul<F32x8> for Float4x8 {
+    type Output = Float4x8;
+
+    fn mul(self, v: F32x8) -> Float4x8 {
+        return Float4x8::new(self.x * v, self.y * v, self.z * v, self.w * v);
+    }
+}
+
+// Float4x8 * f32
+impl Mul<f32> for Float4x8 {
+    type Output = Float4x8;
+
+    fn mul(self, v: f32) -> Float4x8 {
+        return Float4x8::new(self.x * v, self.y * v, self.z * v, self.w * v);
+    }
+}
+
+// Float4x8 / F32x8
+impl Div<F32x8> for Float4x8 {
+    type Output = Float4x8;
+
+    fn div(self, v: F32x8) -> Float4x8 {
+        return Float4x8::new(self.x / v, self.y / v, self.z / v, self.w / v);
+    }
+}
+
+// Float4x8 / f32
+impl Div<f32> for Float4x8 {
+    type Output = Float4x8;
+
+    fn div(self, v: f32) -> Float4x8 {
+        return Float4x8::new(self.x / v, self.y / v, self.z / v, self.w / v);
+    }
+}
@@ -1,3 +1,4 @@
+use super::f32x8::{F32x8, Mask8, LANES};
 
 #[allow(dead_code)]
 pub trait Magnitude
@@ -28,7 +29,77 @@ pub trait Vector:
     std::ops::MulAssign<f32> + 
     std::ops::Div<f32, Output = Self>
 {
+    /// Number of components
+    const DIMENSIONS: usize;
+
+    /// The same vector with each component split into lanes, see `VectorPacket`
+    type Packet: VectorPacket<Scalar = Self>;
+
     fn dot(u: Self, v: Self) -> f32;
+
+    /// Component i, where x is 0
+    fn component(&self, i: usize) -> f32;
+
+    /// Applies f to each pair of matching components of u and v
+    fn zip_map(u: Self, v: Self, f: impl Fn(f32, f32) -> f32) -> Self;
+
+    /// Applies f to each component
+    fn map(self, f: impl Fn(f32) -> f32) -> Self
+    {
+        return Self::zip_map(self, self, |x, _| f(x));
+    }
+}
+
+/// A structure of arrays vector holding one vector per lane, with a component per lane stored together
+pub trait VectorPacket: 
+    Copy +
+    Sized + 
+    std::ops::Add<Output = Self> +
+    std::ops::Neg<Output = Self> +
+    std::ops::Sub<Output = Self> +
+    std::ops::Mul<F32x8, Output = Self> + 
+    std::ops::Mul<f32, Output = Self> + 
+    std::ops::Div<F32x8, Output = Self> +
+    std::ops::Div<f32, Output = Self>
+{
+    type Scalar: Vector;
+
+    /// Every lane set to v
+    fn splat(v: Self::Scalar) -> Self;
+
+    fn from_lanes(lanes: [Self::Scalar; LANES]) -> Self;
+
+    fn lane(&self, i: usize) -> Self::Scalar;
+
+    fn dot(u: Self, v: Self) -> F32x8;
+
+    /// Component i of every lane, where x is 0
+    fn component(&self, i: usize) -> F32x8;
+
+    /// Applies f to each pair of matching components of u and v
+    fn zip_map(u: Self, v: Self, f: impl Fn(F32x8, F32x8) -> F32x8) -> Self;
+
+    /// Applies f to each component
+    fn map(self, f: impl Fn(F32x8) -> F32x8) -> Self
+    {
+        return Self::zip_map(self, self, |x, _| f(x));
+    }
+
+    /// Lanes of a where the mask is set, lanes of b elsewhere
+    fn select(mask: Mask8, a: Self, b: Self) -> Self
+    {
+        return Self::zip_map(a, b, |x, y| F32x8::select(mask, x, y));
+    }
+
+    fn length_squared(&self) -> F32x8
+    {
+        return Self::dot(*self, *self);
+    }
+
+    fn length(&self) -> F32x8
+    {
+        return self.length_squared().sqrt();
+    }
 }
 
 pub trait Bivector: 
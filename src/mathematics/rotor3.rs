@@ -4,6 +4,7 @@ use std::ops::{Mul, MulAssign};
 use super::approx_equal;
 use super::bivector3::Bivector3;
 use super::float3::Float3;
+use super::float3x8::Float3x8;
 use super::multivectors::{Magnitude, Rotor, Vector};
 
 #[derive(Copy, Clone, Debug, Default)]
@@ -229,6 +230,25 @@ impl Mul<Float3> for Rotor3
     }
 }
 
+// Rotor3 * Float3x8
+// The rotation is applied as a matrix built from the rotated basis vectors, so the rotor is only expanded once for all lanes
+impl Mul<Float3x8> for Rotor3
+{
+    type Output = Float3x8;
+    
+    fn mul(self, v: Float3x8) -> Float3x8
+    {
+        let x: Float3 = self.rotate_vector(Float3::new(1.0, 0.0, 0.0));
+        let y: Float3 = self.rotate_vector(Float3::new(0.0, 1.0, 0.0));
+        let z: Float3 = self.rotate_vector(Float3::new(0.0, 0.0, 1.0));
+        return Float3x8::new(
+            v.x * x.x + v.y * y.x + v.z * z.x,
+            v.x * x.y + v.y * y.y + v.z * z.y,
+            v.x * x.z + v.y * y.z + v.z * z.z
+        );
+    }
+}
+
 // Float3 * Rotor3
 impl Mul<Rotor3> for Float3
 {
@@ -4,6 +4,7 @@ use std::ops::{Mul, MulAssign};
 use super::approx_equal;
 use super::bivector4::Bivector4;
 use super::float4::Float4;
+use super::float4x8::Float4x8;
 use super::multivectors::{Magnitude, Rotor, Vector};
 
 #[derive(Copy, Clone, Debug, Default)]
@@ -324,6 +325,27 @@ impl Mul<Float4> for Rotor4
     }
 }
 
+// Rotor4 * Float4x8
+// The rotation is applied as a matrix built from the rotated basis vectors, so the rotor is only expanded once for all lanes
+impl Mul<Float4x8> for Rotor4
+{
+    type Output = Float4x8;
+    
+    fn mul(self, v: Float4x8) -> Float4x8
+    {
+        let x: Float4 = self.rotate_vector(Float4::new(1.0, 0.0, 0.0, 0.0));
+        let y: Float4 = self.rotate_vector(Float4::new(0.0, 1.0, 0.0, 0.0));
+        let z: Float4 = self.rotate_vector(Float4::new(0.0, 0.0, 1.0, 0.0));
+        let w: Float4 = self.rotate_vector(Float4::new(0.0, 0.0, 0.0, 1.0));
+        return Float4x8::new(
+            v.x * x.x + v.y * y.x + v.z * z.x + v.w * w.x,
+            v.x * x.y + v.y * y.y + v.z * z.y + v.w * w.y,
+            v.x * x.z + v.y * y.z + v.z * z.z + v.w * w.z,
+            v.x * x.w + v.y * y.w + v.z * z.w + v.w * w.w
+        );
+    }
+}
+
 // Float4 * Rotor4 
 impl Mul<Rotor4> for Float4
 {
@@ -0,0 +1,82 @@
+//! Several 3D cross sections of a 4D scene side by side in one image.
+//!
+//! Every ray of a `Camera4` lies in the hyperplane across the camera's own w axis, so turning the
+//! camera so its w axis points along another direction shows a different cross section. Each view
+//! has a camera of its own, made by turning the scene's camera about its pivot, and the views share
+//! a grid of equal sub viewports. Turning, moving or zooming the camera does the same to every view.
+
+use crate::camera::{Camera, Camera4};
+use crate::mathematics::bivector4::Bivector4;
+use crate::mathematics::float2::Float2;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::multivectors::{Magnitude, Rotor, Vector};
+use crate::mathematics::rotor4::Rotor4;
+
+/// Most views a grid holds, every count up to this fills its grid
+pub const MAX_VIEWS: usize = 4;
+
+/// The camera's hidden axis before its rotation
+const CAMERA_W: Float4 = Float4{ x: 0.0, y: 0.0, z: 0.0, w: 1.0 };
+
+/// Turns showing the XYZ, XYW, XZW and YZW cross sections, each swaps the hidden axis with w
+pub fn axis_slice_turns() -> Vec<Rotor4>
+{
+    return vec![
+        Rotor4::IDENTITY,
+        turn_to_normal(Float4::new(0.0, 0.0, 1.0, 0.0)),
+        turn_to_normal(Float4::new(0.0, 1.0, 0.0, 0.0)),
+        turn_to_normal(Float4::new(1.0, 0.0, 0.0, 0.0)),
+    ];
+}
+
+/// A camera for each turn, the scene's camera turned about its pivot by it
+pub fn turned_views(camera: &Camera4, turns: &[Rotor4]) -> Vec<Camera4>
+{
+    return turns.iter().map(|turn| {
+        let mut view: Camera4 = *camera;
+        view.rotate_camera(*turn);
+        return view;
+    }).collect();
+}
+
+/// The turn that points the camera's hidden w axis along normal, showing the cross section across it
+/// The turn is in the plane of w and the normal, so the other axes stay as they are
+pub fn turn_to_normal(normal: Float4) -> Rotor4
+{
+    let normal: Float4 = normal.normalized();
+    let cosine: f32 = f32::clamp(Float4::dot(CAMERA_W, normal), -1.0, 1.0);
+    let plane: Bivector4 = Float4::wedge(CAMERA_W, normal);
+    if plane.length() < 1e-6
+    {
+        // Already along w, or straight against it which is the same hyperplane
+        return Rotor4::IDENTITY;
+    }
+    return Rotor4::bivector_angle(&plane, f32::acos(cosine));
+}
+
+/// Columns and rows of the grid count views are laid out in, wider than tall
+pub fn grid(count: usize) -> (usize, usize)
+{
+    let rows: usize = usize::max((count as f32).sqrt() as usize, 1);
+    return (count.div_ceil(rows), rows);
+}
+
+/// The view a uv on the whole image falls in, and the uv within that view. Views narrower than the
+/// image are cropped at the sides rather than squashed
+pub fn locate(uv: Float2, count: usize) -> (usize, Float2)
+{
+    let (columns, rows): (usize, usize) = grid(count);
+    let column: usize = usize::min((uv.x * columns as f32) as usize, columns - 1);
+    // v counts up from the bottom, rows are counted from the top so views read left to right, top to bottom
+    let row_from_bottom: usize = usize::min((uv.y * rows as f32) as usize, rows - 1);
+    let row: usize = rows - 1 - row_from_bottom;
+
+    let cell: Float2 = Float2::new(uv.x * columns as f32 - column as f32, uv.y * rows as f32 - row_from_bottom as f32);
+    let crop: f32 = rows as f32 / columns as f32;
+    let index: usize = usize::min(row * columns + column, count - 1);
+    return (index, Float2::new(0.5 + (cell.x - 0.5) * crop, cell.y));
+}
+
+#[cfg(test)]
+#[path = "multi_view_tests.rs"]
+mod tests;
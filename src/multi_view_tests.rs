@@ -0,0 +1,135 @@
+use std::sync::{Arc, RwLock};
+
+use crate::application::Application;
+use crate::camera::{Camera, Camera4};
+use crate::framebuffer::Framebuffer;
+use crate::mathematics::{approx_equal, approx_equal4};
+use crate::mathematics::bivector4::Bivector4;
+use crate::mathematics::float2::Float2;
+use crate::mathematics::float3::Float3;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::multivectors::{Magnitude, Rotor, Vector};
+use crate::mathematics::rotor4::Rotor4;
+use crate::multi_view::*;
+use crate::renderer;
+use crate::scene::{Scene, SubScene4};
+use crate::scene_file::parse_scene;
+use crate::worker_pool::WorkerPool;
+
+const W: Float4 = Float4{ x: 0.0, y: 0.0, z: 0.0, w: 1.0 };
+
+#[test]
+fn grids_are_always_full()
+{
+    assert_eq!(grid(1), (1, 1));
+    assert_eq!(grid(2), (2, 1));
+    assert_eq!(grid(3), (3, 1));
+    assert_eq!(grid(MAX_VIEWS), (2, 2));
+}
+
+#[test]
+fn views_read_left_to_right_top_to_bottom()
+{
+    assert_eq!(locate(Float2::new(0.25, 0.75), 4), (0, Float2::new(0.5, 0.5)));
+    assert_eq!(locate(Float2::new(0.75, 0.75), 4).0, 1);
+    assert_eq!(locate(Float2::new(0.25, 0.25), 4).0, 2);
+    assert_eq!(locate(Float2::new(1.0, 0.0), 4), (3, Float2::new(1.0, 0.0)));
+
+    // Side by side views are cropped to half their width rather than squashed
+    assert_eq!(locate(Float2::new(0.0, 0.5), 2), (0, Float2::new(0.25, 0.5)));
+    assert_eq!(locate(Float2::new(0.75, 0.5), 2), (1, Float2::new(0.5, 0.5)));
+}
+
+#[test]
+fn turns_point_the_hidden_axis_along_the_normal()
+{
+    for normal in [Float4::new(1.0, 0.0, 0.0, 0.0), Float4::new(0.0, 1.0, 0.0, 0.0), Float4::new(0.0, 0.0, 1.0, 0.0), Float4::new(1.0, -2.0, 0.5, 1.0)]
+    {
+        let turn: Rotor4 = turn_to_normal(normal);
+        assert!(approx_equal4(turn * W, normal.normalized()));
+    }
+    assert_eq!(turn_to_normal(W), Rotor4::IDENTITY);
+    assert_eq!(turn_to_normal(W * -3.0), Rotor4::IDENTITY);
+
+    // The axis slices each hide a different axis
+    let hidden: Vec<Float4> = axis_slice_turns().iter().map(|turn| *turn * W).collect();
+    assert!(approx_equal4(hidden[0], W));
+    assert!(approx_equal4(hidden[1], Float4::new(0.0, 0.0, 1.0, 0.0)));
+    assert!(approx_equal4(hidden[2], Float4::new(0.0, 1.0, 0.0, 0.0)));
+    assert!(approx_equal4(hidden[3], Float4::new(1.0, 0.0, 0.0, 0.0)));
+}
+
+#[test]
+fn turned_rays_match_a_turned_camera()
+{
+    let rotation: Rotor4 = Rotor4::bivector_angle(&Bivector4::new(1.0, 0.5, 0.0, 0.7, 0.2, 0.4), 1.1);
+    let turn: Rotor4 = turn_to_normal(Float4::new(0.0, 1.0, 0.0, 1.0));
+    let camera: Camera4 = Camera4::new(Float4::new(1.0, 0.0, 0.0, 0.5), Float4::new(0.0, 0.0, 4.0, 0.0), rotation, 1.5, 70.0, 1.0);
+    let mut turned: Camera4 = Camera4::new(Float4::new(1.0, 0.0, 0.0, 0.5), Float4::new(0.0, 0.0, 4.0, 0.0), rotation, 1.5, 70.0, 1.0);
+    turned.rotate_camera(turn);
+
+    for uv in [Float2::new(0.0, 0.0), Float2::new(0.3, 0.9), Float2::new(1.0, 0.5)]
+    {
+        let (position, direction): (Float4, Float4) = camera.get_turned_ray(uv, turn);
+        assert!(approx_equal4(position, turned.get_camera_position()));
+        assert!(approx_equal4(direction, turned.get_ray_direction(uv)));
+    }
+    let (_, direction): (Float4, Float4) = camera.get_turned_ray(Float2::new(0.5, 0.5), Rotor4::IDENTITY);
+    assert!(approx_equal(Float4::dot(direction, camera.get_ray_direction(Float2::new(0.5, 0.5))), direction.length() * direction.length()));
+}
+
+#[test]
+fn only_views_showing_w_see_objects_off_the_camera_slice()
+{
+    // The hypersphere is away along w, outside the XYZ cross section through the camera
+    let mut scene: Scene = parse_scene("object sphere\n    position 0 0 0 2\n    radius 0.5\nend\ncamera\n    offset 0 0 4 0\nend\n", 1.0).unwrap();
+    let scene_4d: &mut SubScene4 = scene.scene_4d.as_mut().unwrap();
+    scene_4d.views = turned_views(&scene_4d.camera, &axis_slice_turns());
+    let scene: Arc<RwLock<Scene>> = Arc::new(RwLock::new(scene));
+    let application: Application = Application::new(1.0, 64, false);
+    let pool: WorkerPool = WorkerPool::new(2);
+    let framebuffer: Framebuffer = renderer::render_image(&pool, &scene, &application);
+
+    // Views are 32 pixels square, the framebuffer's rows count down from the top
+    let covered = |view: u32| -> usize {
+        let (left, top): (u32, u32) = ((view % 2) * 32, (view / 2) * 32);
+        let pixels = (left..left + 32).flat_map(|x| (top..top + 32).map(move |y| (x, y)));
+        return pixels.filter(|(x, y)| framebuffer.get(*x, *y) != Float3::new(0.0, 0.0, 0.0)).count();
+    };
+    assert_eq!(covered(0), 0);
+    for view in 1..4
+    {
+        assert!(covered(view) > 0, "View {} shows nothing", view);
+    }
+}
+
+#[test]
+fn views_have_cameras_of_their_own()
+{
+    let mut scene: Scene = parse_scene("object sphere\n    radius 1\nend\ncamera\n    offset 0 0 4 0\nend\n", 1.0).unwrap();
+    let scene_4d: &mut SubScene4 = scene.scene_4d.as_mut().unwrap();
+    let turns: Vec<Rotor4> = axis_slice_turns();
+    scene_4d.views = turned_views(&scene_4d.camera, &turns[..2]);
+    for (view, turn) in scene_4d.views.iter().zip(&turns)
+    {
+        assert!(approx_equal4(view.get_camera_rotation() * W, scene_4d.camera.get_camera_rotation() * (*turn * W)));
+    }
+
+    // Moving one view's camera away leaves the other looking at the hypersphere
+    scene_4d.views[1].move_camera(Float4::new(0.0, 50.0, 0.0, 0.0));
+    let scene: Arc<RwLock<Scene>> = Arc::new(RwLock::new(scene));
+    let application: Application = Application::new(1.0, 64, false);
+    let framebuffer: Framebuffer = renderer::render_image(&WorkerPool::new(2), &scene, &application);
+    let covered = |view: u32| -> usize {
+        let pixels = (view * 32..view * 32 + 32).flat_map(|x| (0..64).map(move |y| (x, y)));
+        return pixels.filter(|(x, y)| framebuffer.get(*x, *y) != Float3::new(0.0, 0.0, 0.0)).count();
+    };
+    assert!(covered(0) > 0);
+    assert_eq!(covered(1), 0);
+
+    // The controls move every view along with the scene's camera
+    let mut locked = scene.write().unwrap();
+    let scene_4d: &mut SubScene4 = locked.scene_4d.as_mut().unwrap();
+    scene_4d.cameras_mut().for_each(|camera| camera.move_camera(Float4::new(1.0, 0.0, 0.0, 0.0)));
+    assert_eq!(scene_4d.views[0].get_camera_position(), scene_4d.camera.get_camera_position());
+}
@@ -0,0 +1,192 @@
+//! Distance functions for the six convex regular polychora.
+//!
+//! A regular polychoron {p,q,r} is symmetric under the reflection group with the same Coxeter diagram.
+//! Any point can be reflected into one fundamental chamber of that group without changing its
+//! distance to the surface. Inside the chamber the polychoron is bounded by a single orthoscheme:
+//! the path from the centre to a facet centre, ridge centre, edge centre and vertex, where every step
+//! is orthogonal to the last. Measuring the point along those four steps gives the exact distance
+//! in the same way `sdf_box4` does for the tesseract.
+
+use crate::mathematics::PI;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::multivectors::{Magnitude, Vector};
+
+#[derive(Copy, Clone, Debug)]
+pub struct Polychoron
+{
+    /// Normals of the four mirrors bounding the fundamental chamber
+    roots: [Float4; 4],
+    /// Orthonormal steps from the centre to a facet centre, ridge centre, edge centre and vertex
+    axes: [Float4; 4],
+    /// Length of each step for a polychoron with a circumradius of 1
+    extents: [f32; 4],
+    pub circumradius: f32,
+}
+
+impl Polychoron
+{
+    /// Builds the regular polychoron with Schläfli symbol {p,q,r}
+    /// Dual pairs share the same orientation, with the tesseract's facets and the 16-cell's vertices on the axes
+    pub fn new(schlafli: [u32; 3], circumradius: f32) -> Polychoron
+    {
+        // {r,q,p} has the same mirrors as {p,q,r} in reverse order
+        let reversed: bool = schlafli[0] < schlafli[2];
+        let frame: [u32; 3] = if reversed { [schlafli[2], schlafli[1], schlafli[0]] } else { schlafli };
+
+        // Mirrors i and j meet at an angle of PI / m_ij, the Gram matrix of the roots is tridiagonal
+        // with -cos(PI / m) off the diagonal so its Cholesky factor is lower bidiagonal
+        let g: [f32; 3] = frame.map(|m| -f32::cos(PI / m as f32));
+
+        let mut diagonal: [f32; 4] = [1.0; 4];
+        let mut lower: [f32; 3] = [0.0; 3];
+        for i in 0..3
+        {
+            lower[i] = g[i] / diagonal[i];
+            diagonal[i + 1] = f32::sqrt(1.0 - lower[i] * lower[i]);
+        }
+
+        let mut roots: [Float4; 4] = [
+            Float4::new(diagonal[0], 0.0, 0.0, 0.0),
+            Float4::new(lower[0], diagonal[1], 0.0, 0.0),
+            Float4::new(0.0, lower[1], diagonal[2], 0.0),
+            Float4::new(0.0, 0.0, lower[2], diagonal[3]),
+        ];
+        if reversed
+        {
+            roots.reverse();
+        }
+
+        // Fundamental weights: weight i lies on every mirror except mirror i
+        // The vertex lies on mirrors 2, 3, 4 and the facet centre on mirrors 1, 2, 3
+        let weights: [Float4; 4] = [0, 1, 2, 3].map(|i| {
+            let others: Vec<Float4> = (0..4).filter(|j| *j != i).map(|j| roots[j]).collect();
+            let weight: Float4 = cross(others[0], others[1], others[2]);
+            return if Float4::dot(weight, roots[i]) < 0.0 { -weight } else { weight };
+        });
+
+        // Gram-Schmidt from the facet centre back to the vertex gives the orthoscheme steps
+        let vertex: Float4 = weights[0].normalized();
+        let mut axes: [Float4; 4] = [Float4::default(); 4];
+        let mut extents: [f32; 4] = [0.0; 4];
+        for i in 0..4
+        {
+            let mut axis: Float4 = weights[3 - i];
+            for previous in &axes[0..i]
+            {
+                axis -= *previous * Float4::dot(axis, *previous);
+            }
+            axis.normalize();
+
+            extents[i] = Float4::dot(vertex, axis);
+            if extents[i] < 0.0
+            {
+                axis = -axis;
+                extents[i] = -extents[i];
+            }
+            axes[i] = axis;
+        }
+
+        return Polychoron{ roots: roots, axes: axes, extents: extents, circumradius: circumradius };
+    }
+
+    pub fn five_cell(circumradius: f32) -> Polychoron { return Polychoron::new([3, 3, 3], circumradius); }
+
+    pub fn tesseract(circumradius: f32) -> Polychoron { return Polychoron::new([4, 3, 3], circumradius); }
+
+    pub fn sixteen_cell(circumradius: f32) -> Polychoron { return Polychoron::new([3, 3, 4], circumradius); }
+
+    pub fn twenty_four_cell(circumradius: f32) -> Polychoron { return Polychoron::new([3, 4, 3], circumradius); }
+
+    pub fn hundred_twenty_cell(circumradius: f32) -> Polychoron { return Polychoron::new([5, 3, 3], circumradius); }
+
+    pub fn six_hundred_cell(circumradius: f32) -> Polychoron { return Polychoron::new([3, 3, 5], circumradius); }
+
+    /// Distance from the centre to the middle of each facet
+    pub fn inradius(&self) -> f32
+    {
+        return self.extents[0] * self.circumradius;
+    }
+
+    /// One vertex of the polychoron, every other vertex is a reflection of this one
+    pub fn vertex(&self) -> Float4
+    {
+        let mut vertex: Float4 = Float4::default();
+        for i in 0..4
+        {
+            vertex += self.axes[i] * self.extents[i] * self.circumradius;
+        }
+        return vertex;
+    }
+
+    /// Mirror normals of the symmetry group
+    pub fn roots(&self) -> [Float4; 4]
+    {
+        return self.roots;
+    }
+
+    /// Reflect p into the fundamental chamber
+    pub fn fold(&self, p: Float4) -> Float4
+    {
+        // The longest chain of reflections needed is 60, for the 120-cell and 600-cell
+        const MAX_REFLECTIONS: i32 = 64;
+
+        let mut p: Float4 = p;
+        let mut reflections: i32 = 0;
+        let mut folded: bool = true;
+        while folded && reflections < MAX_REFLECTIONS
+        {
+            folded = false;
+            for root in &self.roots
+            {
+                let d: f32 = Float4::dot(p, *root);
+                if d < 0.0
+                {
+                    p -= *root * (2.0 * d);
+                    folded = true;
+                    reflections += 1;
+                }
+            }
+        }
+        return p;
+    }
+}
+
+/// Vector orthogonal to a, b and c
+fn cross(a: Float4, b: Float4, c: Float4) -> Float4
+{
+    let det3 = |a0: f32, a1: f32, a2: f32, b0: f32, b1: f32, b2: f32, c0: f32, c1: f32, c2: f32| -> f32 {
+        return a0 * (b1 * c2 - b2 * c1) - a1 * (b0 * c2 - b2 * c0) + a2 * (b0 * c1 - b1 * c0);
+    };
+
+    return Float4::new(
+         det3(a.y, a.z, a.w, b.y, b.z, b.w, c.y, c.z, c.w),
+        -det3(a.x, a.z, a.w, b.x, b.z, b.w, c.x, c.z, c.w),
+         det3(a.x, a.y, a.w, b.x, b.y, b.w, c.x, c.y, c.w),
+        -det3(a.x, a.y, a.z, b.x, b.y, b.z, c.x, c.y, c.z)
+    );
+}
+
+/// Regular polychoron with its edges and corners rounded by edge_radius, like sdf_box4
+pub fn sdf_polychoron(p: Float4, centre: Float4, polychoron: &Polychoron, edge_radius: f32) -> f32
+{
+    let p: Float4 = polychoron.fold(p - centre);
+
+    // Shrink the polychoron so that rounding keeps the facets in place
+    let inradius: f32 = polychoron.inradius();
+    let scale: f32 = polychoron.circumradius * f32::max(inradius - edge_radius, 0.0) / inradius;
+
+    let mut outside: f32 = 0.0;
+    for i in 0..4
+    {
+        let q: f32 = Float4::dot(p, polychoron.axes[i]) - polychoron.extents[i] * scale;
+        outside += f32::max(q, 0.0) * f32::max(q, 0.0);
+    }
+
+    let inside: f32 = f32::min(Float4::dot(p, polychoron.axes[0]) - polychoron.extents[0] * scale, 0.0);
+
+    return f32::sqrt(outside) + inside - edge_radius;
+}
+
+#[cfg(test)]
+#[path = "polychora_tests.rs"]
+mod tests;
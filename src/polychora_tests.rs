@@ -0,0 +1,130 @@
+use crate::distance_functions::sdf_box4;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::multivectors::{Magnitude, Vector};
+use crate::polychora::*;
+
+const ORIGIN: Float4 = Float4{ x: 0.0, y: 0.0, z: 0.0, w: 0.0 };
+
+/// Every vertex, found by reflecting the first vertex until no new vertices appear
+fn vertices(polychoron: &Polychoron) -> Vec<Float4>
+{
+    let mut vertices: Vec<Float4> = vec![polychoron.vertex()];
+    let mut i: usize = 0;
+    while i < vertices.len()
+    {
+        for root in polychoron.roots()
+        {
+            let v: Float4 = vertices[i];
+            let reflected: Float4 = v - root * (2.0 * Float4::dot(v, root));
+            if !vertices.iter().any(|u| (*u - reflected).length() < 1e-3)
+            {
+                vertices.push(reflected);
+            }
+        }
+        i += 1;
+    }
+    return vertices;
+}
+
+/// Points on a lattice around the polychoron, including points inside, outside and on the surface
+fn sample_points() -> Vec<Float4>
+{
+    let mut points: Vec<Float4> = Vec::new();
+    let steps: [f32; 5] = [-1.7, -0.6, 0.0, 0.35, 1.2];
+    for x in steps { for y in steps { for z in steps { for w in steps {
+        points.push(Float4::new(x, y, z, w));
+    }}}}
+    return points;
+}
+
+fn all_polychora() -> [(Polychoron, usize, f32); 6]
+{
+    let golden_ratio: f32 = (1.0 + f32::sqrt(5.0)) / 2.0;
+    let h4_ratio: f32 = golden_ratio * golden_ratio / f32::sqrt(8.0);
+
+    // Polychoron, vertex count and inradius / circumradius
+    return [
+        (Polychoron::five_cell(1.0), 5, 0.25),
+        (Polychoron::tesseract(1.0), 16, 0.5),
+        (Polychoron::sixteen_cell(1.0), 8, 0.5),
+        (Polychoron::twenty_four_cell(1.0), 24, 1.0 / f32::sqrt(2.0)),
+        (Polychoron::hundred_twenty_cell(1.0), 600, h4_ratio),
+        (Polychoron::six_hundred_cell(1.0), 120, h4_ratio),
+    ];
+}
+
+#[test]
+fn vertex_counts()
+{
+    for (polychoron, vertex_count, _) in all_polychora()
+    {
+        let vertices: Vec<Float4> = vertices(&polychoron);
+        assert_eq!(vertices.len(), vertex_count);
+
+        for v in vertices
+        {
+            assert!(f32::abs(v.length() - 1.0) < 1e-4);
+            assert!(f32::abs(sdf_polychoron(v, ORIGIN, &polychoron, 0.0)) < 1e-4);
+        }
+    }
+}
+
+#[test]
+fn inradius()
+{
+    for (polychoron, _, ratio) in all_polychora()
+    {
+        assert!(f32::abs(polychoron.inradius() - ratio) < 1e-4);
+        assert!(f32::abs(sdf_polychoron(ORIGIN, ORIGIN, &polychoron, 0.0) + ratio) < 1e-4);
+    }
+}
+
+#[test]
+fn distance_is_bounded_by_vertices()
+{
+    // The distance can never be more than the distance to the nearest vertex
+    // and the point is outside exactly when it is further from the centre than the facets allow
+    for (polychoron, _, _) in all_polychora()
+    {
+        let vertices: Vec<Float4> = vertices(&polychoron);
+        for p in sample_points()
+        {
+            let d: f32 = sdf_polychoron(p, ORIGIN, &polychoron, 0.0);
+            let nearest_vertex: f32 = vertices.iter().map(|v| (p - *v).length()).fold(f32::MAX, f32::min);
+            assert!(d <= nearest_vertex + 1e-4);
+            assert!(d >= p.length() - 1.0 - 1e-4);
+        }
+    }
+}
+
+#[test]
+fn tesseract_matches_box()
+{
+    // A tesseract of circumradius 2 has a half size of 1
+    let tesseract: Polychoron = Polychoron::tesseract(2.0);
+    let size: Float4 = Float4::new(1.0, 1.0, 1.0, 1.0);
+    let centre: Float4 = Float4::new(0.5, 0.0, -0.25, 1.0);
+
+    for p in sample_points()
+    {
+        let p: Float4 = p * 2.0;
+        assert!(f32::abs(sdf_polychoron(p, centre, &tesseract, 0.0) - sdf_box4(p, centre, size, 0.0)) < 1e-4);
+        assert!(f32::abs(sdf_polychoron(p, centre, &tesseract, 0.2) - sdf_box4(p, centre, size, 0.2)) < 1e-4);
+    }
+}
+
+#[test]
+fn sixteen_cell()
+{
+    let sixteen_cell: Polychoron = Polychoron::sixteen_cell(1.0);
+
+    // Beyond a vertex
+    assert!(f32::abs(sdf_polychoron(Float4::new(0.0, 0.0, -2.0, 0.0), ORIGIN, &sixteen_cell, 0.0) - 1.0) < 1e-4);
+
+    // Beyond the middle of the facet x + y + z + w = 1
+    assert!(f32::abs(sdf_polychoron(Float4::new(1.0, 1.0, 1.0, 1.0), ORIGIN, &sixteen_cell, 0.0) - 1.5) < 1e-4);
+
+    // Rounding keeps the facets in place but pulls in the vertices
+    assert!(f32::abs(sdf_polychoron(Float4::new(1.0, 1.0, 1.0, 1.0), ORIGIN, &sixteen_cell, 0.1) - 1.5) < 1e-4);
+    assert!(sdf_polychoron(Float4::new(1.0, 0.0, 0.0, 0.0), ORIGIN, &sixteen_cell, 0.1) > 0.0);
+}
@@ -0,0 +1,186 @@
+//! Progressive refinement for views that stay still.
+//!
+//! Instead of rendering every pixel each frame, the image is built up over several passes. The
+//! first passes render one pixel per block and fill the block with it, halving the block size each
+//! pass until every pixel has been rendered once. Later passes add jittered samples to each pixel
+//! and average them, anti aliasing the image for as long as the view does not change.
+
+use std::sync::{Arc, RwLock};
+
+use crate::anti_aliasing::halton;
+use crate::application::Application;
+use crate::framebuffer::Framebuffer;
+use crate::mathematics::f32x8::LANES;
+use crate::mathematics::float2::Float2;
+use crate::mathematics::float3::Float3;
+use crate::renderer;
+use crate::scene::Scene;
+use crate::worker_pool::{Tile, WorkerPool, TILE_SIZE};
+
+/// Block sizes of the coarse passes before every pixel is rendered, each divides the tile size
+pub const BLOCK_SIZES: [u32; 3] = [8, 4, 2];
+
+/// What a single pass renders
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Pass
+{
+    /// One pixel per block of this size, copied over the whole block
+    Coarse(u32),
+    /// Every pixel, sample is the number of samples each pixel will have once the pass is done
+    Sample{ sample: u32, offset: Float2 },
+}
+
+pub struct ProgressiveRenderer
+{
+    /// Passes finished since the last reset
+    passes: u32,
+    /// Samples per pixel after which the image is finished
+    pub max_samples: u32,
+}
+
+impl ProgressiveRenderer
+{
+    pub fn new(max_samples: u32) -> ProgressiveRenderer
+    {
+        return ProgressiveRenderer{ passes: 0, max_samples: max_samples };
+    }
+
+    /// Starts again from the coarsest pass, call whenever the camera or scene changes
+    pub fn reset(&mut self)
+    {
+        self.passes = 0;
+    }
+
+    /// Samples every pixel has so far, 0 while the coarse passes are running
+    pub fn samples(&self) -> u32
+    {
+        return self.passes.saturating_sub(BLOCK_SIZES.len() as u32);
+    }
+
+    pub fn is_finished(&self) -> bool
+    {
+        return self.samples() >= self.max_samples;
+    }
+
+    /// The pass to render next, None once the image is finished
+    pub fn next_pass(&self) -> Option<Pass>
+    {
+        if self.is_finished()
+        {
+            return None;
+        }
+        if let Some(block_size) = BLOCK_SIZES.get(self.passes as usize)
+        {
+            return Some(Pass::Coarse(*block_size));
+        }
+
+        let sample: u32 = self.samples() + 1;
+        // The first sample is the pixel centre, the rest are spread over the pixel
+        let offset: Float2 = if sample == 1 { Float2::new(0.0, 0.0) } else { Float2::new(halton(sample - 1, 2) - 0.5, halton(sample - 1, 3) - 0.5) };
+        return Some(Pass::Sample{ sample: sample, offset: offset });
+    }
+
+    /// Renders the next pass into the framebuffer, returns false when the image was already finished
+    pub fn render_next_pass(&mut self, pool: &WorkerPool, scene: &Arc<RwLock<Scene>>, application: &Application, framebuffer: &Arc<Framebuffer>) -> bool
+    {
+        let Some(pass) = self.next_pass() else {
+            return false;
+        };
+
+        render_pass(pool, scene, application, framebuffer, pass);
+        self.passes += 1;
+        return true;
+    }
+
+    /// Renders every pass, for a finished image in one go
+    pub fn render_all(&mut self, pool: &WorkerPool, scene: &Arc<RwLock<Scene>>, application: &Application, framebuffer: &Arc<Framebuffer>)
+    {
+        self.reset();
+        while self.render_next_pass(pool, scene, application, framebuffer) {}
+    }
+}
+
+/// Renders one pass across the worker pool
+pub fn render_pass(pool: &WorkerPool, scene: &Arc<RwLock<Scene>>, application: &Application, framebuffer: &Arc<Framebuffer>, pass: Pass)
+{
+    let scene: Arc<RwLock<Scene>> = scene.clone();
+    let framebuffer: Arc<Framebuffer> = framebuffer.clone();
+    let application: Application = *application;
+    let tiles: Vec<Tile> = Tile::split(application.width, application.height, TILE_SIZE);
+
+    pool.run_tiles(tiles, move |tile| {
+        let scene = scene.read().expect("Scene lock was poisoned");
+        let pixels: Vec<(u32, u32)> = pass_pixels(tile, pass);
+        let offset: Float2 = if let Pass::Sample{ offset, .. } = pass { offset } else { Float2::new(0.0, 0.0) };
+        for packet in pixels.chunks(LANES)
+        {
+            // Short packets at the end repeat their last pixel
+            let uvs: [Float2; LANES] = std::array::from_fn(|i| {
+                let (x, y): (u32, u32) = packet[usize::min(i, packet.len() - 1)];
+                return renderer::pixel_uv(x as f32 + offset.x, y as f32 + offset.y, &application);
+            });
+            let colours: [Float3; LANES] = renderer::get_packet_colours(&uvs, &scene);
+
+            for (&(x, y), colour) in packet.iter().zip(colours)
+            {
+                match pass
+                {
+                    Pass::Coarse(block_size) => fill_block(&framebuffer, &application, x, y, block_size, colour),
+                    Pass::Sample{ sample, .. } =>
+                    {
+                        // Framebuffer rows count down from the top
+                        let row: u32 = application.height - 1 - y;
+                        let average: Float3 = if sample == 1 { colour } else {
+                            let previous: Float3 = framebuffer.get(x, row);
+                            previous + (colour - previous) / sample as f32
+                        };
+                        framebuffer.set(x, row, average);
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// The pixels of a tile rendered by a pass, leaving out those already rendered by the pass before
+fn pass_pixels(tile: &Tile, pass: Pass) -> Vec<(u32, u32)>
+{
+    let block_size: u32 = if let Pass::Coarse(block_size) = pass { block_size } else { 1 };
+    let previous_block_size: Option<u32> = match pass
+    {
+        Pass::Coarse(block_size) if block_size == BLOCK_SIZES[0] => None,
+        Pass::Coarse(block_size) => Some(block_size * 2),
+        Pass::Sample{ sample: 1, .. } => Some(BLOCK_SIZES[BLOCK_SIZES.len() - 1]),
+        Pass::Sample{ .. } => None,
+    };
+
+    let mut pixels: Vec<(u32, u32)> = Vec::new();
+    for y in (tile.y..tile.y + tile.height).step_by(block_size as usize)
+    {
+        for x in (tile.x..tile.x + tile.width).step_by(block_size as usize)
+        {
+            if previous_block_size.is_some_and(|previous| x % previous == 0 && y % previous == 0)
+            {
+                continue;
+            }
+            pixels.push((x, y));
+        }
+    }
+    return pixels;
+}
+
+/// Copies a colour over the block whose bottom left pixel is (x, y), clipped to the image
+fn fill_block(framebuffer: &Framebuffer, application: &Application, x: u32, y: u32, block_size: u32, colour: Float3)
+{
+    for block_y in y..u32::min(y + block_size, application.height)
+    {
+        for block_x in x..u32::min(x + block_size, application.width)
+        {
+            framebuffer.set(block_x, application.height - 1 - block_y, colour);
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "progressive_tests.rs"]
+mod tests;
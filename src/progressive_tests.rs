@@ -0,0 +1,88 @@
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+
+use crate::application::Application;
+use crate::camera::{Camera, Camera4};
+use crate::framebuffer::Framebuffer;
+use crate::mathematics::float2::Float2;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::rotor4::Rotor4;
+use crate::progressive::*;
+use crate::renderer;
+use crate::scene::Scene;
+use crate::worker_pool::{Tile, WorkerPool};
+
+#[test]
+fn passes_refine_then_sample()
+{
+    let mut progressive: ProgressiveRenderer = ProgressiveRenderer::new(3);
+    let mut passes: Vec<Pass> = Vec::new();
+    while let Some(pass) = progressive.next_pass()
+    {
+        passes.push(pass);
+        progressive.passes += 1;
+    }
+
+    assert_eq!(passes[0..3], [Pass::Coarse(8), Pass::Coarse(4), Pass::Coarse(2)]);
+    assert_eq!(passes[3], Pass::Sample{ sample: 1, offset: Float2::new(0.0, 0.0) });
+    assert_eq!(passes.len(), 6);
+    assert!(progressive.is_finished());
+    assert_eq!(progressive.samples(), 3);
+
+    // Jittered samples stay inside the pixel
+    for pass in &passes[4..]
+    {
+        let Pass::Sample{ offset, .. } = pass else { panic!("Expected a sample pass") };
+        assert!(f32::abs(offset.x) <= 0.5 && f32::abs(offset.y) <= 0.5);
+        assert!(offset.x != 0.0 || offset.y != 0.0);
+    }
+
+    progressive.reset();
+    assert_eq!(progressive.next_pass(), Some(Pass::Coarse(8)));
+}
+
+#[test]
+fn refinement_renders_each_pixel_once()
+{
+    let tile: Tile = Tile{ x: 16, y: 32, width: 16, height: 11 };
+    let mut rendered: HashSet<(u32, u32)> = HashSet::new();
+    for pass in [Pass::Coarse(8), Pass::Coarse(4), Pass::Coarse(2), Pass::Sample{ sample: 1, offset: Float2::new(0.0, 0.0) }]
+    {
+        for pixel in pass_pixels(&tile, pass)
+        {
+            assert!(rendered.insert(pixel), "{:?} rendered twice", pixel);
+        }
+    }
+    assert_eq!(rendered.len(), 16 * 11);
+
+    // Later samples cover every pixel again
+    assert_eq!(pass_pixels(&tile, Pass::Sample{ sample: 2, offset: Float2::new(0.1, 0.1) }).len(), 16 * 11);
+}
+
+#[test]
+fn first_sample_matches_a_full_render()
+{
+    let application: Application = Application::new(16.0 / 9.0, 70, false);
+    let camera: Camera4 = Camera4::new(Float4::new(0.0, 0.0, 0.0, 0.0), Float4::new(0.0, 0.0, 4.0, 0.0), Rotor4::IDENTITY, application.aspect_ratio, 70.0, 1.0);
+    let scene: Arc<RwLock<Scene>> = Arc::new(RwLock::new(Scene::new_4d(camera, Float4::new(2.0, 2.0, 4.0, 0.0))));
+    let pool: WorkerPool = WorkerPool::new(2);
+
+    let framebuffer: Arc<Framebuffer> = Arc::new(Framebuffer::new(application.width, application.height));
+    let mut progressive: ProgressiveRenderer = ProgressiveRenderer::new(1);
+
+    // The first pass already covers the whole image
+    assert!(progressive.render_next_pass(&pool, &scene, &application, &framebuffer));
+    assert!((0..application.height).all(|y| (0..application.width).all(|x| framebuffer.get_rgba(x, y)[3] == 1.0)));
+
+    while progressive.render_next_pass(&pool, &scene, &application, &framebuffer) {}
+    assert!(progressive.is_finished());
+    assert_eq!(framebuffer.colours(), renderer::render_image(&pool, &scene, &application).colours());
+
+    // More samples smooth the edges without moving the image
+    let mut antialiased: ProgressiveRenderer = ProgressiveRenderer::new(8);
+    antialiased.render_all(&pool, &scene, &application, &framebuffer);
+    assert_eq!(antialiased.samples(), 8);
+    let centre: (u32, u32) = (application.width / 2, application.height / 2);
+    let difference: f32 = framebuffer.get(centre.0, centre.1).x - renderer::render_image(&pool, &scene, &application).get(centre.0, centre.1).x;
+    assert!(f32::abs(difference) < 0.02, "{}", difference);
+}
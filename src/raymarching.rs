@@ -1,36 +1,311 @@
+use crate::mathematics::f32x8::{F32x8, Mask8, LANES};
 use crate::mathematics::float3::Float3;
+use crate::mathematics::float3x8::Float3x8;
 use crate::mathematics::float4::Float4;
-use crate::mathematics::multivectors::{Magnitude, Vector};
+use crate::mathematics::float4x8::Float4x8;
+use crate::mathematics::multivectors::{Magnitude, Vector, VectorPacket};
+use crate::sdf::Sdf;
 
-pub const MAX_DIST: f32 = 100.0;
-const MAX_STEPS: i32 = 100;
-const SURF_DIST: f32 = 0.001;
+/// How far each step along a ray goes
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MarchMethod
+{
+    /// Steps exactly the distance to the scene
+    Sphere,
+    /// Over-relaxed sphere tracing from Keinert et al. 2014, "Enhanced Sphere Tracing"
+    /// Steps the distance scaled by a factor between 1 and 2. When a step overshoots, the march
+    /// goes back and continues as plain sphere tracing
+    OverRelaxed(f32),
+    /// Accelerated sphere tracing from Bálint and Valasek 2018, "Accelerating Sphere Tracing"
+    /// Lengthens each step by estimating the surface as a plane from the last two distances, the
+    /// factor between 0 and 1 sets how far to trust that estimate. Overshoots fall back for one step
+    Accelerated(f32),
+}
+
+impl MarchMethod
+{
+    pub const NAMES: &'static str = "sphere, relaxed or accelerated";
+
+    /// Looks up a method by name, with its default relaxation factor
+    pub fn from_name(name: &str) -> Option<MarchMethod>
+    {
+        return match name
+        {
+            "sphere" => Some(MarchMethod::Sphere),
+            "relaxed" => Some(MarchMethod::OverRelaxed(1.6)),
+            "accelerated" => Some(MarchMethod::Accelerated(0.9)),
+            _ => None,
+        };
+    }
+
+    /// The same method with another relaxation factor, which has to lie in the range the method
+    /// converges for. Sphere tracing has no factor to set
+    pub fn with_factor(&self, factor: f32) -> Result<MarchMethod, String>
+    {
+        return match self
+        {
+            MarchMethod::Sphere => Err(String::from("sphere has no relaxation factor")),
+            MarchMethod::OverRelaxed(_) if (1.0..2.0).contains(&factor) => Ok(MarchMethod::OverRelaxed(factor)),
+            MarchMethod::OverRelaxed(_) => Err(format!("relaxed needs a factor of at least 1 and less than 2, found {}", factor)),
+            MarchMethod::Accelerated(_) if (0.0..=1.0).contains(&factor) => Ok(MarchMethod::Accelerated(factor)),
+            MarchMethod::Accelerated(_) => Err(format!("accelerated needs a factor between 0 and 1, found {}", factor)),
+        };
+    }
+}
+
+/// Trades speed for accuracy when marching rays
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RaymarchSettings
+{
+    /// Rays that travel further than this are misses
+    pub max_distance: f32,
+    pub max_steps: u32,
+    /// Rays closer than this to a surface have hit it
+    pub surface_distance: f32,
+    /// Offset used to sample the gradient for normals
+    pub normal_epsilon: f32,
+    pub method: MarchMethod,
+}
+
+impl RaymarchSettings
+{
+    pub const PREVIEW: RaymarchSettings = RaymarchSettings{ max_distance: 50.0, max_steps: 48, surface_distance: 0.005, normal_epsilon: 0.02, method: MarchMethod::Sphere };
+    pub const DEFAULT: RaymarchSettings = RaymarchSettings{ max_distance: 100.0, max_steps: 100, surface_distance: 0.001, normal_epsilon: 0.01, method: MarchMethod::Sphere };
+    pub const FINAL: RaymarchSettings = RaymarchSettings{ max_distance: 200.0, max_steps: 400, surface_distance: 0.0001, normal_epsilon: 0.001, method: MarchMethod::Sphere };
+
+    pub const PRESET_NAMES: &'static str = "preview, default or final";
+
+    /// Looks up a named preset: preview, default or final
+    pub fn preset(name: &str) -> Option<RaymarchSettings>
+    {
+        return match name
+        {
+            "preview" => Some(RaymarchSettings::PREVIEW),
+            "default" => Some(RaymarchSettings::DEFAULT),
+            "final" => Some(RaymarchSettings::FINAL),
+            _ => None,
+        };
+    }
+}
+
+impl Default for RaymarchSettings
+{
+    fn default() -> RaymarchSettings
+    {
+        return RaymarchSettings::DEFAULT;
+    }
+}
+
+/// Everything learned about a ray while marching it
+#[derive(Copy, Clone, Debug)]
+pub struct Hit<V: Vector>
+{
+    /// The ray stopped within the maximum distance, at a surface or where it ran out of steps
+    pub hit: bool,
+    /// The ray got within the surface distance of a surface, rather than running out of steps
+    pub converged: bool,
+    /// Distance travelled along the ray
+    pub distance: f32,
+    /// Where the ray stopped
+    pub position: V,
+    pub steps: u32,
+    /// Smallest distance to the scene seen along the ray, small values mean the ray grazed a surface
+    pub closest_approach: f32,
+    /// Distance to the scene at the final position
+    pub sdf_value: f32,
+    /// Index of the object whose surface the ray stopped at, if the sdf reports one
+    pub object_id: Option<usize>,
+}
+
+/// Why a ray stopped without reaching a surface
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MissReason
+{
+    /// Ran out of steps while still close enough to the scene to keep marching
+    MaxSteps,
+    /// Went past the maximum distance
+    MaxDistance,
+}
+
+impl<V: Vector> Hit<V>
+{
+    /// None for rays that converged on a surface
+    pub fn miss_reason(&self, settings: &RaymarchSettings) -> Option<MissReason>
+    {
+        if self.converged
+        {
+            return None;
+        }
+        if self.distance > settings.max_distance
+        {
+            return Some(MissReason::MaxDistance);
+        }
+        return Some(MissReason::MaxSteps);
+    }
+}
 
-pub fn raymarch<V: Vector>(ro: &V, rd: &V, distance_function: fn(V) -> f32) -> f32
+pub fn raymarch<V: Vector, S: Sdf<V> + ?Sized>(ro: &V, rd: &V, sdf: &S, settings: &RaymarchSettings) -> Hit<V>
 {
     let mut d_origin: f32 = 0.0; // Distance from Origin
+    let mut d_surface: f32 = f32::MAX;
+    let mut closest_approach: f32 = f32::MAX;
+    let mut object_id: Option<usize> = None;
+    let mut steps: u32 = 0;
+    let mut converged: bool = false;
 
-    for _i in 0..MAX_STEPS
+    // The last point that was not an overshoot, its distance to the scene and the step taken from it
+    let mut previous_d_surface: f32 = 0.0;
+    let mut previous_step: f32 = 0.0;
+    let mut relaxation: f32 = if let MarchMethod::OverRelaxed(factor) = settings.method { factor } else { 1.0 };
+
+    while steps < settings.max_steps
     {
         let p: V = *ro + (*rd * d_origin);
-        let d_surface: f32  = distance_function(p);
-        d_origin += d_surface;
-        if d_surface < SURF_DIST || d_origin > MAX_DIST
+        (d_surface, object_id) = sdf.evaluate(p);
+        steps += 1;
+
+        // When the unbounding spheres of the last two points do not overlap, a lengthened step may
+        // have jumped over a surface. Go back and take a plain sphere tracing step instead
+        if previous_step > previous_d_surface && previous_d_surface + d_surface < previous_step
+        {
+            d_origin += previous_d_surface - previous_step;
+            previous_step = previous_d_surface;
+            relaxation = 1.0;
+            continue;
+        }
+
+        closest_approach = f32::min(closest_approach, d_surface);
+
+        let step: f32 = match settings.method
+        {
+            MarchMethod::Sphere => d_surface,
+            MarchMethod::OverRelaxed(_) => d_surface * relaxation,
+            MarchMethod::Accelerated(factor) =>
+            {
+                // Ratio of the next step to this distance if the surface were the plane through the last two samples
+                let denominator: f32 = previous_step + previous_d_surface - d_surface;
+                let ratio: f32 = if denominator > 0.0 { (previous_step - previous_d_surface + d_surface) / denominator } else { 0.0 };
+                d_surface * (1.0 + factor * f32::clamp(ratio, 0.0, 1.0))
+            }
+        };
+
+        d_origin += step;
+        previous_d_surface = d_surface;
+        previous_step = step;
+
+        if d_surface < settings.surface_distance
+        {
+            converged = true;
+            break;
+        }
+        if d_origin > settings.max_distance
         {
             break;
         }
     }
 
-    return d_origin;
+    // Rays that run out of steps short of the maximum distance are hits too, they are close to a
+    // surface they are grazing
+    return Hit{
+        hit: d_origin <= settings.max_distance,
+        converged: converged,
+        distance: d_origin,
+        position: *ro + (*rd * d_origin),
+        steps: steps,
+        closest_approach: closest_approach,
+        sdf_value: d_surface,
+        object_id: object_id,
+    };
 }
 
-pub fn normal3(p: Float3, distance_function: fn(Float3) -> f32) -> Float3
+/// Marches a packet of rays together, one per lane, giving the same hits as `raymarch` for each lane
+/// Every lane keeps stepping while any lane is still marching, the finished lanes are masked off
+pub fn raymarch_packet<V: Vector, S: Sdf<V> + ?Sized>(ro: &V::Packet, rd: &V::Packet, sdf: &S, settings: &RaymarchSettings) -> [Hit<V>; LANES]
 {
-    const E: f32 = 0.01;
-    let n: Float3 = distance_function(p) - Float3::new(
-        distance_function(p - Float3::new(E, 0.0, 0.0)),
-        distance_function(p - Float3::new(0.0, E, 0.0)),
-        distance_function(p - Float3::new(0.0, 0.0, E))
+    let mut d_origin: F32x8 = F32x8::ZERO;
+    let mut d_surface: F32x8 = F32x8::splat(f32::MAX);
+    let mut closest_approach: F32x8 = F32x8::splat(f32::MAX);
+    let mut object_ids: [Option<usize>; LANES] = [None; LANES];
+    let mut steps: u32 = 0;
+    let mut lane_steps: [u32; LANES] = [0; LANES];
+    let mut converged: Mask8 = Mask8::NONE;
+    let mut marching: Mask8 = Mask8::ALL;
+
+    let mut previous_d_surface: F32x8 = F32x8::ZERO;
+    let mut previous_step: F32x8 = F32x8::ZERO;
+    let mut relaxation: F32x8 = F32x8::splat(if let MarchMethod::OverRelaxed(factor) = settings.method { factor } else { 1.0 });
+
+    while steps < settings.max_steps && marching.any()
+    {
+        let p: V::Packet = *ro + (*rd * d_origin);
+        let (distances, ids) = sdf.evaluate_packet(p);
+        steps += 1;
+
+        d_surface = F32x8::select(marching, distances, d_surface);
+        for i in 0..LANES
+        {
+            if marching.lane(i)
+            {
+                object_ids[i] = ids[i];
+                lane_steps[i] = steps;
+            }
+        }
+
+        // Lanes that may have jumped over a surface go back, the same as in `raymarch`
+        let overshot: Mask8 = marching & previous_step.greater_than(previous_d_surface) & (previous_d_surface + d_surface).less_than(previous_step);
+        d_origin = F32x8::select(overshot, d_origin + (previous_d_surface - previous_step), d_origin);
+        previous_step = F32x8::select(overshot, previous_d_surface, previous_step);
+        relaxation = F32x8::select(overshot, F32x8::splat(1.0), relaxation);
+
+        let stepping: Mask8 = marching & !overshot;
+        closest_approach = F32x8::select(stepping, F32x8::min(closest_approach, d_surface), closest_approach);
+
+        let step: F32x8 = match settings.method
+        {
+            MarchMethod::Sphere => d_surface,
+            MarchMethod::OverRelaxed(_) => d_surface * relaxation,
+            MarchMethod::Accelerated(factor) =>
+            {
+                let denominator: F32x8 = previous_step + previous_d_surface - d_surface;
+                let ratio: F32x8 = F32x8::select(
+                    denominator.greater_than(F32x8::ZERO),
+                    (previous_step - previous_d_surface + d_surface) / denominator,
+                    F32x8::ZERO
+                );
+                d_surface * (ratio.clamp(0.0, 1.0) * factor + 1.0)
+            }
+        };
+
+        d_origin = F32x8::select(stepping, d_origin + step, d_origin);
+        previous_d_surface = F32x8::select(stepping, d_surface, previous_d_surface);
+        previous_step = F32x8::select(stepping, step, previous_step);
+
+        let surface: Mask8 = stepping & d_surface.less_than(F32x8::splat(settings.surface_distance));
+        let escaped: Mask8 = stepping & d_origin.greater_than(F32x8::splat(settings.max_distance));
+        converged = converged | surface;
+        marching = marching & !surface & !escaped;
+    }
+
+    let positions: V::Packet = *ro + (*rd * d_origin);
+    return std::array::from_fn(|i| Hit{
+        hit: d_origin.lane(i) <= settings.max_distance,
+        converged: converged.lane(i),
+        distance: d_origin.lane(i),
+        position: positions.lane(i),
+        steps: lane_steps[i],
+        closest_approach: closest_approach.lane(i),
+        sdf_value: d_surface.lane(i),
+        object_id: object_ids[i],
+    });
+}
+
+pub fn normal3<S: Sdf<Float3> + ?Sized>(p: Float3, sdf: &S, settings: &RaymarchSettings) -> Float3
+{
+    let e: f32 = settings.normal_epsilon;
+    let n: Float3 = sdf.distance(p) - Float3::new(
+        sdf.distance(p - Float3::new(e, 0.0, 0.0)),
+        sdf.distance(p - Float3::new(0.0, e, 0.0)),
+        sdf.distance(p - Float3::new(0.0, 0.0, e))
     );
     
     if n.length_squared() == 0.0
@@ -41,14 +316,14 @@ pub fn normal3(p: Float3, distance_function: fn(Float3) -> f32) -> Float3
     return n.normalized();
 }
 
-pub fn normal4(p: Float4, distance_function: fn(Float4) -> f32) -> Float4
+pub fn normal4<S: Sdf<Float4> + ?Sized>(p: Float4, sdf: &S, settings: &RaymarchSettings) -> Float4
 {
-    const E: f32 = 0.01;
-    let n: Float4 = distance_function(p) - Float4::new(
-        distance_function(p - Float4::new(E, 0.0, 0.0, 0.0)),
-        distance_function(p - Float4::new(0.0, E, 0.0, 0.0)),
-        distance_function(p - Float4::new(0.0, 0.0, E, 0.0)),
-        distance_function(p - Float4::new(0.0, 0.0, 0.0, E))
+    let e: f32 = settings.normal_epsilon;
+    let n: Float4 = sdf.distance(p) - Float4::new(
+        sdf.distance(p - Float4::new(e, 0.0, 0.0, 0.0)),
+        sdf.distance(p - Float4::new(0.0, e, 0.0, 0.0)),
+        sdf.distance(p - Float4::new(0.0, 0.0, e, 0.0)),
+        sdf.distance(p - Float4::new(0.0, 0.0, 0.0, e))
     );
     
     if n.length_squared() == 0.0
@@ -58,3 +333,43 @@ pub fn normal4(p: Float4, distance_function: fn(Float4) -> f32) -> Float4
 
     return n.normalized();
 }
+
+/// `normal3` for every lane of a packet
+pub fn normal3_packet<S: Sdf<Float3> + ?Sized>(p: Float3x8, sdf: &S, settings: &RaymarchSettings) -> Float3x8
+{
+    let e: f32 = settings.normal_epsilon;
+    let d: F32x8 = sdf.distance_packet(p);
+    let n: Float3x8 = Float3x8::new(
+        d - sdf.distance_packet(p - Float3x8::splat(Float3::new(e, 0.0, 0.0))),
+        d - sdf.distance_packet(p - Float3x8::splat(Float3::new(0.0, e, 0.0))),
+        d - sdf.distance_packet(p - Float3x8::splat(Float3::new(0.0, 0.0, e)))
+    );
+
+    return normalized_packet(n);
+}
+
+/// `normal4` for every lane of a packet
+pub fn normal4_packet<S: Sdf<Float4> + ?Sized>(p: Float4x8, sdf: &S, settings: &RaymarchSettings) -> Float4x8
+{
+    let e: f32 = settings.normal_epsilon;
+    let d: F32x8 = sdf.distance_packet(p);
+    let n: Float4x8 = Float4x8::new(
+        d - sdf.distance_packet(p - Float4x8::splat(Float4::new(e, 0.0, 0.0, 0.0))),
+        d - sdf.distance_packet(p - Float4x8::splat(Float4::new(0.0, e, 0.0, 0.0))),
+        d - sdf.distance_packet(p - Float4x8::splat(Float4::new(0.0, 0.0, e, 0.0))),
+        d - sdf.distance_packet(p - Float4x8::splat(Float4::new(0.0, 0.0, 0.0, e)))
+    );
+
+    return normalized_packet(n);
+}
+
+/// Normalizes every lane that is not zero
+fn normalized_packet<P: VectorPacket>(n: P) -> P
+{
+    let length: F32x8 = n.length();
+    return P::select(length.greater_than(F32x8::ZERO), n / length, n);
+}
+
+#[cfg(test)]
+#[path = "raymarching_tests.rs"]
+mod tests;
@@ -0,0 +1,216 @@
+use crate::camera::{Camera, Camera4};
+use crate::mathematics::f32x8::LANES;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::float4x8::Float4x8;
+use crate::mathematics::multivectors::{Magnitude, VectorPacket};
+use crate::mathematics::rotor4::Rotor4;
+use crate::raymarching::*;
+use crate::scene::{Scene, SubScene4};
+use crate::scene_file::parse_scene;
+
+const TWO_SPHERES: &str = "
+object sphere
+    position 0 0 0 0
+    radius 1
+end
+object sphere
+    position 0 3 0 0
+    radius 1
+end
+";
+
+#[test]
+fn hit_records_position_and_object()
+{
+    let scene: Scene = parse_scene(TWO_SPHERES, 1.0).unwrap();
+    let scene_4d: &SubScene4 = scene.scene_4d.as_ref().unwrap();
+
+    let ro: Float4 = Float4::new(0.0, 3.0, 5.0, 0.0);
+    let rd: Float4 = Float4::new(0.0, 0.0, -1.0, 0.0);
+    let hit: Hit<Float4> = raymarch(&ro, &rd, scene_4d, &RaymarchSettings::DEFAULT);
+
+    assert!(hit.hit);
+    assert!(f32::abs(hit.distance - 4.0) < 0.001);
+    assert!(f32::abs(hit.position.z - 1.0) < 0.001);
+    assert!(hit.sdf_value < 0.001);
+    assert!(hit.steps >= 1);
+    assert_eq!(hit.object_id, Some(1));
+}
+
+#[test]
+fn miss_records_closest_approach()
+{
+    let scene: Scene = parse_scene(TWO_SPHERES, 1.0).unwrap();
+    let scene_4d: &SubScene4 = scene.scene_4d.as_ref().unwrap();
+
+    // Passes 0.5 above the surface of the first sphere
+    let ro: Float4 = Float4::new(0.0, 1.5, 5.0, 0.0);
+    let rd: Float4 = Float4::new(0.0, 0.0, -1.0, 0.0);
+    let hit: Hit<Float4> = raymarch(&ro, &rd, scene_4d, &RaymarchSettings::DEFAULT);
+
+    assert!(!hit.hit);
+    assert!(hit.distance > RaymarchSettings::DEFAULT.max_distance);
+    assert!(f32::abs(hit.closest_approach - 0.5) < 0.01);
+}
+
+#[test]
+fn camera_rays_hit_the_default_scene()
+{
+    let camera: Camera4 = Camera4::new(Float4::new(0.0, 0.0, 0.0, 0.0), Float4::new(0.0, 0.0, 4.0, 0.0), Rotor4::IDENTITY, 1.0, 70.0, 1.0);
+    let scene: Scene = Scene::new_4d(camera, Float4::new(2.0, 2.0, 4.0, 0.0));
+    let scene_4d: &SubScene4 = scene.scene_4d.as_ref().unwrap();
+
+    let ro: Float4 = scene_4d.camera.get_camera_position();
+    let rd: Float4 = (Float4::new(0.0, 0.0, 0.0, 0.0) - ro) / 4.0;
+    let hit: Hit<Float4> = raymarch(&ro, &rd, scene_4d, &RaymarchSettings::DEFAULT);
+    assert!(hit.hit);
+    assert_eq!(hit.object_id, Some(0));
+}
+
+#[test]
+fn settings_limit_the_march()
+{
+    let scene: Scene = parse_scene(TWO_SPHERES, 1.0).unwrap();
+    let scene_4d: &SubScene4 = scene.scene_4d.as_ref().unwrap();
+
+    let ro: Float4 = Float4::new(0.0, 0.0, 10.0, 0.0);
+    let rd: Float4 = Float4::new(0.0, 0.0, -1.0, 0.0);
+
+    let short: RaymarchSettings = RaymarchSettings{ max_distance: 5.0, ..RaymarchSettings::DEFAULT };
+    assert!(!raymarch(&ro, &rd, scene_4d, &short).hit);
+
+    // Running out of steps short of the maximum distance still counts as a hit, as it always has
+    let few_steps: RaymarchSettings = RaymarchSettings{ max_steps: 1, surface_distance: 0.0, ..RaymarchSettings::DEFAULT };
+    let hit: Hit<Float4> = raymarch(&ro, &rd, scene_4d, &few_steps);
+    assert!(hit.hit && !hit.converged);
+    assert_eq!(hit.steps, 1);
+
+    assert_eq!(RaymarchSettings::preset("final"), Some(RaymarchSettings::FINAL));
+    assert_eq!(RaymarchSettings::preset("best"), None);
+}
+
+const METHODS: [MarchMethod; 3] = [MarchMethod::Sphere, MarchMethod::OverRelaxed(1.6), MarchMethod::Accelerated(0.9)];
+
+#[test]
+fn methods_agree_on_hits()
+{
+    let scene: Scene = parse_scene(TWO_SPHERES, 1.0).unwrap();
+    let scene_4d: &SubScene4 = scene.scene_4d.as_ref().unwrap();
+
+    let ro: Float4 = Float4::new(0.0, 3.0, 5.0, 0.0);
+    let rd: Float4 = Float4::new(0.0, -0.2, -1.0, 0.0).normalized();
+    let reference: Hit<Float4> = raymarch(&ro, &rd, scene_4d, &RaymarchSettings::DEFAULT);
+    for method in METHODS
+    {
+        let hit: Hit<Float4> = raymarch(&ro, &rd, scene_4d, &RaymarchSettings{ method: method, ..RaymarchSettings::DEFAULT });
+        assert!(hit.hit, "{:?}", method);
+        assert!(f32::abs(hit.distance - reference.distance) < 0.01, "{:?}", method);
+        assert_eq!(hit.object_id, reference.object_id, "{:?}", method);
+    }
+}
+
+#[test]
+fn relaxed_methods_take_fewer_steps_near_surfaces()
+{
+    // A ray running alongside a plane, which sphere tracing crawls along
+    let floor = |p: Float4| p.y + 1.0;
+    let ro: Float4 = Float4::new(0.0, 0.0, 0.0, 0.0);
+    let rd: Float4 = Float4::new(0.0, -0.05, -1.0, 0.0).normalized();
+
+    let steps: Vec<u32> = METHODS.iter()
+        .map(|method| raymarch(&ro, &rd, &floor, &RaymarchSettings{ method: *method, ..RaymarchSettings::FINAL }).steps)
+        .collect();
+    assert!(steps[1] < steps[0], "{:?}", steps);
+    assert!(steps[2] < steps[0], "{:?}", steps);
+}
+
+#[test]
+fn overshoots_fall_back()
+{
+    // A thin slab that a large relaxation factor would step straight through
+    let slab = |p: Float4| f32::abs(p.z) - 0.05;
+    let ro: Float4 = Float4::new(0.0, 0.0, 10.0, 0.0);
+    let rd: Float4 = Float4::new(0.0, 0.0, -1.0, 0.0);
+
+    for method in [MarchMethod::OverRelaxed(1.99), MarchMethod::Accelerated(1.0)]
+    {
+        let hit: Hit<Float4> = raymarch(&ro, &rd, &slab, &RaymarchSettings{ method: method, ..RaymarchSettings::DEFAULT });
+        assert!(hit.hit, "{:?}", method);
+        assert!(f32::abs(hit.position.z - 0.05) < 0.01, "{:?} stopped at {}", method, hit.position.z);
+    }
+}
+
+#[test]
+fn factors_stay_in_the_range_that_converges()
+{
+    assert_eq!(MarchMethod::from_name("relaxed"), Some(METHODS[1]));
+    assert_eq!(MarchMethod::from_name("accelerated").unwrap().with_factor(0.0), Ok(MarchMethod::Accelerated(0.0)));
+    assert_eq!(METHODS[1].with_factor(1.0), Ok(MarchMethod::OverRelaxed(1.0)));
+
+    // 0 stalls every ray, negative factors march backwards and larger ones overshoot on every step
+    for factor in [0.0, -1.0, 2.0, f32::NAN]
+    {
+        assert!(METHODS[1].with_factor(factor).is_err(), "relaxed {}", factor);
+    }
+    for factor in [-0.1, 1.1, f32::NAN]
+    {
+        assert!(METHODS[2].with_factor(factor).is_err(), "accelerated {}", factor);
+    }
+    assert!(MarchMethod::Sphere.with_factor(1.5).is_err());
+}
+
+#[test]
+fn packets_march_like_single_rays()
+{
+    let scene: Scene = parse_scene(TWO_SPHERES, 1.0).unwrap();
+    let scene_4d: &SubScene4 = scene.scene_4d.as_ref().unwrap();
+
+    // A fan of rays that hit either sphere, graze past or miss entirely
+    let ro: Float4 = Float4::new(0.0, 1.5, 5.0, 0.0);
+    let directions: [Float4; LANES] = std::array::from_fn(|i| Float4::new(0.1 * i as f32 - 0.35, 0.4 - 0.15 * i as f32, -1.0, 0.0).normalized());
+    let ro_packet: Float4x8 = Float4x8::splat(ro);
+    let rd_packet: Float4x8 = Float4x8::from_lanes(directions);
+
+    for method in METHODS
+    {
+        let settings: RaymarchSettings = RaymarchSettings{ method: method, ..RaymarchSettings::DEFAULT };
+        let hits: [Hit<Float4>; LANES] = raymarch_packet(&ro_packet, &rd_packet, scene_4d, &settings);
+        for (i, rd) in directions.iter().enumerate()
+        {
+            let single: Hit<Float4> = raymarch(&ro, rd, scene_4d, &settings);
+            assert_eq!(hits[i].hit, single.hit, "{:?} lane {}", method, i);
+            assert_eq!(hits[i].converged, single.converged, "{:?} lane {}", method, i);
+            assert_eq!(hits[i].steps, single.steps, "{:?} lane {}", method, i);
+            assert_eq!(hits[i].distance, single.distance, "{:?} lane {}", method, i);
+            assert_eq!(hits[i].closest_approach, single.closest_approach, "{:?} lane {}", method, i);
+            assert_eq!(hits[i].object_id, single.object_id, "{:?} lane {}", method, i);
+        }
+        assert!(hits.iter().any(|hit| hit.hit) && hits.iter().any(|hit| !hit.hit));
+    }
+
+    let positions: Float4x8 = Float4x8::from_lanes(std::array::from_fn(|i| ro + directions[i] * 3.5));
+    let normals: Float4x8 = normal4_packet(positions, scene_4d, &RaymarchSettings::DEFAULT);
+    for i in 0..LANES
+    {
+        assert_eq!(normals.lane(i), normal4(positions.lane(i), scene_4d, &RaymarchSettings::DEFAULT));
+    }
+}
+
+#[test]
+fn misses_report_why_they_stopped()
+{
+    let scene: Scene = parse_scene(TWO_SPHERES, 1.0).unwrap();
+    let scene_4d: &SubScene4 = scene.scene_4d.as_ref().unwrap();
+    let ro: Float4 = Float4::new(0.0, 0.0, 5.0, 0.0);
+
+    let hit: Hit<Float4> = raymarch(&ro, &Float4::new(0.0, 0.0, -1.0, 0.0), scene_4d, &RaymarchSettings::DEFAULT);
+    assert_eq!(hit.miss_reason(&RaymarchSettings::DEFAULT), None);
+
+    let away: Hit<Float4> = raymarch(&ro, &Float4::new(0.0, 0.0, 1.0, 0.0), scene_4d, &RaymarchSettings::DEFAULT);
+    assert_eq!(away.miss_reason(&RaymarchSettings::DEFAULT), Some(MissReason::MaxDistance));
+
+    let few_steps: RaymarchSettings = RaymarchSettings{ max_steps: 2, ..RaymarchSettings::DEFAULT };
+    let short: Hit<Float4> = raymarch(&Float4::new(0.0, 1.5, 5.0, 0.0), &Float4::new(0.0, 0.0, -1.0, 0.0), scene_4d, &few_steps);
+    assert_eq!(short.miss_reason(&few_steps), Some(MissReason::MaxSteps));
+    assert!(short.hit);
+}
@@ -0,0 +1,138 @@
+//! Debug views showing what the raymarcher did for each pixel instead of the shaded image.
+
+use crate::mathematics::float3::Float3;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::multivectors::Vector;
+use crate::raymarching::{Hit, MissReason, RaymarchSettings};
+
+/// What each pixel shows
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum RenderMode
+{
+    /// Diffuse lighting of the object's material
+    #[default]
+    Shaded,
+    /// Raymarching steps taken as a heatmap from blue for none to red for the maximum, hits or not
+    Steps,
+    /// Distance to the surface, white up close fading to black at the maximum distance
+    Depth,
+    /// World space normals, xyz as rgb. The w of 4D normals brightens or darkens the colour
+    Normals,
+    /// A distinct colour for each object, white for surfaces without one
+    Objects,
+    /// Grey hits, red for rays that ran out of steps and blue for rays that went past the maximum distance
+    Misses,
+}
+
+impl RenderMode
+{
+    pub const NAMES: &'static str = "shaded, steps, depth, normals, objects or misses";
+
+    /// Every mode in the order the window cycles through them
+    pub const ALL: [RenderMode; 6] = [RenderMode::Shaded, RenderMode::Steps, RenderMode::Depth, RenderMode::Normals, RenderMode::Objects, RenderMode::Misses];
+
+    pub fn from_name(name: &str) -> Option<RenderMode>
+    {
+        return RenderMode::ALL.into_iter().find(|mode| mode.name() == name);
+    }
+
+    pub fn name(&self) -> &'static str
+    {
+        return match self
+        {
+            RenderMode::Shaded => "shaded",
+            RenderMode::Steps => "steps",
+            RenderMode::Depth => "depth",
+            RenderMode::Normals => "normals",
+            RenderMode::Objects => "objects",
+            RenderMode::Misses => "misses",
+        };
+    }
+
+    /// The mode after this one, wrapping back to shaded
+    pub fn next(&self) -> RenderMode
+    {
+        let index: usize = RenderMode::ALL.iter().position(|mode| mode == self).unwrap_or(0);
+        return RenderMode::ALL[(index + 1) % RenderMode::ALL.len()];
+    }
+
+    /// Colour of a ray in the modes that only need the hit, None for shaded and normals
+    pub fn hit_colour<V: Vector>(&self, hit: &Hit<V>, settings: &RaymarchSettings) -> Option<Float3>
+    {
+        let black: Float3 = Float3::new(0.0, 0.0, 0.0);
+        return match self
+        {
+            RenderMode::Shaded | RenderMode::Normals => None,
+            RenderMode::Steps => Some(heatmap(hit.steps as f32 / u32::max(settings.max_steps, 1) as f32)),
+            RenderMode::Depth =>
+            {
+                if !hit.hit
+                {
+                    return Some(black);
+                }
+                let brightness: f32 = 1.0 - f32::clamp(hit.distance / settings.max_distance, 0.0, 1.0);
+                Some(Float3::new(brightness, brightness, brightness))
+            }
+            RenderMode::Objects =>
+            {
+                if !hit.hit
+                {
+                    return Some(black);
+                }
+                Some(hit.object_id.map_or(Float3::new(1.0, 1.0, 1.0), object_colour))
+            }
+            RenderMode::Misses => Some(match hit.miss_reason(settings)
+            {
+                None => Float3::new(0.5, 0.5, 0.5),
+                Some(MissReason::MaxSteps) => Float3::new(1.0, 0.0, 0.0),
+                Some(MissReason::MaxDistance) => Float3::new(0.0, 0.0, 1.0),
+            }),
+        };
+    }
+}
+
+/// A unit normal as a colour, each axis from 0 for -1 to 1 for 1
+pub fn normal3_colour(n: Float3) -> Float3
+{
+    return n * 0.5 + 0.5;
+}
+
+/// The xyz of a 4D normal as a colour, mixed towards white as w goes to 1 and black as it goes to -1
+pub fn normal4_colour(n: Float4) -> Float3
+{
+    let colour: Float3 = normal3_colour(Float3::new(n.x, n.y, n.z));
+    let towards: Float3 = if n.w > 0.0 { Float3::new(1.0, 1.0, 1.0) } else { Float3::new(0.0, 0.0, 0.0) };
+    return colour + (towards - colour) * f32::abs(n.w);
+}
+
+/// Blue through cyan, green and yellow to red as t goes from 0 to 1
+pub fn heatmap(t: f32) -> Float3
+{
+    let t: f32 = f32::clamp(t, 0.0, 1.0) * 4.0;
+    return Float3::new(
+        f32::clamp(t - 2.0, 0.0, 1.0),
+        f32::clamp(if t < 2.0 { t } else { 4.0 - t }, 0.0, 1.0),
+        f32::clamp(2.0 - t, 0.0, 1.0),
+    );
+}
+
+/// A bright colour for an object index, neighbouring indices get very different hues
+pub fn object_colour(id: usize) -> Float3
+{
+    // Stepping the hue by the golden ratio keeps consecutive colours far apart
+    let hue: f32 = (id as f32 * 0.618034).fract() * 6.0;
+    let x: f32 = 1.0 - f32::abs(hue % 2.0 - 1.0);
+    return match hue as u32
+    {
+        0 => Float3::new(1.0, x, 0.0),
+        1 => Float3::new(x, 1.0, 0.0),
+        2 => Float3::new(0.0, 1.0, x),
+        3 => Float3::new(0.0, x, 1.0),
+        4 => Float3::new(x, 0.0, 1.0),
+        _ => Float3::new(1.0, 0.0, x),
+    };
+}
+
+#[cfg(test)]
+#[path = "render_mode_tests.rs"]
+mod tests;
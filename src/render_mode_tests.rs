@@ -0,0 +1,77 @@
+use crate::mathematics::float3::Float3;
+use crate::mathematics::float4::Float4;
+use crate::raymarching::{Hit, RaymarchSettings};
+use crate::render_mode::*;
+
+fn hit(hit: bool, distance: f32, steps: u32, object_id: Option<usize>) -> Hit<Float4>
+{
+    return Hit{
+        hit: hit,
+        converged: hit,
+        distance: distance,
+        position: Float4::new(0.0, 0.0, 0.0, 0.0),
+        steps: steps,
+        closest_approach: 0.0,
+        sdf_value: 0.0,
+        object_id: object_id,
+    };
+}
+
+#[test]
+fn modes_cycle_by_name()
+{
+    for mode in RenderMode::ALL
+    {
+        assert_eq!(RenderMode::from_name(mode.name()), Some(mode));
+    }
+    assert_eq!(RenderMode::from_name("wireframe"), None);
+    assert_eq!(RenderMode::Shaded.next(), RenderMode::Steps);
+    assert_eq!(RenderMode::Misses.next(), RenderMode::Shaded);
+}
+
+#[test]
+fn heatmap_runs_from_blue_to_red()
+{
+    assert_eq!(heatmap(0.0), Float3::new(0.0, 0.0, 1.0));
+    assert_eq!(heatmap(0.5), Float3::new(0.0, 1.0, 0.0));
+    assert_eq!(heatmap(1.0), Float3::new(1.0, 0.0, 0.0));
+    assert_eq!(heatmap(2.0), Float3::new(1.0, 0.0, 0.0));
+}
+
+#[test]
+fn hit_colours()
+{
+    let settings: RaymarchSettings = RaymarchSettings::DEFAULT;
+    let surface: Hit<Float4> = hit(true, settings.max_distance * 0.25, settings.max_steps / 2, Some(1));
+    let missed: Hit<Float4> = hit(false, settings.max_distance * 2.0, 10, None);
+
+    assert_eq!(RenderMode::Shaded.hit_colour(&surface, &settings), None);
+    assert_eq!(RenderMode::Normals.hit_colour(&surface, &settings), None);
+    assert_eq!(RenderMode::Depth.hit_colour(&surface, &settings), Some(Float3::new(0.75, 0.75, 0.75)));
+    assert_eq!(RenderMode::Depth.hit_colour(&missed, &settings), Some(Float3::new(0.0, 0.0, 0.0)));
+    assert_eq!(RenderMode::Objects.hit_colour(&surface, &settings), Some(object_colour(1)));
+    assert_eq!(RenderMode::Misses.hit_colour(&missed, &settings), Some(Float3::new(0.0, 0.0, 1.0)));
+
+    // Rays that stop short of the maximum distance without converging ran out of steps
+    let stuck: Hit<Float4> = Hit{ converged: false, ..hit(true, 1.0, settings.max_steps, None) };
+    assert_eq!(RenderMode::Misses.hit_colour(&stuck, &settings), Some(Float3::new(1.0, 0.0, 0.0)));
+    assert_eq!(RenderMode::Steps.hit_colour(&stuck, &settings), Some(heatmap(1.0)));
+}
+
+#[test]
+fn normal_colours_show_w()
+{
+    assert_eq!(normal3_colour(Float3::new(1.0, 0.0, -1.0)), Float3::new(1.0, 0.5, 0.0));
+    assert_eq!(normal4_colour(Float4::new(1.0, 0.0, 0.0, 0.0)), Float3::new(1.0, 0.5, 0.5));
+    assert_eq!(normal4_colour(Float4::new(0.0, 0.0, 0.0, 1.0)), Float3::new(1.0, 1.0, 1.0));
+    assert_eq!(normal4_colour(Float4::new(0.0, 0.0, 0.0, -1.0)), Float3::new(0.0, 0.0, 0.0));
+}
+
+#[test]
+fn neighbouring_objects_get_different_colours()
+{
+    for id in 0..8
+    {
+        assert!(object_colour(id) != object_colour(id + 1));
+    }
+}
@@ -0,0 +1,324 @@
+use std::sync::{Arc, RwLock};
+
+use crate::anti_aliasing;
+use crate::application::Application;
+use crate::camera::Camera;
+use crate::framebuffer::Framebuffer;
+use crate::mathematics::f32x8::LANES;
+use crate::mathematics::float2::Float2;
+use crate::mathematics::float3::Float3;
+use crate::mathematics::float3x8::Float3x8;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::float4x8::Float4x8;
+use crate::mathematics::multivectors::{Magnitude, Vector, VectorPacket};
+use crate::raymarching::{self, Hit};
+use crate::render_mode::{self, RenderMode};
+use crate::scene::{Material, Scene, SubScene3, SubScene4};
+use crate::volume::Volume;
+use crate::worker_pool::{Tile, WorkerPool, TILE_SIZE};
+
+pub fn get_pixel_colour(uv: &Float2, scene: &Scene) -> Float3
+{
+    if !scene.is4d
+    {
+        let scene_3d: &SubScene3 = scene.scene_3d.as_ref().expect("Trying to render unassigned scene"); 
+        let ro: Float3 = scene_3d.camera.get_camera_position();
+        let rd: Float3 = scene_3d.camera.get_ray_direction(*uv);
+
+        let hit: Hit<Float3> = raymarching::raymarch(&ro, &rd, scene_3d, &scene.raymarch_settings);
+        if !hit.hit
+        {
+            return colour3(&hit, Float3::new(0.0, 0.0, 0.0), scene_3d, scene);
+        }
+        return colour3(&hit, raymarching::normal3(hit.position, scene_3d, &scene.raymarch_settings), scene_3d, scene);
+    }
+    else 
+    {
+        let scene_4d: &SubScene4 = scene.scene_4d.as_ref().expect("Trying to render unassigned scene"); 
+        if let Some(volume) = scene_4d.volume.filter(|_| scene.render_mode == RenderMode::Shaded)
+        {
+            return volume4(&volume, *uv, scene_4d, scene);
+        }
+        let (ro, rd): (Float4, Float4) = scene_4d.ray(*uv);
+
+        let hit: Hit<Float4> = raymarching::raymarch(&ro, &rd, scene_4d, &scene.raymarch_settings);
+        if !hit.hit
+        {
+            return ghost4(colour4(&hit, Float4::new(0.0, 0.0, 0.0, 0.0), *uv, scene_4d, scene), f32::INFINITY, *uv, scene_4d, scene);
+        }
+        let colour: Float3 = colour4(&hit, scene_4d.view_normal(*uv, raymarching::normal4(hit.position, scene_4d, &scene.raymarch_settings)), *uv, scene_4d, scene);
+        return ghost4(colour, hit.distance, *uv, scene_4d, scene);
+    }
+}
+
+/// The colour of a single ray with what it hit, which is what edges are found from
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RaySample
+{
+    pub colour: Float3,
+    /// Distance along the ray to the surface, infinite for misses
+    pub depth: f32,
+    pub object_id: Option<usize>,
+}
+
+impl RaySample
+{
+    pub const MISS: RaySample = RaySample{ colour: Float3{ x: 0.0, y: 0.0, z: 0.0 }, depth: f32::INFINITY, object_id: None };
+
+    fn new<V: Vector>(hit: &Hit<V>, colour: Float3) -> RaySample
+    {
+        if !hit.hit
+        {
+            return RaySample{ colour: colour, ..RaySample::MISS };
+        }
+        return RaySample{ colour: colour, depth: hit.distance, object_id: hit.object_id };
+    }
+}
+
+/// `get_pixel_colour` for a packet of uvs, the rays are marched together
+pub fn get_packet_colours(uvs: &[Float2; LANES], scene: &Scene) -> [Float3; LANES]
+{
+    return get_packet_samples(uvs, scene).map(|sample| sample.colour);
+}
+
+/// `get_packet_colours` keeping the depth and object each ray hit
+pub fn get_packet_samples(uvs: &[Float2; LANES], scene: &Scene) -> [RaySample; LANES]
+{
+    if !scene.is4d
+    {
+        let scene_3d: &SubScene3 = scene.scene_3d.as_ref().expect("Trying to render unassigned scene"); 
+        let ro: Float3x8 = Float3x8::splat(scene_3d.camera.get_camera_position());
+        let rd: Float3x8 = Float3x8::from_lanes(uvs.map(|uv| scene_3d.camera.get_ray_direction(uv)));
+
+        let hits: [Hit<Float3>; LANES] = raymarching::raymarch_packet(&ro, &rd, scene_3d, &scene.raymarch_settings);
+        let positions: Float3x8 = Float3x8::from_lanes(hits.map(|hit| hit.position));
+        let normals: Float3x8 = raymarching::normal3_packet(positions, scene_3d, &scene.raymarch_settings);
+        return std::array::from_fn(|i| RaySample::new(&hits[i], colour3(&hits[i], normals.lane(i), scene_3d, scene)));
+    }
+    else 
+    {
+        let scene_4d: &SubScene4 = scene.scene_4d.as_ref().expect("Trying to render unassigned scene"); 
+        if let Some(volume) = scene_4d.volume.filter(|_| scene.render_mode == RenderMode::Shaded)
+        {
+            // Volumes have no surfaces, so edges are only found from the colour
+            return uvs.map(|uv| RaySample{ colour: volume4(&volume, uv, scene_4d, scene), ..RaySample::MISS });
+        }
+        let rays: [(Float4, Float4); LANES] = uvs.map(|uv| scene_4d.ray(uv));
+        let ro: Float4x8 = Float4x8::from_lanes(rays.map(|ray| ray.0));
+        let rd: Float4x8 = Float4x8::from_lanes(rays.map(|ray| ray.1));
+
+        let hits: [Hit<Float4>; LANES] = raymarching::raymarch_packet(&ro, &rd, scene_4d, &scene.raymarch_settings);
+        let positions: Float4x8 = Float4x8::from_lanes(hits.map(|hit| hit.position));
+        let normals: Float4x8 = raymarching::normal4_packet(positions, scene_4d, &scene.raymarch_settings);
+        return std::array::from_fn(|i| {
+            let sample: RaySample = RaySample::new(&hits[i], colour4(&hits[i], scene_4d.view_normal(uvs[i], normals.lane(i)), uvs[i], scene_4d, scene));
+            return RaySample{ colour: ghost4(sample.colour, sample.depth, uvs[i], scene_4d, scene), ..sample };
+        });
+    }
+}
+
+/// Colour of a ray in the scene's render mode, n is the surface normal for hits
+fn colour3(hit: &Hit<Float3>, n: Float3, scene_3d: &SubScene3, scene: &Scene) -> Float3
+{
+    if let Some(colour) = scene.render_mode.hit_colour(hit, &scene.raymarch_settings)
+    {
+        return colour;
+    }
+    if scene.render_mode == RenderMode::Normals && hit.hit
+    {
+        return render_mode::normal3_colour(n);
+    }
+    return shade3(hit, n, scene_3d);
+}
+
+/// Colour of a ray in the scene's render mode, n is the surface normal for hits and uv is where the ray is on the image
+fn colour4(hit: &Hit<Float4>, n: Float4, uv: Float2, scene_4d: &SubScene4, scene: &Scene) -> Float3
+{
+    if let Some(colour) = scene.render_mode.hit_colour(hit, &scene.raymarch_settings)
+    {
+        return colour;
+    }
+    if scene.render_mode == RenderMode::Normals && hit.hit
+    {
+        return render_mode::normal4_colour(n);
+    }
+    let tint: Option<Float3> = scene_4d.w_tint.filter(|_| hit.hit).map(|tint| tint.colour(hit.position, &scene_4d.hyperplane(uv)));
+    return shade4(hit, n, tint, scene_4d);
+}
+
+/// Blends the ghosts of the geometry just off the view's hyperplane over the colour of the ray through
+/// uv, whose surface is depth along it. Ghosts behind that surface are hidden, and only the shaded
+/// mode has ghosts. Each layer is marched one ray at a time
+fn ghost4(colour: Float3, depth: f32, uv: Float2, scene_4d: &SubScene4, scene: &Scene) -> Float3
+{
+    let Some(ghosting) = scene_4d.ghosting.filter(|_| scene.render_mode == RenderMode::Shaded) else {
+        return colour;
+    };
+    let (ro, rd): (Float4, Float4) = scene_4d.ray(uv);
+    let normal: Float4 = scene_4d.hyperplane(uv).normal;
+
+    let mut colour: Float3 = colour;
+    for offset in ghosting.offsets()
+    {
+        let hit: Hit<Float4> = raymarching::raymarch(&(ro + normal * offset), &rd, scene_4d, &scene.raymarch_settings);
+        if !hit.hit || hit.distance >= depth
+        {
+            continue;
+        }
+        let n: Float4 = scene_4d.view_normal(uv, raymarching::normal4(hit.position, scene_4d, &scene.raymarch_settings));
+        let ghost: Float3 = colour4(&hit, n, uv, scene_4d, scene);
+        colour += (ghost - colour) * ghosting.layer_opacity(offset);
+    }
+    return colour;
+}
+
+/// Colour of the ray through uv in the X-ray view of a volume
+fn volume4(volume: &Volume, uv: Float2, scene_4d: &SubScene4, scene: &Scene) -> Float3
+{
+    let (ro, rd): (Float4, Float4) = scene_4d.ray(uv);
+    let axis: Float4 = volume.axis.direction(scene_4d.hyperplane(uv).normal);
+    return volume.integrate(ro, rd, axis, scene_4d, &scene.raymarch_settings);
+}
+
+/// Colour of a hit given the surface normal there, misses are black
+fn shade3(hit: &Hit<Float3>, n: Float3, scene_3d: &SubScene3) -> Float3
+{
+    if !hit.hit
+    {
+        return Float3::new(0.0, 0.0, 0.0);
+    }
+
+    let p: Float3 = hit.position;
+    let material: Material = hit.object_id.map_or(Material::WHITE, |id| scene_3d.objects[id].material);
+    
+    let mut diffuse: f32 = 0.0;
+    for light_source in &scene_3d.light_sources
+    {
+        diffuse += Float3::dot(n, (*light_source - p).normalized()) * 0.5 + 0.5;
+    }
+    diffuse /= usize::max(scene_3d.light_sources.len(), 1) as f32;
+    return diffuse * material.colour;
+}
+
+/// Colour of a hit given the surface normal there, misses are black. A tint replaces the material colour
+fn shade4(hit: &Hit<Float4>, n: Float4, tint: Option<Float3>, scene_4d: &SubScene4) -> Float3
+{
+    if !hit.hit
+    {
+        return Float3::new(0.0, 0.0, 0.0);
+    }
+
+    let p: Float4 = hit.position;
+    let material: Material = hit.object_id.map_or(Material::WHITE, |id| scene_4d.objects[id].material);
+    
+    let mut diffuse: f32 = 0.0;
+    for light_source in &scene_4d.light_sources
+    {
+        diffuse += Float4::dot(n, (*light_source - p).normalized()) * 0.5 + 0.5;
+    }
+    diffuse /= usize::max(scene_4d.light_sources.len(), 1) as f32;
+    return diffuse * tint.unwrap_or(material.colour);
+}
+
+#[derive(Copy, Clone)]
+pub struct Pixel
+{
+    pub colour: Float3,
+    pub x: u32,
+    pub y: u32,
+}
+
+/// The uv of a point on the image in pixels, pixel centres are whole numbers and y counts up from the bottom
+pub fn pixel_uv(x: f32, y: f32, application: &Application) -> Float2
+{
+    return Float2::new(x / (application.width - 1) as f32, y / (application.height - 1) as f32);
+}
+
+/// Renders one pixel on its own, anti aliasing takes every sample of the pattern since edges are
+/// only found by `render_frame`, which sees the neighbours
+pub fn render_pixel(x: u32, y: u32, scene: &Scene, application: &Application) -> Pixel
+{
+    let mut colour: Float3 = Float3::new(0.0, 0.0, 0.0);
+    
+    match application.anti_aliasing
+    {
+        None => colour = get_pixel_colour(&pixel_uv(x as f32, y as f32, application), scene),
+        Some(anti_aliasing) =>
+        {
+            let count: u32 = anti_aliasing.pattern.sample_count(anti_aliasing.samples);
+            for index in 0..count
+            {
+                let sample_offset: Float2 = anti_aliasing.pattern.offset(index, anti_aliasing.samples, x, y);
+                let uv: Float2 = pixel_uv(x as f32 + sample_offset.x, y as f32 + sample_offset.y, application);
+                colour += get_pixel_colour(&uv, scene);
+            }
+            colour = colour / count as f32;
+        }
+    }
+
+    return Pixel{colour: colour, x: x, y: y};
+}
+
+/// Renders the row of LANES pixels starting at x together, pixels past the right edge of the image are left out
+/// Like `render_pixel`, anti aliasing supersamples every pixel
+pub fn render_packet(x: u32, y: u32, scene: &Scene, application: &Application) -> Vec<Pixel>
+{
+    // Lanes past the edge repeat the last pixel so the packet stays coherent
+    let count: usize = usize::min(LANES, (application.width - x) as usize);
+    let pixels: Vec<(u32, u32)> = (0..count).map(|i| (x + i as u32, y)).collect();
+
+    let colours: [Float3; LANES] = match application.anti_aliasing
+    {
+        None =>
+        {
+            let uvs: [Float2; LANES] = std::array::from_fn(|i| pixel_uv(pixels[usize::min(i, count - 1)].0 as f32, y as f32, application));
+            get_packet_colours(&uvs, scene)
+        }
+        // Each sample is taken for the whole packet at once, in the same order as render_pixel
+        Some(anti_aliasing) => anti_aliasing.supersample(&pixels, scene, application),
+    };
+
+    return (0..count).map(|i| Pixel{ colour: colours[i], x: x + i as u32, y: y }).collect();
+}
+
+/// Render every pixel across the worker pool, each worker writes its tiles straight into the framebuffer
+/// The scene is read locked for each tile so it can be updated between frames
+/// Adaptive anti aliasing renders in two passes, see `anti_aliasing::render_adaptive`
+pub fn render_frame(pool: &WorkerPool, scene: &Arc<RwLock<Scene>>, application: &Application, framebuffer: &Arc<Framebuffer>)
+{
+    if let Some(anti_aliasing) = application.anti_aliasing && anti_aliasing.adaptive
+    {
+        anti_aliasing::render_adaptive(pool, scene, application, &anti_aliasing, framebuffer);
+        return;
+    }
+
+    let scene: Arc<RwLock<Scene>> = scene.clone();
+    let framebuffer: Arc<Framebuffer> = framebuffer.clone();
+    let application: Application = *application;
+    let tiles: Vec<Tile> = Tile::split(application.width, application.height, TILE_SIZE);
+
+    pool.run_tiles(tiles, move |tile| {
+        let scene = scene.read().expect("Scene lock was poisoned");
+        for y in tile.y..tile.y + tile.height
+        {
+            for x in (tile.x..tile.x + tile.width).step_by(LANES)
+            {
+                for pixel in render_packet(x, y, &scene, &application)
+                {
+                    // Pixel rows count up from the bottom, framebuffer rows count down from the top
+                    framebuffer.set(pixel.x, application.height - 1 - pixel.y, pixel.colour);
+                }
+            }
+        }
+    });
+}
+
+/// Render the full image into a new framebuffer without a window
+pub fn render_image(pool: &WorkerPool, scene: &Arc<RwLock<Scene>>, application: &Application) -> Framebuffer
+{
+    let framebuffer: Arc<Framebuffer> = Arc::new(Framebuffer::new(application.width, application.height));
+    render_frame(pool, scene, application, &framebuffer);
+
+    // The pool has let go of the framebuffer once the frame is finished
+    return Arc::try_unwrap(framebuffer).ok().expect("Framebuffer is still shared after rendering");
+}
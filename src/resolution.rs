@@ -0,0 +1,62 @@
+//! Dynamic resolution scaling to hold a target frame rate.
+//!
+//! The window renders at a fraction of its size and the image is stretched to fill it. After each
+//! frame the fraction is nudged towards the size that would have taken exactly the target frame
+//! time, so slow views drop resolution and fast views win it back.
+
+use crate::application::Application;
+
+/// Smallest fraction of the window size that is rendered
+pub const MIN_SCALE: f32 = 0.25;
+
+/// How far the scale moves towards its ideal each frame, lower values react slower but flicker less
+const SMOOTHING: f32 = 0.25;
+
+/// Frame times within this fraction of the target leave the scale alone
+const TOLERANCE: f64 = 0.1;
+
+/// Narrowest render width so a frame always has room for a packet of pixels
+const MIN_WIDTH: u32 = 16;
+
+#[derive(Copy, Clone, Debug)]
+pub struct DynamicResolution
+{
+    pub target_fps: f32,
+    /// Fraction of the window width and height currently rendered
+    pub scale: f32,
+}
+
+impl DynamicResolution
+{
+    /// Starts at the full window size
+    pub fn new(target_fps: f32) -> DynamicResolution
+    {
+        return DynamicResolution{ target_fps: target_fps, scale: 1.0 };
+    }
+
+    /// Adjusts the scale from the time the last frame took in seconds
+    pub fn update(&mut self, frame_time: f64)
+    {
+        let target_time: f64 = 1.0 / self.target_fps as f64;
+        if frame_time <= 0.0 || f64::abs(frame_time - target_time) < target_time * TOLERANCE
+        {
+            return;
+        }
+
+        // Render time grows with the number of pixels, which is the square of the scale
+        let ideal: f32 = self.scale * f64::sqrt(target_time / frame_time) as f32;
+        self.scale = f32::clamp(self.scale + (ideal - self.scale) * SMOOTHING, MIN_SCALE, 1.0);
+    }
+
+    /// The application to render this frame, the window's application scaled down
+    pub fn render_application(&self, window: &Application) -> Application
+    {
+        let width: u32 = u32::clamp((window.width as f32 * self.scale).round() as u32, u32::min(MIN_WIDTH, window.width), window.width);
+        let height: u32 = u32::clamp((width as f32 / window.aspect_ratio) as u32, 2, window.height);
+        return Application{ width: width, height: height, ..*window };
+    }
+}
+
+#[cfg(test)]
+#[path = "resolution_tests.rs"]
+mod tests;
@@ -0,0 +1,66 @@
+use crate::application::Application;
+use crate::resolution::{DynamicResolution, MIN_SCALE};
+
+#[test]
+fn slow_frames_lower_the_resolution()
+{
+    let mut resolution: DynamicResolution = DynamicResolution::new(30.0);
+    let mut previous: f32 = resolution.scale;
+    for _frame in 0..5
+    {
+        resolution.update(0.1);
+        assert!(resolution.scale < previous);
+        previous = resolution.scale;
+    }
+
+    // Frames close to the target leave it where it is
+    resolution.update(1.0 / 31.0);
+    assert_eq!(resolution.scale, previous);
+
+    // Fast frames win it back, but never past the window size
+    for _frame in 0..100
+    {
+        resolution.update(0.001);
+    }
+    assert_eq!(resolution.scale, 1.0);
+}
+
+#[test]
+fn settles_on_the_target()
+{
+    // A renderer whose frame time is proportional to the number of pixels
+    let full_frame_time: f64 = 0.08;
+    let mut resolution: DynamicResolution = DynamicResolution::new(50.0);
+    for _frame in 0..200
+    {
+        let scale: f64 = resolution.scale as f64;
+        resolution.update(full_frame_time * scale * scale);
+    }
+
+    let frame_time: f64 = full_frame_time * (resolution.scale * resolution.scale) as f64;
+    assert!(f64::abs(frame_time - 0.02) < 0.02 * 0.15, "settled at {}s", frame_time);
+
+    // An impossible target stops at the smallest scale
+    let mut resolution: DynamicResolution = DynamicResolution::new(1000.0);
+    for _frame in 0..200
+    {
+        resolution.update(0.05);
+    }
+    assert_eq!(resolution.scale, MIN_SCALE);
+}
+
+#[test]
+fn render_size_keeps_the_aspect_ratio()
+{
+    let window: Application = Application::new(16.0 / 9.0, 480, true);
+    let resolution: DynamicResolution = DynamicResolution{ target_fps: 30.0, scale: 0.5 };
+    let render: Application = resolution.render_application(&window);
+
+    assert_eq!(render.width, 240);
+    assert_eq!(render.height, 135);
+    assert_eq!(render.aspect_ratio, window.aspect_ratio);
+    assert_eq!(render.anti_aliasing, window.anti_aliasing);
+
+    let full: Application = DynamicResolution::new(30.0).render_application(&window);
+    assert_eq!((full.width, full.height), (window.width, window.height));
+}
@@ -0,0 +1,401 @@
+use crate::camera::{Camera, Camera3, Camera4};
+use crate::csg::{self, Blend, CsgOperation, Operand};
+use crate::mathematics::f32x8::{F32x8, LANES};
+use crate::mathematics::float2::Float2;
+use crate::mathematics::float3::Float3;
+use crate::mathematics::float3x8::Float3x8;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::float4x8::Float4x8;
+use crate::mathematics::bivector3::Bivector3;
+use crate::mathematics::bivector4::Bivector4;
+use crate::mathematics::multivectors::{Magnitude, Rotor};
+use crate::mathematics::rotor3::Rotor3;
+use crate::mathematics::rotor4::Rotor4;
+use crate::multi_view;
+use crate::raymarching::RaymarchSettings;
+use crate::render_mode::RenderMode;
+use crate::sdf::{Box3, Box4, Sdf, SdfNode};
+use crate::slice::Slice;
+use crate::transform::{Transform3, Transform4};
+use crate::volume::Volume;
+use crate::w_shading::{Ghosting, WTint};
+
+#[derive(Copy, Clone, Debug)]
+pub struct Material
+{
+    pub colour: Float3,
+}
+
+impl Material
+{
+    pub const WHITE: Material = Material{ colour: Float3{ x: 1.0, y: 1.0, z: 1.0 } };
+}
+
+pub struct SceneObject3
+{
+    pub sdf: SdfNode<Float3>,
+    /// Placement relative to the parent, or to the scene for objects without one
+    pub transform: Transform3,
+    /// Index of the parent object, parents must come before their children
+    pub parent: Option<usize>,
+    /// Rotation applied to the transform every second, the plane scaled by the speed in radians
+    pub spin: Bivector3,
+    /// Placement in the scene, kept up to date by `update_transforms`
+    pub world_transform: Transform3,
+    pub material: Material,
+    /// How the object combines with the objects before it, ignored for the first object
+    pub operation: CsgOperation,
+    pub blend: Blend,
+}
+
+impl Sdf<Float3> for SceneObject3
+{
+    fn distance(&self, p: Float3) -> f32
+    {
+        // Move the sample point into the object's local space rather than moving the object
+        return self.sdf.distance(self.world_transform.to_local(p)) * self.world_transform.scale;
+    }
+
+    fn distance_packet(&self, p: Float3x8) -> F32x8
+    {
+        return self.sdf.distance_packet(self.world_transform.to_local_packet(p)) * self.world_transform.scale;
+    }
+}
+
+pub struct SceneObject4
+{
+    pub sdf: SdfNode<Float4>,
+    /// Placement relative to the parent, or to the scene for objects without one
+    pub transform: Transform4,
+    /// Index of the parent object, parents must come before their children
+    pub parent: Option<usize>,
+    /// Rotation applied to the transform every second, the plane scaled by the speed in radians
+    pub spin: Bivector4,
+    /// Placement in the scene, kept up to date by `update_transforms`
+    pub world_transform: Transform4,
+    pub material: Material,
+    /// How the object combines with the objects before it, ignored for the first object
+    pub operation: CsgOperation,
+    pub blend: Blend,
+}
+
+impl Sdf<Float4> for SceneObject4
+{
+    fn distance(&self, p: Float4) -> f32
+    {
+        // Move the sample point into the object's local space rather than moving the object
+        return self.sdf.distance(self.world_transform.to_local(p)) * self.world_transform.scale;
+    }
+
+    fn distance_packet(&self, p: Float4x8) -> F32x8
+    {
+        return self.sdf.distance_packet(self.world_transform.to_local_packet(p)) * self.world_transform.scale;
+    }
+}
+
+pub struct Scene
+{
+    pub is4d: bool,
+    pub raymarch_settings: RaymarchSettings,
+    /// What the renderer shows, the shaded image or a debug view
+    pub render_mode: RenderMode,
+    pub scene_3d: Option<Box<SubScene3>>,
+    pub scene_4d: Option<Box<SubScene4>>,
+}
+
+impl Scene
+{
+    pub fn new_3d(camera: Camera3, light_source: Float3) -> Scene
+    {
+        return Scene{
+            is4d: false,
+            raymarch_settings: RaymarchSettings::DEFAULT,
+            render_mode: RenderMode::Shaded,
+            scene_3d: Some(Box::new(SubScene3{
+                camera: camera,
+                light_sources: vec![light_source],
+                objects: vec![SceneObject3{
+                    sdf: Box::new(Box3{ centre: Float3::new(0.0, 0.0, 0.0), size: Float3::new(1.0, 1.0, 1.0), edge_radius: 0.01 }),
+                    transform: Transform3::IDENTITY,
+                    parent: None,
+                    spin: Bivector3::default(),
+                    world_transform: Transform3::IDENTITY,
+                    material: Material::WHITE,
+                    operation: CsgOperation::Union,
+                    blend: Blend::Sharp,
+                }],
+            })),
+            scene_4d: None,
+        };
+    }
+
+    pub fn new_4d(camera: Camera4, light_source: Float4) -> Scene
+    {
+        return Scene{
+            is4d: true,
+            raymarch_settings: RaymarchSettings::DEFAULT,
+            render_mode: RenderMode::Shaded,
+            scene_3d: None,
+            scene_4d: Some(Box::new(SubScene4{
+                camera: camera,
+                light_sources: vec![light_source],
+                objects: vec![SceneObject4{
+                    sdf: Box::new(Box4{ centre: Float4::new(0.0, 0.0, 0.0, 0.0), size: Float4::new(1.0, 1.0, 1.0, 1.0), edge_radius: 0.01 }),
+                    transform: Transform4::IDENTITY,
+                    parent: None,
+                    spin: Bivector4::default(),
+                    world_transform: Transform4::IDENTITY,
+                    material: Material::WHITE,
+                    operation: CsgOperation::Union,
+                    blend: Blend::Sharp,
+                }],
+                slice: None,
+                views: Vec::new(),
+                w_tint: None,
+                ghosting: None,
+                volume: None,
+            })),
+        };
+    }
+}
+
+pub struct SubScene3
+{
+    pub camera: Camera3,
+    pub light_sources: Vec<Float3>,
+    pub objects: Vec<SceneObject3>,
+}
+
+impl SubScene3
+{
+    /// Recomputes the world transform of every object from its parents
+    pub fn update_transforms(&mut self)
+    {
+        for i in 0..self.objects.len()
+        {
+            let world_transform: Transform3 = match self.objects[i].parent
+            {
+                Some(parent) =>
+                {
+                    assert!(parent < i, "Object {} has parent {}, parents must come before their children", i, parent);
+                    Transform3::combine(&self.objects[parent].world_transform, &self.objects[i].transform)
+                }
+                None => self.objects[i].transform,
+            };
+            self.objects[i].world_transform = world_transform;
+        }
+    }
+
+    /// Rotates every spinning object in its own planes, children follow their parents
+    pub fn spin_objects(&mut self, delta_time: f32)
+    {
+        for object in self.objects.iter_mut()
+        {
+            let speed: f32 = object.spin.length();
+            if speed > 0.0
+            {
+                object.transform.rotation *= Rotor3::bivector_angle(&object.spin, speed * delta_time);
+            }
+        }
+        self.update_transforms();
+    }
+
+    /// Returns the object whose surface is closest to the point p
+    pub fn get_closest_object(&self, p: Float3) -> Option<&SceneObject3>
+    {
+        return self.evaluate(p).1.map(|index| &self.objects[index]);
+    }
+}
+
+/// The objects are combined in order, each with its own operation
+impl Sdf<Float3> for SubScene3
+{
+    fn distance(&self, p: Float3) -> f32
+    {
+        return self.evaluate(p).0;
+    }
+
+    fn evaluate(&self, p: Float3) -> (f32, Option<usize>)
+    {
+        return evaluate_objects(self.objects.iter().map(|object| (object.distance(p), object.operation, object.blend)));
+    }
+
+    fn distance_packet(&self, p: Float3x8) -> F32x8
+    {
+        return self.evaluate_packet(p).0;
+    }
+
+    fn evaluate_packet(&self, p: Float3x8) -> (F32x8, [Option<usize>; LANES])
+    {
+        return evaluate_objects_packet(self.objects.iter().map(|object| (object.distance_packet(p), object.operation, object.blend)));
+    }
+}
+
+pub struct SubScene4
+{
+    pub camera: Camera4,
+    pub light_sources: Vec<Float4>,
+    pub objects: Vec<SceneObject4>,
+    /// The hyperplane the view is confined to, None for a perspective projection of all four dimensions
+    pub slice: Option<Slice>,
+    /// Cameras shown side by side, each a cross section across its own w axis in place of the slice.
+    /// Empty for the single view
+    pub views: Vec<Camera4>,
+    /// Colours hits by where they are along w instead of by their material
+    pub w_tint: Option<WTint>,
+    /// Blends in faint layers of the geometry just off the view's hyperplane
+    pub ghosting: Option<Ghosting>,
+    /// Shows the density of the scene along an axis across the view in place of its surfaces
+    pub volume: Option<Volume>,
+}
+
+impl SubScene4
+{
+    /// The start and direction of the ray through uv, kept inside the slice when there is one, or the
+    /// cross section of the view uv falls in when there are several
+    pub fn ray(&self, uv: Float2) -> (Float4, Float4)
+    {
+        if !self.views.is_empty()
+        {
+            let (view, view_uv): (usize, Float2) = multi_view::locate(uv, self.views.len());
+            return (self.views[view].get_camera_position(), self.views[view].get_ray_direction(view_uv));
+        }
+        return match self.slice
+        {
+            // The camera is turned to look along the hyperplane and moved onto it, so its rays span
+            // all three directions in it whichever way the slice faces
+            Some(slice) =>
+            {
+                let (position, direction): (Float4, Float4) = self.camera.get_turned_ray(uv, slice.camera_turn(&self.camera));
+                (slice.project_point(position), direction)
+            }
+            None => (self.camera.get_camera_position(), self.camera.get_ray_direction(uv)),
+        };
+    }
+
+    /// The hyperplane the ray through uv lies in, the slice or the cross section of its view, otherwise
+    /// the one across the camera's own w axis
+    pub fn hyperplane(&self, uv: Float2) -> Slice
+    {
+        if !self.views.is_empty()
+        {
+            let (view, _): (usize, Float2) = multi_view::locate(uv, self.views.len());
+            return Slice::from_camera(&self.views[view]);
+        }
+        return self.slice.unwrap_or_else(|| Slice::from_camera(&self.camera));
+    }
+
+    /// The scene's camera followed by the camera of every view, so the controls move them all together
+    pub fn cameras_mut(&mut self) -> impl Iterator<Item = &mut Camera4>
+    {
+        return std::iter::once(&mut self.camera).chain(self.views.iter_mut());
+    }
+
+    /// The normal of a surface as seen through uv, inside a cross section that is the normal of the cross section
+    pub fn view_normal(&self, uv: Float2, n: Float4) -> Float4
+    {
+        if self.views.is_empty() && self.slice.is_none()
+        {
+            return n;
+        }
+        return self.hyperplane(uv).project_direction(n);
+    }
+
+    /// Recomputes the world transform of every object from its parents
+    pub fn update_transforms(&mut self)
+    {
+        for i in 0..self.objects.len()
+        {
+            let world_transform: Transform4 = match self.objects[i].parent
+            {
+                Some(parent) =>
+                {
+                    assert!(parent < i, "Object {} has parent {}, parents must come before their children", i, parent);
+                    Transform4::combine(&self.objects[parent].world_transform, &self.objects[i].transform)
+                }
+                None => self.objects[i].transform,
+            };
+            self.objects[i].world_transform = world_transform;
+        }
+    }
+
+    /// Rotates every spinning object in its own planes, children follow their parents
+    pub fn spin_objects(&mut self, delta_time: f32)
+    {
+        for object in self.objects.iter_mut()
+        {
+            let speed: f32 = object.spin.length();
+            if speed > 0.0
+            {
+                object.transform.rotation *= Rotor4::bivector_angle(&object.spin, speed * delta_time);
+            }
+        }
+        self.update_transforms();
+    }
+
+    /// Returns the object whose surface is closest to the point p
+    pub fn get_closest_object(&self, p: Float4) -> Option<&SceneObject4>
+    {
+        return self.evaluate(p).1.map(|index| &self.objects[index]);
+    }
+}
+
+/// The objects are combined in order, each with its own operation
+impl Sdf<Float4> for SubScene4
+{
+    fn distance(&self, p: Float4) -> f32
+    {
+        return self.evaluate(p).0;
+    }
+
+    fn evaluate(&self, p: Float4) -> (f32, Option<usize>)
+    {
+        return evaluate_objects(self.objects.iter().map(|object| (object.distance(p), object.operation, object.blend)));
+    }
+
+    fn distance_packet(&self, p: Float4x8) -> F32x8
+    {
+        return self.evaluate_packet(p).0;
+    }
+
+    fn evaluate_packet(&self, p: Float4x8) -> (F32x8, [Option<usize>; LANES])
+    {
+        return evaluate_objects_packet(self.objects.iter().map(|object| (object.distance_packet(p), object.operation, object.blend)));
+    }
+}
+
+/// Folds the object distances together, keeping track of which object each surface belongs to
+fn evaluate_objects(objects: impl Iterator<Item = (f32, CsgOperation, Blend)>) -> (f32, Option<usize>)
+{
+    let mut result: (f32, Option<usize>) = (f32::MAX, None);
+    for (index, (distance, operation, blend)) in objects.enumerate()
+    {
+        if index == 0
+        {
+            result = (distance, Some(index));
+            continue;
+        }
+
+        let (combined, operand) = csg::combine(operation, result.0, distance, blend);
+        result = (combined, if operand == Operand::First { result.1 } else { Some(index) });
+    }
+    return result;
+}
+
+/// `evaluate_objects` for every lane of a packet
+fn evaluate_objects_packet(objects: impl Iterator<Item = (F32x8, CsgOperation, Blend)>) -> (F32x8, [Option<usize>; LANES])
+{
+    let mut result: (F32x8, [Option<usize>; LANES]) = (F32x8::splat(f32::MAX), [None; LANES]);
+    for (index, (distance, operation, blend)) in objects.enumerate()
+    {
+        if index == 0
+        {
+            result = (distance, [Some(index); LANES]);
+            continue;
+        }
+
+        let (combined, second) = csg::combine_packet(operation, result.0, distance, blend);
+        result = (combined, std::array::from_fn(|i| if second.lane(i) { Some(index) } else { result.1[i] }));
+    }
+    return result;
+}
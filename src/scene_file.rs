@@ -0,0 +1,886 @@
+//! Loading of scene description files.
+//!
+//! A scene file is a list of blocks, each opened by a keyword and closed with `end`.
+//! Every line inside a block sets one field, `#` starts a comment.
+//!
+//! ```text
+//! dimensions 4
+//!
+//! camera
+//!     pivot 0 0 0 0
+//!     offset 0 0 4 0
+//!     rotation xw 30
+//!     vfov 70
+//!     focal_length 1
+//! end
+//!
+//! light
+//!     position 2 2 4 0
+//! end
+//!
+//! raymarch
+//!     preset final
+//!     max_steps 200
+//! end
+//!
+//! object box
+//!     position 0 0 0 0
+//!     rotation xw 45
+//!     size 1 1 1 1
+//!     edge_radius 0.01
+//!     colour 1 0.5 0.2
+//! end
+//! ```
+//!
+//! The optional `raymarch` block sets the quality of the render. `preset` picks `preview`, `default`
+//! or `final`, and `max_distance`, `max_steps`, `surface_distance` and `normal_epsilon` override
+//! single values of it. `method` picks how rays step: `sphere`, `relaxed` or `accelerated`,
+//! optionally followed by a relaxation factor from 1 up to 2 for `relaxed` or from 0 to 1 for
+//! `accelerated`.
+//!
+//! Object primitives and the fields they use:
+//!
+//! | primitive        | fields                                   |
+//! |------------------|------------------------------------------|
+//! | `sphere`         | `radius`                                 |
+//! | `box`            | `size`, `edge_radius`                    |
+//! | `half_space`     | `normal`, `offset`                       |
+//! | `hyperplane`     | `normal`, `offset`                       |
+//! | `capsule`        | `from`, `to`, `radius`                   |
+//! | `duocylinder`    | `radii` (XY and ZW)                      |
+//! | `spherinder`     | `radius`, `height`                       |
+//! | `cubinder`       | `radius`, `length`                       |
+//! | `cylinder_prism` | `radius`, `height`, `length`             |
+//! | `cone`           | `radius`, `height`, `length`             |
+//! | `spherone`       | `radius`, `height`                       |
+//! | `tiger`          | `radii` (XY and ZW), `radius`            |
+//! | `ditorus`        | `radii` (major and middle), `radius`     |
+//! | `torisphere`     | `radii` (major and minor)                |
+//! | `spheritorus`    | `radii` (major and minor)                |
+//! | `five_cell`, `tesseract`, `sixteen_cell`, `twenty_four_cell`, `hundred_twenty_cell`, `six_hundred_cell` | `radius` (circumradius), `edge_radius` |
+//!
+//! Only `sphere`, `box`, `half_space`, `hyperplane` and `capsule` are available in 3D scenes.
+//! See `distance_functions` for the exact shape of each primitive.
+//!
+//! Objects are combined in the order they are listed. `operation` sets how an object combines with
+//! the objects before it: `union` (the default), `intersection`, `subtraction` or `xor`.
+//! `blend` sets how the surfaces meet: `sharp` (the default), `polynomial <k>`, `exponential <k>`,
+//! `chamfer <size>` or `stairs <size> <steps>`. Each surface keeps the colour of its object.
+//!
+//! Every object can also set `scale` for a uniform scale, and `spin <plane> <degrees>` to rotate in
+//! its own planes by that many degrees every second in the interactive window. An object given a
+//! `name` can be used as the `parent` of objects listed after it, whose position, rotation and
+//! scale are then relative to the parent and follow it as it spins.
+//!
+//! Domain operators warp the primitive and are applied in the order they are listed, each one
+//! wrapping the result so far:
+//!
+//! | field            | values                                                        |
+//! |------------------|---------------------------------------------------------------|
+//! | `repeat`         | period on each axis, 0 leaves the axis alone                  |
+//! | `repeat_limited` | period on each axis followed by the copies either side        |
+//! | `mirror`         | normal of a plane through the object's origin                 |
+//! | `symmetry`       | axes to mirror, such as `xw`                                  |
+//! | `elongate`       | half length added on each axis                                |
+//! | `round`          | radius                                                        |
+//! | `onion`          | shell thickness                                               |
+//! | `twist`, `bend`  | plane, axis and degrees per unit along the axis               |
+//!
+//! A twist uses an axis outside the plane, such as `twist xy z 45`, and a bend an axis inside it,
+//! such as `bend xw x 20`.
+//!
+//! Vectors take 3 or 4 numbers depending on `dimensions`, which must come before any block.
+//! `rotation` takes a plane (xy, xz, yz, xw, yw or zw) and an angle in degrees, several
+//! rotations in one block are applied in order.
+
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+use crate::camera::{Camera, Camera3, Camera4};
+use crate::csg::{Blend, CsgOperation};
+use crate::mathematics;
+use crate::mathematics::bivector3::Bivector3;
+use crate::mathematics::bivector4::Bivector4;
+use crate::mathematics::float2::Float2;
+use crate::mathematics::float3::Float3;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::multivectors::{Magnitude, Rotor, Vector};
+use crate::mathematics::rotor3::Rotor3;
+use crate::mathematics::rotor4::Rotor4;
+use crate::polychora::Polychoron;
+use crate::raymarching::{MarchMethod, RaymarchSettings};
+use crate::render_mode::RenderMode;
+use crate::scene::*;
+use crate::sdf::*;
+use crate::transform::{Transform3, Transform4};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SceneFileError
+{
+    pub line: usize,
+    pub field: String,
+    pub message: String,
+}
+
+impl SceneFileError
+{
+    fn new(line: usize, field: &str, message: String) -> SceneFileError
+    {
+        return SceneFileError{ line: line, field: field.to_string(), message: message };
+    }
+}
+
+impl Display for SceneFileError
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result
+    {
+        write!(f, "line {}, field \"{}\": {}", self.line, self.field, self.message)
+    }
+}
+
+/// Load a scene file from disk, errors are reported as "path: line n, field "name": message"
+pub fn load_scene(path: &Path, aspect_ratio: f32) -> Result<Scene, String>
+{
+    let source: String = fs::read_to_string(path).map_err(|e| format!("Unable to read {}: {}", path.display(), e))?;
+    return parse_scene(&source, aspect_ratio).map_err(|e| format!("{}: {}", path.display(), e));
+}
+
+/// A rotation in a single plane, kept until the dimension of the scene is known
+#[derive(Copy, Clone)]
+struct PlaneRotation
+{
+    plane: Bivector4,
+    angle: f32,
+}
+
+struct CameraDescription
+{
+    pivot: Float4,
+    offset: Float4,
+    rotations: Vec<PlaneRotation>,
+    vfov: f32,
+    focal_length: f32,
+}
+
+struct ObjectDescription
+{
+    line: usize,
+    primitive: String,
+    position: Float4,
+    rotations: Vec<PlaneRotation>,
+    size: Option<Float4>,
+    radius: Option<f32>,
+    radii: Option<Float2>,
+    height: Option<f32>,
+    length: Option<f32>,
+    normal: Option<Float4>,
+    offset: Option<f32>,
+    from: Option<Float4>,
+    to: Option<Float4>,
+    edge_radius: f32,
+    colour: Float3,
+    operation: CsgOperation,
+    blend: Blend,
+    operators: Vec<DomainOperator>,
+    name: Option<String>,
+    parent: Option<usize>,
+    scale: f32,
+    /// Planes scaled by their speed in radians per second
+    spin: Bivector4,
+}
+
+/// Domain operators in the order they wrap the primitive
+enum DomainOperator
+{
+    Repeat(Float4),
+    RepeatLimited{ period: Float4, count: Float4 },
+    Mirror(Float4),
+    Symmetry(Float4),
+    Elongate(Float4),
+    Round(f32),
+    Onion(f32),
+    /// Twist and bend only differ in whether the axis lies in the plane
+    Twist{ plane: Bivector4, axis: usize, rate: f32 },
+}
+
+enum Block
+{
+    None,
+    Camera,
+    Light,
+    Raymarch,
+    Object(Box<ObjectDescription>),
+}
+
+struct SceneDescription
+{
+    dimensions: usize,
+    camera: CameraDescription,
+    lights: Vec<Float4>,
+    objects: Vec<ObjectDescription>,
+    raymarch_settings: RaymarchSettings,
+}
+
+pub fn parse_scene(source: &str, aspect_ratio: f32) -> Result<Scene, SceneFileError>
+{
+    let mut description: SceneDescription = SceneDescription{
+        dimensions: 4,
+        camera: CameraDescription{
+            pivot: Float4::new(0.0, 0.0, 0.0, 0.0),
+            offset: Float4::new(0.0, 0.0, 4.0, 0.0),
+            rotations: Vec::new(),
+            vfov: 70.0,
+            focal_length: 1.0,
+        },
+        lights: Vec::new(),
+        objects: Vec::new(),
+        raymarch_settings: RaymarchSettings::DEFAULT,
+    };
+
+    let mut block: Block = Block::None;
+    let mut block_line: usize = 0;
+    let mut seen_block: bool = false;
+
+    for (index, line) in source.lines().enumerate()
+    {
+        let line_number: usize = index + 1;
+        let line: &str = line.split('#').next().unwrap_or("");
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if tokens.is_empty()
+        {
+            continue;
+        }
+
+        let field: &str = tokens[0];
+        let values: &[&str] = &tokens[1..];
+
+        if field == "end"
+        {
+            match std::mem::replace(&mut block, Block::None)
+            {
+                Block::None => return Err(SceneFileError::new(line_number, field, String::from("\"end\" without an open block"))),
+                Block::Object(object) => description.objects.push(*object),
+                Block::Camera | Block::Light | Block::Raymarch => {}
+            }
+            continue;
+        }
+
+        match &mut block
+        {
+            Block::None =>
+            {
+                match field
+                {
+                    "dimensions" =>
+                    {
+                        if seen_block
+                        {
+                            return Err(SceneFileError::new(line_number, field, String::from("must be set before any block")));
+                        }
+                        let dimensions: f32 = parse_number(values, line_number, field)?;
+                        if dimensions != 3.0 && dimensions != 4.0
+                        {
+                            return Err(SceneFileError::new(line_number, field, format!("expected 3 or 4, found {}", dimensions)));
+                        }
+                        description.dimensions = dimensions as usize;
+                    }
+                    "camera" => { expect_values(values, 0, line_number, field)?; block = Block::Camera; }
+                    "light" => { expect_values(values, 0, line_number, field)?; block = Block::Light; }
+                    "raymarch" => { expect_values(values, 0, line_number, field)?; block = Block::Raymarch; }
+                    "object" =>
+                    {
+                        expect_values(values, 1, line_number, field)?;
+                        block = Block::Object(Box::new(ObjectDescription{
+                            line: line_number,
+                            primitive: values[0].to_string(),
+                            position: Float4::new(0.0, 0.0, 0.0, 0.0),
+                            rotations: Vec::new(),
+                            size: None,
+                            radius: None,
+                            radii: None,
+                            height: None,
+                            length: None,
+                            normal: None,
+                            offset: None,
+                            from: None,
+                            to: None,
+                            edge_radius: 0.0,
+                            colour: Float3::new(1.0, 1.0, 1.0),
+                            operation: CsgOperation::Union,
+                            blend: Blend::Sharp,
+                            operators: Vec::new(),
+                            name: None,
+                            parent: None,
+                            scale: 1.0,
+                            spin: Bivector4::default(),
+                        }));
+                    }
+                    _ => return Err(SceneFileError::new(line_number, field, String::from("unknown block, expected dimensions, camera, light, raymarch or object")))
+                }
+
+                seen_block |= field != "dimensions";
+                block_line = line_number;
+            }
+            Block::Camera =>
+            {
+                let camera: &mut CameraDescription = &mut description.camera;
+                match field
+                {
+                    "pivot" => camera.pivot = parse_vector(values, description.dimensions, line_number, field)?,
+                    "offset" => camera.offset = parse_vector(values, description.dimensions, line_number, field)?,
+                    "rotation" => camera.rotations.push(parse_rotation(values, description.dimensions, line_number, field)?),
+                    "vfov" => camera.vfov = parse_number(values, line_number, field)?,
+                    "focal_length" => camera.focal_length = parse_number(values, line_number, field)?,
+                    _ => return Err(SceneFileError::new(line_number, field, String::from("unknown camera field")))
+                }
+            }
+            Block::Light =>
+            {
+                match field
+                {
+                    "position" => description.lights.push(parse_vector(values, description.dimensions, line_number, field)?),
+                    _ => return Err(SceneFileError::new(line_number, field, String::from("unknown light field")))
+                }
+            }
+            Block::Raymarch =>
+            {
+                let settings: &mut RaymarchSettings = &mut description.raymarch_settings;
+                match field
+                {
+                    "preset" =>
+                    {
+                        expect_values(values, 1, line_number, field)?;
+                        *settings = RaymarchSettings::preset(values[0]).ok_or_else(|| {
+                            SceneFileError::new(line_number, field, format!("unknown preset \"{}\", expected {}", values[0], RaymarchSettings::PRESET_NAMES))
+                        })?;
+                    }
+                    "max_distance" => { expect_values(values, 1, line_number, field)?; settings.max_distance = parse_positive(values[0], line_number, field)?; }
+                    "max_steps" =>
+                    {
+                        settings.max_steps = parse_count(values, line_number, field)?;
+                        if settings.max_steps == 0
+                        {
+                            return Err(SceneFileError::new(line_number, field, String::from("must be at least 1")));
+                        }
+                    }
+                    "surface_distance" => { expect_values(values, 1, line_number, field)?; settings.surface_distance = parse_positive(values[0], line_number, field)?; }
+                    "normal_epsilon" => { expect_values(values, 1, line_number, field)?; settings.normal_epsilon = parse_positive(values[0], line_number, field)?; }
+                    "method" =>
+                    {
+                        if values.is_empty() || values.len() > 2
+                        {
+                            return Err(SceneFileError::new(line_number, field, format!("expected a method and an optional factor, found {} value(s)", values.len())));
+                        }
+                        settings.method = MarchMethod::from_name(values[0]).ok_or_else(|| {
+                            SceneFileError::new(line_number, field, format!("unknown method \"{}\", expected {}", values[0], MarchMethod::NAMES))
+                        })?;
+                        if let Some(value) = values.get(1)
+                        {
+                            let factor: f32 = parse_float(value, line_number, field)?;
+                            settings.method = settings.method.with_factor(factor).map_err(|error| SceneFileError::new(line_number, field, error))?;
+                        }
+                    }
+                    _ => return Err(SceneFileError::new(line_number, field, String::from("unknown raymarch field")))
+                }
+            }
+            Block::Object(object) =>
+            {
+                match field
+                {
+                    "position" => object.position = parse_vector(values, description.dimensions, line_number, field)?,
+                    "rotation" => object.rotations.push(parse_rotation(values, description.dimensions, line_number, field)?),
+                    "size" => object.size = Some(parse_vector(values, description.dimensions, line_number, field)?),
+                    "radius" => object.radius = Some(parse_number(values, line_number, field)?),
+                    "radii" => object.radii = Some(Float2::from(parse_vector(values, 2, line_number, field)?)),
+                    "height" => object.height = Some(parse_number(values, line_number, field)?),
+                    "length" => object.length = Some(parse_number(values, line_number, field)?),
+                    "normal" => object.normal = Some(parse_normal(values, description.dimensions, line_number, field)?),
+                    "offset" => object.offset = Some(parse_number(values, line_number, field)?),
+                    "from" => object.from = Some(parse_vector(values, description.dimensions, line_number, field)?),
+                    "to" => object.to = Some(parse_vector(values, description.dimensions, line_number, field)?),
+                    "edge_radius" => object.edge_radius = parse_number(values, line_number, field)?,
+                    "colour" => object.colour = Float3::from(parse_vector(values, 3, line_number, field)?),
+                    "operation" => object.operation = parse_operation(values, line_number, field)?,
+                    "blend" => object.blend = parse_blend(values, line_number, field)?,
+                    "scale" =>
+                    {
+                        expect_values(values, 1, line_number, field)?;
+                        object.scale = parse_positive(values[0], line_number, field)?;
+                    }
+                    "spin" =>
+                    {
+                        let rotation: PlaneRotation = parse_rotation(values, description.dimensions, line_number, field)?;
+                        object.spin = add_bivectors(object.spin, rotation.plane, rotation.angle);
+                    }
+                    "name" =>
+                    {
+                        expect_values(values, 1, line_number, field)?;
+                        if description.objects.iter().any(|other| other.name.as_deref() == Some(values[0]))
+                        {
+                            return Err(SceneFileError::new(line_number, field, format!("an object named \"{}\" already exists", values[0])));
+                        }
+                        object.name = Some(values[0].to_string());
+                    }
+                    "parent" =>
+                    {
+                        expect_values(values, 1, line_number, field)?;
+                        let parent: usize = description.objects.iter().position(|other| other.name.as_deref() == Some(values[0])).ok_or_else(|| {
+                            SceneFileError::new(line_number, field, format!("no object named \"{}\" before this one", values[0]))
+                        })?;
+                        object.parent = Some(parent);
+                    }
+                    "repeat" => object.operators.push(DomainOperator::Repeat(parse_vector(values, description.dimensions, line_number, field)?)),
+                    "repeat_limited" =>
+                    {
+                        expect_values(values, 2 * description.dimensions, line_number, field)?;
+                        let (period, count) = values.split_at(description.dimensions);
+                        object.operators.push(DomainOperator::RepeatLimited{
+                            period: parse_vector(period, description.dimensions, line_number, field)?,
+                            count: parse_vector(count, description.dimensions, line_number, field)?,
+                        });
+                    }
+                    "mirror" => object.operators.push(DomainOperator::Mirror(parse_normal(values, description.dimensions, line_number, field)?)),
+                    "symmetry" => object.operators.push(DomainOperator::Symmetry(parse_axes(values, description.dimensions, line_number, field)?)),
+                    "elongate" => object.operators.push(DomainOperator::Elongate(parse_vector(values, description.dimensions, line_number, field)?)),
+                    "round" => object.operators.push(DomainOperator::Round(parse_number(values, line_number, field)?)),
+                    "onion" => object.operators.push(DomainOperator::Onion(parse_number(values, line_number, field)?)),
+                    "twist" | "bend" =>
+                    {
+                        expect_values(values, 3, line_number, field)?;
+                        object.operators.push(DomainOperator::Twist{
+                            plane: parse_plane(values[0], description.dimensions, line_number, field)?,
+                            axis: parse_axis(values[1], description.dimensions, line_number, field)?,
+                            rate: parse_float(values[2], line_number, field)? * mathematics::DEGREES_TO_RADIANS,
+                        });
+                    }
+                    _ => return Err(SceneFileError::new(line_number, field, String::from("unknown object field")))
+                }
+            }
+        }
+    }
+
+    match block
+    {
+        Block::None => {}
+        Block::Camera => return Err(SceneFileError::new(block_line, "camera", String::from("block is missing \"end\""))),
+        Block::Light => return Err(SceneFileError::new(block_line, "light", String::from("block is missing \"end\""))),
+        Block::Raymarch => return Err(SceneFileError::new(block_line, "raymarch", String::from("block is missing \"end\""))),
+        Block::Object(_) => return Err(SceneFileError::new(block_line, "object", String::from("block is missing \"end\""))),
+    }
+
+    if description.lights.is_empty()
+    {
+        description.lights.push(Float4::new(2.0, 2.0, 4.0, 0.0));
+    }
+
+    if description.dimensions == 4
+    {
+        return build_scene_4d(&description, aspect_ratio);
+    }
+    return build_scene_3d(&description, aspect_ratio);
+}
+
+fn build_scene_3d(description: &SceneDescription, aspect_ratio: f32) -> Result<Scene, SceneFileError>
+{
+    let camera: &CameraDescription = &description.camera;
+    let mut scene: SubScene3 = SubScene3{
+        camera: Camera3::new(
+            Float3::from(camera.pivot),
+            Float3::from(camera.offset),
+            rotor3(&camera.rotations),
+            aspect_ratio,
+            camera.vfov,
+            camera.focal_length
+        ),
+        light_sources: description.lights.iter().map(|light| Float3::from(*light)).collect(),
+        objects: Vec::with_capacity(description.objects.len()),
+    };
+
+    for object in &description.objects
+    {
+        let centre: Float3 = Float3::new(0.0, 0.0, 0.0);
+        let sdf: SdfNode<Float3> = match object.primitive.as_str()
+        {
+            "sphere" => Box::new(Sphere{ centre: centre, radius: require(object.radius, object.line, "radius")? }),
+            "box" => Box::new(Box3{ centre: centre, size: Float3::from(require(object.size, object.line, "size")?), edge_radius: object.edge_radius }),
+            "half_space" => Box::new(HalfSpace{ normal: Float3::from(require(object.normal, object.line, "normal")?), offset: object.offset.unwrap_or(0.0) }),
+            "hyperplane" => Box::new(Hyperplane{ normal: Float3::from(require(object.normal, object.line, "normal")?), offset: object.offset.unwrap_or(0.0) }),
+            "capsule" => Box::new(Capsule{
+                a: Float3::from(require(object.from, object.line, "from")?),
+                b: Float3::from(require(object.to, object.line, "to")?),
+                radius: require(object.radius, object.line, "radius")?
+            }),
+            _ => return Err(unknown_primitive(object, PRIMITIVES_3D))
+        };
+        let sdf: SdfNode<Float3> = apply_operators(sdf, &object.operators, |child, plane, axis, rate| {
+            return Box::new(Twist3{ plane: bivector3(plane), axis: axis, rate: rate, child: child });
+        });
+
+        scene.objects.push(SceneObject3{
+            sdf: sdf,
+            transform: Transform3::new(Float3::from(object.position), rotor3(&object.rotations), object.scale),
+            parent: object.parent,
+            spin: bivector3(object.spin),
+            world_transform: Transform3::IDENTITY,
+            material: Material{ colour: object.colour },
+            operation: object.operation,
+            blend: object.blend,
+        });
+    }
+
+    scene.update_transforms();
+
+    return Ok(Scene{ is4d: false, raymarch_settings: description.raymarch_settings, render_mode: RenderMode::Shaded, scene_3d: Some(Box::new(scene)), scene_4d: None });
+}
+
+fn build_scene_4d(description: &SceneDescription, aspect_ratio: f32) -> Result<Scene, SceneFileError>
+{
+    let camera: &CameraDescription = &description.camera;
+    let mut scene: SubScene4 = SubScene4{
+        camera: Camera4::new(
+            camera.pivot,
+            camera.offset,
+            rotor4(&camera.rotations),
+            aspect_ratio,
+            camera.vfov,
+            camera.focal_length
+        ),
+        light_sources: description.lights.clone(),
+        objects: Vec::with_capacity(description.objects.len()),
+        slice: None,
+        views: Vec::new(),
+        w_tint: None,
+        ghosting: None,
+        volume: None,
+    };
+
+    for object in &description.objects
+    {
+        let centre: Float4 = Float4::new(0.0, 0.0, 0.0, 0.0);
+        let sdf: SdfNode<Float4> = match object.primitive.as_str()
+        {
+            "sphere" => Box::new(Sphere{ centre: centre, radius: require(object.radius, object.line, "radius")? }),
+            "box" => Box::new(Box4{ centre: centre, size: require(object.size, object.line, "size")?, edge_radius: object.edge_radius }),
+            "half_space" => Box::new(HalfSpace{ normal: require(object.normal, object.line, "normal")?, offset: object.offset.unwrap_or(0.0) }),
+            "hyperplane" => Box::new(Hyperplane{ normal: require(object.normal, object.line, "normal")?, offset: object.offset.unwrap_or(0.0) }),
+            "capsule" => Box::new(Capsule{
+                a: require(object.from, object.line, "from")?,
+                b: require(object.to, object.line, "to")?,
+                radius: require(object.radius, object.line, "radius")?
+            }),
+            "duocylinder" => Box::new(Duocylinder{ centre: centre, radii: require(object.radii, object.line, "radii")? }),
+            "spherinder" => Box::new(Spherinder{
+                centre: centre,
+                radius: require(object.radius, object.line, "radius")?,
+                height: require(object.height, object.line, "height")?
+            }),
+            "cubinder" => Box::new(Cubinder{
+                centre: centre,
+                radius: require(object.radius, object.line, "radius")?,
+                size: require(object.length, object.line, "length")?
+            }),
+            "cylinder_prism" => Box::new(CylinderPrism{
+                centre: centre,
+                radius: require(object.radius, object.line, "radius")?,
+                height: require(object.height, object.line, "height")?,
+                length: require(object.length, object.line, "length")?
+            }),
+            "cone" => Box::new(Cone4{
+                centre: centre,
+                radius: require(object.radius, object.line, "radius")?,
+                height: require(object.height, object.line, "height")?,
+                length: require(object.length, object.line, "length")?
+            }),
+            "spherone" => Box::new(Spherone{
+                centre: centre,
+                radius: require(object.radius, object.line, "radius")?,
+                height: require(object.height, object.line, "height")?
+            }),
+            "tiger" => Box::new(Tiger{
+                centre: centre,
+                radii: require(object.radii, object.line, "radii")?,
+                radius: require(object.radius, object.line, "radius")?
+            }),
+            "ditorus" => Box::new(Ditorus{
+                centre: centre,
+                radii: require(object.radii, object.line, "radii")?,
+                radius: require(object.radius, object.line, "radius")?
+            }),
+            "torisphere" =>
+            {
+                let radii: Float2 = require(object.radii, object.line, "radii")?;
+                Box::new(Torisphere{ centre: centre, major_radius: radii.x, minor_radius: radii.y })
+            }
+            "spheritorus" =>
+            {
+                let radii: Float2 = require(object.radii, object.line, "radii")?;
+                Box::new(Spheritorus{ centre: centre, major_radius: radii.x, minor_radius: radii.y })
+            }
+            "five_cell" | "tesseract" | "sixteen_cell" | "twenty_four_cell" | "hundred_twenty_cell" | "six_hundred_cell" =>
+            {
+                let circumradius: f32 = require(object.radius, object.line, "radius")?;
+                let polychoron: Polychoron = match object.primitive.as_str()
+                {
+                    "five_cell" => Polychoron::five_cell(circumradius),
+                    "tesseract" => Polychoron::tesseract(circumradius),
+                    "sixteen_cell" => Polychoron::sixteen_cell(circumradius),
+                    "twenty_four_cell" => Polychoron::twenty_four_cell(circumradius),
+                    "hundred_twenty_cell" => Polychoron::hundred_twenty_cell(circumradius),
+                    _ => Polychoron::six_hundred_cell(circumradius),
+                };
+                Box::new(RegularPolychoron{ centre: centre, polychoron: polychoron, edge_radius: object.edge_radius })
+            }
+            _ => return Err(unknown_primitive(object, PRIMITIVES_4D))
+        };
+        let sdf: SdfNode<Float4> = apply_operators(sdf, &object.operators, |child, plane, axis, rate| {
+            return Box::new(Twist4{ plane: plane, axis: axis, rate: rate, child: child });
+        });
+
+        scene.objects.push(SceneObject4{
+            sdf: sdf,
+            transform: Transform4::new(object.position, rotor4(&object.rotations), object.scale),
+            parent: object.parent,
+            spin: object.spin,
+            world_transform: Transform4::IDENTITY,
+            material: Material{ colour: object.colour },
+            operation: object.operation,
+            blend: object.blend,
+        });
+    }
+
+    scene.update_transforms();
+
+    return Ok(Scene{ is4d: true, raymarch_settings: description.raymarch_settings, render_mode: RenderMode::Shaded, scene_3d: None, scene_4d: Some(Box::new(scene)) });
+}
+
+/// Wraps the primitive in its domain operators, twist needs the scene's own rotor types so it is passed in
+fn apply_operators<V: Vector + From<Float4> + Send + Sync + 'static>(
+    sdf: SdfNode<V>,
+    operators: &[DomainOperator],
+    twist: impl Fn(SdfNode<V>, Bivector4, usize, f32) -> SdfNode<V>
+) -> SdfNode<V>
+{
+    let mut sdf: SdfNode<V> = sdf;
+    for operator in operators
+    {
+        sdf = match *operator
+        {
+            DomainOperator::Repeat(period) => Box::new(Repeat{ period: V::from(period), child: sdf }),
+            DomainOperator::RepeatLimited{ period, count } => Box::new(RepeatLimited{ period: V::from(period), count: V::from(count), child: sdf }),
+            DomainOperator::Mirror(normal) => Box::new(Mirror{ normal: V::from(normal), offset: 0.0, child: sdf }),
+            DomainOperator::Symmetry(axes) => Box::new(Symmetry{ axes: V::from(axes), child: sdf }),
+            DomainOperator::Elongate(size) => Box::new(Elongate{ size: V::from(size), child: sdf }),
+            DomainOperator::Round(radius) => Box::new(Round{ radius: radius, child: sdf }),
+            DomainOperator::Onion(thickness) => Box::new(Onion{ thickness: thickness, child: sdf }),
+            DomainOperator::Twist{ plane, axis, rate } => twist(sdf, plane, axis, rate),
+        };
+    }
+    return sdf;
+}
+
+/// a + b * scale
+fn add_bivectors(a: Bivector4, b: Bivector4, scale: f32) -> Bivector4
+{
+    return Bivector4::new(
+        a.yz + b.yz * scale,
+        a.xz + b.xz * scale,
+        a.xy + b.xy * scale,
+        a.xw + b.xw * scale,
+        a.yw + b.yw * scale,
+        a.zw + b.zw * scale
+    );
+}
+
+fn bivector3(plane: Bivector4) -> Bivector3
+{
+    return Bivector3::new(plane.yz, plane.xz, plane.xy);
+}
+
+fn rotor3(rotations: &[PlaneRotation]) -> Rotor3
+{
+    let mut rotor: Rotor3 = Rotor3::IDENTITY;
+    for rotation in rotations
+    {
+        rotor *= Rotor3::bivector_angle(&bivector3(rotation.plane), rotation.angle);
+    }
+    return rotor;
+}
+
+fn rotor4(rotations: &[PlaneRotation]) -> Rotor4
+{
+    let mut rotor: Rotor4 = Rotor4::IDENTITY;
+    for rotation in rotations
+    {
+        rotor *= Rotor4::bivector_angle(&rotation.plane, rotation.angle);
+    }
+    return rotor;
+}
+
+fn require<T>(value: Option<T>, line: usize, field: &str) -> Result<T, SceneFileError>
+{
+    return value.ok_or_else(|| SceneFileError::new(line, field, String::from("required by this primitive but not set")));
+}
+
+const PRIMITIVES_3D: &str = "sphere, box, half_space, hyperplane or capsule";
+const PRIMITIVES_4D: &str = "sphere, box, half_space, hyperplane, capsule, duocylinder, spherinder, cubinder, cylinder_prism, cone, spherone, tiger, ditorus, torisphere, spheritorus, five_cell, tesseract, sixteen_cell, twenty_four_cell, hundred_twenty_cell or six_hundred_cell";
+
+fn unknown_primitive(object: &ObjectDescription, expected: &str) -> SceneFileError
+{
+    return SceneFileError::new(object.line, "object", format!("unknown primitive \"{}\", expected {}", object.primitive, expected));
+}
+
+fn expect_values(values: &[&str], count: usize, line: usize, field: &str) -> Result<(), SceneFileError>
+{
+    if values.len() != count
+    {
+        return Err(SceneFileError::new(line, field, format!("expected {} value(s), found {}", count, values.len())));
+    }
+    Ok(())
+}
+
+fn parse_float(value: &str, line: usize, field: &str) -> Result<f32, SceneFileError>
+{
+    return value.parse::<f32>().map_err(|_| SceneFileError::new(line, field, format!("\"{}\" is not a number", value)));
+}
+
+fn parse_number(values: &[&str], line: usize, field: &str) -> Result<f32, SceneFileError>
+{
+    expect_values(values, 1, line, field)?;
+    return parse_float(values[0], line, field);
+}
+
+/// Parses a finite number that has to be more than 0
+fn parse_positive(value: &str, line: usize, field: &str) -> Result<f32, SceneFileError>
+{
+    let number: f32 = parse_float(value, line, field)?;
+    if number > 0.0 && number.is_finite()
+    {
+        return Ok(number);
+    }
+    return Err(SceneFileError::new(line, field, format!("{} must be a finite number more than 0", value)));
+}
+
+fn parse_count(values: &[&str], line: usize, field: &str) -> Result<u32, SceneFileError>
+{
+    expect_values(values, 1, line, field)?;
+    return values[0].parse::<u32>().map_err(|_| SceneFileError::new(line, field, format!("\"{}\" is not a whole number", values[0])));
+}
+
+/// Parses 2, 3 or 4 numbers, missing components are set to 0
+fn parse_vector(values: &[&str], dimensions: usize, line: usize, field: &str) -> Result<Float4, SceneFileError>
+{
+    expect_values(values, dimensions, line, field)?;
+
+    let mut components: [f32; 4] = [0.0; 4];
+    for (component, value) in components.iter_mut().zip(values)
+    {
+        *component = parse_float(value, line, field)?;
+    }
+
+    return Ok(Float4::new(components[0], components[1], components[2], components[3]));
+}
+
+/// Parses a direction and scales it to unit length, a zero vector has no direction
+fn parse_normal(values: &[&str], dimensions: usize, line: usize, field: &str) -> Result<Float4, SceneFileError>
+{
+    let normal: Float4 = parse_vector(values, dimensions, line, field)?;
+    if normal.length() == 0.0
+    {
+        return Err(SceneFileError::new(line, field, String::from("normal can not be zero")));
+    }
+    return Ok(normal.normalized());
+}
+
+fn parse_rotation(values: &[&str], dimensions: usize, line: usize, field: &str) -> Result<PlaneRotation, SceneFileError>
+{
+    expect_values(values, 2, line, field)?;
+
+    let plane: Bivector4 = parse_plane(values[0], dimensions, line, field)?;
+    let angle: f32 = parse_float(values[1], line, field)? * mathematics::DEGREES_TO_RADIANS;
+
+    return Ok(PlaneRotation{ plane: plane, angle: angle });
+}
+
+fn parse_plane(value: &str, dimensions: usize, line: usize, field: &str) -> Result<Bivector4, SceneFileError>
+{
+    return match value
+    {
+        "yz" => Ok(Bivector4::new(1.0, 0.0, 0.0, 0.0, 0.0, 0.0)),
+        "xz" => Ok(Bivector4::new(0.0, 1.0, 0.0, 0.0, 0.0, 0.0)),
+        "xy" => Ok(Bivector4::new(0.0, 0.0, 1.0, 0.0, 0.0, 0.0)),
+        "xw" if dimensions == 4 => Ok(Bivector4::new(0.0, 0.0, 0.0, 1.0, 0.0, 0.0)),
+        "yw" if dimensions == 4 => Ok(Bivector4::new(0.0, 0.0, 0.0, 0.0, 1.0, 0.0)),
+        "zw" if dimensions == 4 => Ok(Bivector4::new(0.0, 0.0, 0.0, 0.0, 0.0, 1.0)),
+        _ => Err(SceneFileError::new(line, field, format!("\"{}\" is not a plane of a {}D scene", value, dimensions)))
+    };
+}
+
+fn parse_axis(value: &str, dimensions: usize, line: usize, field: &str) -> Result<usize, SceneFileError>
+{
+    return match value
+    {
+        "x" => Ok(0),
+        "y" => Ok(1),
+        "z" => Ok(2),
+        "w" if dimensions == 4 => Ok(3),
+        _ => Err(SceneFileError::new(line, field, format!("\"{}\" is not an axis of a {}D scene", value, dimensions)))
+    };
+}
+
+/// Parses a list of axes such as "x w" or "xyz" into a mask with 1 on each axis
+fn parse_axes(values: &[&str], dimensions: usize, line: usize, field: &str) -> Result<Float4, SceneFileError>
+{
+    if values.is_empty()
+    {
+        return Err(SceneFileError::new(line, field, String::from("expected at least one axis")));
+    }
+
+    let mut mask: [f32; 4] = [0.0; 4];
+    for value in values
+    {
+        for axis in value.chars()
+        {
+            mask[parse_axis(&axis.to_string(), dimensions, line, field)?] = 1.0;
+        }
+    }
+    return Ok(Float4::new(mask[0], mask[1], mask[2], mask[3]));
+}
+
+fn parse_operation(values: &[&str], line: usize, field: &str) -> Result<CsgOperation, SceneFileError>
+{
+    expect_values(values, 1, line, field)?;
+
+    return match values[0]
+    {
+        "union" => Ok(CsgOperation::Union),
+        "intersection" => Ok(CsgOperation::Intersection),
+        "subtraction" => Ok(CsgOperation::Subtraction),
+        "xor" => Ok(CsgOperation::Xor),
+        _ => Err(SceneFileError::new(line, field, format!("unknown operation \"{}\", expected union, intersection, subtraction or xor", values[0])))
+    };
+}
+
+fn parse_blend(values: &[&str], line: usize, field: &str) -> Result<Blend, SceneFileError>
+{
+    let kind: &str = values.first().copied().unwrap_or("");
+    let parameters: &[&str] = values.get(1..).unwrap_or(&[]);
+
+    return match kind
+    {
+        "sharp" => { expect_values(parameters, 0, line, field)?; Ok(Blend::Sharp) }
+        "polynomial" => { expect_values(parameters, 1, line, field)?; Ok(Blend::Polynomial(parse_positive(parameters[0], line, field)?)) }
+        "exponential" => { expect_values(parameters, 1, line, field)?; Ok(Blend::Exponential(parse_positive(parameters[0], line, field)?)) }
+        "chamfer" => { expect_values(parameters, 1, line, field)?; Ok(Blend::Chamfer(parse_positive(parameters[0], line, field)?)) }
+        "stairs" =>
+        {
+            expect_values(parameters, 2, line, field)?;
+            Ok(Blend::Stairs{ radius: parse_positive(parameters[0], line, field)?, steps: parse_positive(parameters[1], line, field)? })
+        }
+        _ => Err(SceneFileError::new(line, field, format!("unknown blend \"{}\", expected sharp, polynomial, exponential, chamfer or stairs", kind)))
+    };
+}
+
+#[cfg(test)]
+#[path = "scene_file_tests.rs"]
+mod tests;
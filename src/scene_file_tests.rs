@@ -0,0 +1,323 @@
+use crate::mathematics::f32x8::LANES;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::float4x8::Float4x8;
+use crate::mathematics::multivectors::VectorPacket;
+use crate::raymarching::{MarchMethod, RaymarchSettings};
+use crate::scene::{Scene, SubScene4};
+use crate::sdf::Sdf;
+use crate::scene_file::parse_scene;
+
+const SCENE_4D: &str = "
+# A tesseract and a hypersphere
+dimensions 4
+
+camera
+    offset 0 0 6 0
+    rotation xw 30
+end
+
+light
+    position 2 2 4 0
+end
+
+object box
+    position 0 0 0 0
+    rotation xw 45
+    size 1 1 1 1
+    edge_radius 0.01
+    colour 1 0.5 0.2
+end
+
+object sphere
+    position 3 0 0 0
+    radius 1
+end
+";
+
+#[test]
+fn parse_4d_scene()
+{
+    let scene: Scene = parse_scene(SCENE_4D, 16.0 / 9.0).unwrap();
+    assert!(scene.is4d);
+
+    let scene_4d: &SubScene4 = scene.scene_4d.as_ref().unwrap();
+    assert_eq!(scene_4d.objects.len(), 2);
+    assert_eq!(scene_4d.light_sources, vec![Float4::new(2.0, 2.0, 4.0, 0.0)]);
+    assert_eq!(scene_4d.objects[1].transform.position, Float4::new(3.0, 0.0, 0.0, 0.0));
+
+    // The hypersphere surface is 1 unit from its centre
+    assert!(f32::abs(scene_4d.objects[1].distance(Float4::new(3.0, 0.0, 0.0, 2.0)) - 1.0) < 1e-5);
+    assert!(f32::abs(scene_4d.distance(Float4::new(3.0, 0.0, 0.0, 2.0)) - 1.0) < 1e-5);
+
+    // The tesseract is only rotated in XW so its YZ faces stay in place
+    assert!(f32::abs(scene_4d.objects[0].distance(Float4::new(0.0, 2.0, 0.0, 0.0)) - 1.0) < 1e-5);
+}
+
+#[test]
+fn parse_3d_scene()
+{
+    let source: &str = "
+dimensions 3
+object box
+    size 1 2 3
+    rotation xy 90
+end
+";
+    let scene: Scene = parse_scene(source, 1.0).unwrap();
+    assert!(!scene.is4d);
+    assert_eq!(scene.scene_3d.as_ref().unwrap().objects.len(), 1);
+}
+
+#[test]
+fn error_reports_line_and_field()
+{
+    let source: &str = "dimensions 4\nobject sphere\n    radius one\nend\n";
+    let error = parse_scene(source, 1.0).err().unwrap();
+    assert_eq!(error.line, 3);
+    assert_eq!(error.field, "radius");
+
+    let source: &str = "dimensions 4\nobject box\n    size 1 1 1\nend\n";
+    let error = parse_scene(source, 1.0).err().unwrap();
+    assert_eq!(error.line, 3);
+    assert_eq!(error.field, "size");
+
+    let source: &str = "dimensions 3\nobject box\n    rotation xw 10\nend\n";
+    let error = parse_scene(source, 1.0).err().unwrap();
+    assert_eq!(error.line, 3);
+    assert_eq!(error.field, "rotation");
+}
+
+#[test]
+fn error_reports_missing_fields()
+{
+    let source: &str = "\n\nobject sphere\n    position 1 0 0 0\nend\n";
+    let error = parse_scene(source, 1.0).err().unwrap();
+    assert_eq!(error.line, 3);
+    assert_eq!(error.field, "radius");
+
+    let source: &str = "object sphere\n    radius 1\n";
+    let error = parse_scene(source, 1.0).err().unwrap();
+    assert_eq!(error.line, 1);
+    assert_eq!(error.field, "object");
+}
+
+#[test]
+fn parse_4d_primitives()
+{
+    let source: &str = "
+object duocylinder
+    radii 1 2
+end
+object tiger
+    radii 2 2
+    radius 0.5
+    position 10 0 0 0
+end
+object spherone
+    radius 1
+    height 1
+end
+";
+    let scene: Scene = parse_scene(source, 1.0).unwrap();
+    let scene_4d: &SubScene4 = scene.scene_4d.as_ref().unwrap();
+    assert_eq!(scene_4d.objects.len(), 3);
+    assert!(f32::abs(scene_4d.objects[0].distance(Float4::new(3.0, 0.0, 0.0, 0.0)) - 2.0) < 1e-5);
+    assert!(f32::abs(scene_4d.objects[1].distance(Float4::new(12.0, 0.0, 2.0, 0.0)) + 0.5) < 1e-5);
+
+    // 4D only primitives are rejected in 3D scenes
+    let error = parse_scene("dimensions 3\nobject tiger\nend\n", 1.0).err().unwrap();
+    assert_eq!(error.line, 2);
+    assert_eq!(error.field, "object");
+
+    let error = parse_scene("object tiger\n    radius 1\nend\n", 1.0).err().unwrap();
+    assert_eq!(error.line, 1);
+    assert_eq!(error.field, "radii");
+}
+
+#[test]
+fn parse_regular_polychora()
+{
+    let source: &str = "
+object sixteen_cell
+    radius 1
+end
+object six_hundred_cell
+    radius 2
+    edge_radius 0.1
+end
+";
+    let scene: Scene = parse_scene(source, 1.0).unwrap();
+    let scene_4d: &SubScene4 = scene.scene_4d.as_ref().unwrap();
+
+    // 16-cell vertices lie on the axes
+    assert!(f32::abs(scene_4d.objects[0].distance(Float4::new(0.0, 2.0, 0.0, 0.0)) - 1.0) < 1e-4);
+    assert!(scene_4d.objects[1].distance(Float4::new(0.0, 0.0, 0.0, 0.0)) < -1.5);
+}
+
+#[test]
+fn parse_csg_operations()
+{
+    let source: &str = "
+object box
+    size 1 1 1 1
+    colour 1 0 0
+end
+object sphere
+    radius 0.5
+    position 1 0 0 0
+    operation subtraction
+    blend polynomial 0.1
+    colour 0 1 0
+end
+";
+    let scene: Scene = parse_scene(source, 1.0).unwrap();
+    let scene_4d: &SubScene4 = scene.scene_4d.as_ref().unwrap();
+
+    // The sphere carves a hole in the box face and the hole keeps the sphere's colour
+    assert!(scene_4d.distance(Float4::new(0.9, 0.0, 0.0, 0.0)) > 0.0);
+    assert_eq!(scene_4d.evaluate(Float4::new(0.6, 0.0, 0.0, 0.0)).1, Some(1));
+    assert_eq!(scene_4d.evaluate(Float4::new(0.0, 0.9, 0.0, 0.0)).1, Some(0));
+
+    // Packets of points pick the same objects
+    let points: [Float4; LANES] = std::array::from_fn(|i| Float4::new(0.15 * i as f32, 0.9 - 0.1 * i as f32, 0.0, 0.0));
+    let (distances, ids) = scene_4d.evaluate_packet(Float4x8::from_lanes(points));
+    for i in 0..LANES
+    {
+        assert_eq!(ids[i], scene_4d.evaluate(points[i]).1);
+        assert!(f32::abs(distances.lane(i) - scene_4d.distance(points[i])) < 1e-6);
+    }
+
+    let error = parse_scene("object sphere\n    radius 1\n    blend stairs 0.5\nend\n", 1.0).err().unwrap();
+    assert_eq!(error.line, 3);
+    assert_eq!(error.field, "blend");
+
+    // Blends with no width, or a negative one, have no seam to blend
+    for blend in ["polynomial 0", "exponential -0.1", "chamfer 0", "stairs 0.5 0", "stairs -0.5 4"]
+    {
+        let source: String = format!("object sphere\n    radius 1\n    blend {}\nend\n", blend);
+        let error = parse_scene(&source, 1.0).err().unwrap();
+        assert_eq!((error.line, error.field.as_str()), (3, "blend"), "{}", blend);
+    }
+}
+
+#[test]
+fn parse_domain_operators()
+{
+    let source: &str = "
+object sphere
+    radius 0.5
+    repeat 0 0 0 4
+    onion 0.1
+end
+object box
+    position 10 0 0 0
+    size 1 1 1 1
+    elongate 1 0 0 0
+    twist xy z 45
+    symmetry x w
+end
+";
+    let scene: Scene = parse_scene(source, 1.0).unwrap();
+    let scene_4d: &SubScene4 = scene.scene_4d.as_ref().unwrap();
+
+    // Copies of the hollow sphere repeat along W
+    assert!(f32::abs(scene_4d.objects[0].distance(Float4::new(0.0, 0.0, 0.0, 8.0)) - 0.4) < 1e-5);
+    assert!(f32::abs(scene_4d.objects[0].distance(Float4::new(0.0, 0.0, 0.0, 8.5)) + 0.1) < 1e-5);
+
+    // The elongated box reaches 2 along X, twisting in XY does nothing at z = 0
+    assert!(f32::abs(scene_4d.objects[1].distance(Float4::new(13.0, 0.0, 0.0, 0.0)) - 1.0) < 1e-5);
+
+    let error = parse_scene("dimensions 3\nobject sphere\n    radius 1\n    twist xy w 10\nend\n", 1.0).err().unwrap();
+    assert_eq!(error.line, 4);
+    assert_eq!(error.field, "twist");
+
+    // A zero normal has no direction to mirror across
+    let error = parse_scene("object sphere\n    radius 1\n    mirror 0 0 0 0\nend\n", 1.0).err().unwrap();
+    assert_eq!((error.line, error.field.as_str()), (3, "mirror"));
+
+    // Nor does a zero half space or hyperplane normal, which would make every distance NaN
+    for primitive in ["half_space", "hyperplane"]
+    {
+        let error = parse_scene(&format!("object {}\n    normal 0 0 0 0\nend\n", primitive), 1.0).err().unwrap();
+        assert_eq!((error.line, error.field.as_str()), (2, "normal"));
+    }
+}
+
+#[test]
+fn parse_object_hierarchy()
+{
+    let source: &str = "
+object box
+    name base
+    position 0 0 0 5
+    scale 2
+    size 1 1 1 1
+    spin xw 90
+end
+object sphere
+    parent base
+    position 1 0 0 0
+    radius 0.25
+end
+";
+    let mut scene: Scene = parse_scene(source, 1.0).unwrap();
+    let scene_4d: &mut SubScene4 = scene.scene_4d.as_mut().unwrap();
+    assert_eq!(scene_4d.objects[1].parent, Some(0));
+
+    // The child is placed and scaled in its parent's space
+    assert_eq!(scene_4d.objects[1].world_transform.position, Float4::new(2.0, 0.0, 0.0, 5.0));
+    assert!(f32::abs(scene_4d.objects[0].distance(Float4::new(3.0, 0.0, 0.0, 5.0)) - 1.0) < 1e-5);
+    assert!(f32::abs(scene_4d.objects[1].distance(Float4::new(2.0, 0.0, 0.0, 5.0)) + 0.5) < 1e-5);
+
+    // A quarter turn of the parent in XW carries the child with it
+    scene_4d.spin_objects(1.0);
+    let position: Float4 = scene_4d.objects[1].world_transform.position;
+    assert!(f32::abs(position.x) < 1e-4 && f32::abs(f32::abs(position.w - 5.0) - 2.0) < 1e-4);
+
+    let error = parse_scene("object sphere\n    radius 1\n    parent base\nend\n", 1.0).err().unwrap();
+    assert_eq!(error.line, 3);
+    assert_eq!(error.field, "parent");
+
+    // A scale of 0 divides by zero and a negative one turns the object inside out
+    for scale in ["0", "-2"]
+    {
+        let error = parse_scene(&format!("object sphere\n    radius 1\n    scale {}\nend\n", scale), 1.0).err().unwrap();
+        assert_eq!((error.line, error.field.as_str()), (3, "scale"));
+    }
+}
+
+#[test]
+fn parse_raymarch_settings()
+{
+    let source: &str = "
+raymarch
+    preset preview
+    max_steps 64
+end
+object sphere
+    radius 1
+end
+";
+    let scene: Scene = parse_scene(source, 1.0).unwrap();
+    assert_eq!(scene.raymarch_settings, RaymarchSettings{ max_steps: 64, ..RaymarchSettings::PREVIEW });
+    assert_eq!(parse_scene("object sphere\n    radius 1\nend\n", 1.0).unwrap().raymarch_settings, RaymarchSettings::DEFAULT);
+
+    let error = parse_scene("raymarch\n    max_steps 1.5\nend\n", 1.0).err().unwrap();
+    assert_eq!(error.line, 2);
+    assert_eq!(error.field, "max_steps");
+
+    // No steps misses everything, and distances that are not more than 0 leave the image empty
+    for setting in ["max_steps 0", "max_distance 0", "surface_distance -0.1", "normal_epsilon inf"]
+    {
+        let error = parse_scene(&format!("raymarch\n    {}\nend\n", setting), 1.0).err().unwrap();
+        assert_eq!((error.line, error.field.as_str()), (2, setting.split(' ').next().unwrap()));
+    }
+
+    let scene: Scene = parse_scene("raymarch\n    method relaxed 1.2\nend\nobject sphere\n    radius 1\nend\n", 1.0).unwrap();
+    assert_eq!(scene.raymarch_settings.method, MarchMethod::OverRelaxed(1.2));
+    for method in ["relaxed 2.5", "accelerated -1", "sphere 1.5"]
+    {
+        let error = parse_scene(&format!("raymarch\n    method {}\nend\n", method), 1.0).err().unwrap();
+        assert_eq!((error.line, error.field.as_str()), (2, "method"));
+    }
+}
@@ -0,0 +1,661 @@
+//! Signed distance functions that can be composed at runtime.
+//!
+//! Any type implementing [`Sdf`] can be raymarched, including plain closures. Scenes are built as a
+//! tree of boxed nodes: the primitives of `distance_functions` are the leaves, and transform and
+//! CSG nodes own their children.
+//!
+//! Sdfs can also measure a packet of eight points at once with `distance_packet`. The primitives,
+//! transforms and CSG nodes have packet versions built from the lane types in `mathematics`, the
+//! rest fall back to measuring each lane in turn.
+
+use crate::csg::{self, blend_max, blend_min, Blend, CsgOperation};
+use crate::distance_functions::*;
+use crate::domain;
+use crate::mathematics::bivector3::Bivector3;
+use crate::mathematics::bivector4::Bivector4;
+use crate::mathematics::f32x8::{F32x8, LANES};
+use crate::mathematics::float2::Float2;
+use crate::mathematics::float3::Float3;
+use crate::mathematics::float3x8::Float3x8;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::float4x8::Float4x8;
+use crate::mathematics::multivectors::{Rotor, Vector, VectorPacket};
+use crate::mathematics::rotor3::Rotor3;
+use crate::mathematics::rotor4::Rotor4;
+use crate::polychora::{sdf_polychoron, Polychoron};
+
+pub trait Sdf<V: Vector>: Send + Sync
+{
+    /// Signed distance from p to the surface, negative inside
+    fn distance(&self, p: V) -> f32;
+
+    /// Distance along with the index of the object whose surface is closest, for sdfs made of several objects
+    fn evaluate(&self, p: V) -> (f32, Option<usize>)
+    {
+        return (self.distance(p), None);
+    }
+
+    /// Distances from every lane of a packet, sdfs without a packet version measure one lane at a time
+    fn distance_packet(&self, p: V::Packet) -> F32x8
+    {
+        return F32x8::from_fn(|i| self.distance(p.lane(i)));
+    }
+
+    /// `evaluate` for every lane of a packet, sdfs that override `evaluate` should override this too
+    fn evaluate_packet(&self, p: V::Packet) -> (F32x8, [Option<usize>; LANES])
+    {
+        return (self.distance_packet(p), [None; LANES]);
+    }
+}
+
+/// Closures can be used directly as distance functions
+impl<V: Vector, F: Fn(V) -> f32 + Send + Sync> Sdf<V> for F
+{
+    fn distance(&self, p: V) -> f32
+    {
+        return self(p);
+    }
+}
+
+pub type SdfNode<V> = Box<dyn Sdf<V>>;
+
+// Primitives
+
+pub struct Sphere<V: Vector>
+{
+    pub centre: V,
+    pub radius: f32,
+}
+
+impl<V: Vector + Send + Sync> Sdf<V> for Sphere<V>
+{
+    fn distance(&self, p: V) -> f32
+    {
+        return sdf_sphere(p, self.centre, self.radius);
+    }
+
+    fn distance_packet(&self, p: V::Packet) -> F32x8
+    {
+        return sdf_sphere_packet(p, self.centre, self.radius);
+    }
+}
+
+pub struct Box3
+{
+    pub centre: Float3,
+    pub size: Float3,
+    pub edge_radius: f32,
+}
+
+impl Sdf<Float3> for Box3
+{
+    fn distance(&self, p: Float3) -> f32
+    {
+        return sdf_box3(p, self.centre, self.size, self.edge_radius);
+    }
+
+    fn distance_packet(&self, p: Float3x8) -> F32x8
+    {
+        return sdf_box3_packet(p, self.centre, self.size, self.edge_radius);
+    }
+}
+
+pub struct Box4
+{
+    pub centre: Float4,
+    pub size: Float4,
+    pub edge_radius: f32,
+}
+
+impl Sdf<Float4> for Box4
+{
+    fn distance(&self, p: Float4) -> f32
+    {
+        return sdf_box4(p, self.centre, self.size, self.edge_radius);
+    }
+
+    fn distance_packet(&self, p: Float4x8) -> F32x8
+    {
+        return sdf_box4_packet(p, self.centre, self.size, self.edge_radius);
+    }
+}
+
+pub struct HalfSpace<V: Vector>
+{
+    pub normal: V,
+    pub offset: f32,
+}
+
+impl<V: Vector + Send + Sync> Sdf<V> for HalfSpace<V>
+{
+    fn distance(&self, p: V) -> f32
+    {
+        return sdf_half_space(p, self.normal, self.offset);
+    }
+
+    fn distance_packet(&self, p: V::Packet) -> F32x8
+    {
+        return sdf_half_space_packet(p, self.normal, self.offset);
+    }
+}
+
+pub struct Hyperplane<V: Vector>
+{
+    pub normal: V,
+    pub offset: f32,
+}
+
+impl<V: Vector + Send + Sync> Sdf<V> for Hyperplane<V>
+{
+    fn distance(&self, p: V) -> f32
+    {
+        return sdf_hyperplane(p, self.normal, self.offset);
+    }
+
+    fn distance_packet(&self, p: V::Packet) -> F32x8
+    {
+        return sdf_hyperplane_packet(p, self.normal, self.offset);
+    }
+}
+
+pub struct Capsule<V: Vector>
+{
+    pub a: V,
+    pub b: V,
+    pub radius: f32,
+}
+
+impl<V: Vector + Send + Sync> Sdf<V> for Capsule<V>
+{
+    fn distance(&self, p: V) -> f32
+    {
+        return sdf_capsule(p, self.a, self.b, self.radius);
+    }
+
+    fn distance_packet(&self, p: V::Packet) -> F32x8
+    {
+        return sdf_capsule_packet(p, self.a, self.b, self.radius);
+    }
+}
+
+pub struct Duocylinder
+{
+    pub centre: Float4,
+    pub radii: Float2,
+}
+
+impl Sdf<Float4> for Duocylinder
+{
+    fn distance(&self, p: Float4) -> f32
+    {
+        return sdf_duocylinder(p, self.centre, self.radii);
+    }
+}
+
+pub struct Spherinder
+{
+    pub centre: Float4,
+    pub radius: f32,
+    pub height: f32,
+}
+
+impl Sdf<Float4> for Spherinder
+{
+    fn distance(&self, p: Float4) -> f32
+    {
+        return sdf_spherinder(p, self.centre, self.radius, self.height);
+    }
+}
+
+pub struct Cubinder
+{
+    pub centre: Float4,
+    pub radius: f32,
+    pub size: f32,
+}
+
+impl Sdf<Float4> for Cubinder
+{
+    fn distance(&self, p: Float4) -> f32
+    {
+        return sdf_cubinder(p, self.centre, self.radius, self.size);
+    }
+}
+
+pub struct CylinderPrism
+{
+    pub centre: Float4,
+    pub radius: f32,
+    pub height: f32,
+    pub length: f32,
+}
+
+impl Sdf<Float4> for CylinderPrism
+{
+    fn distance(&self, p: Float4) -> f32
+    {
+        return sdf_cylinder_prism(p, self.centre, self.radius, self.height, self.length);
+    }
+}
+
+pub struct Cone4
+{
+    pub centre: Float4,
+    pub radius: f32,
+    pub height: f32,
+    pub length: f32,
+}
+
+impl Sdf<Float4> for Cone4
+{
+    fn distance(&self, p: Float4) -> f32
+    {
+        return sdf_cone4(p, self.centre, self.radius, self.height, self.length);
+    }
+}
+
+pub struct Spherone
+{
+    pub centre: Float4,
+    pub radius: f32,
+    pub height: f32,
+}
+
+impl Sdf<Float4> for Spherone
+{
+    fn distance(&self, p: Float4) -> f32
+    {
+        return sdf_spherone(p, self.centre, self.radius, self.height);
+    }
+}
+
+pub struct Tiger
+{
+    pub centre: Float4,
+    pub radii: Float2,
+    pub radius: f32,
+}
+
+impl Sdf<Float4> for Tiger
+{
+    fn distance(&self, p: Float4) -> f32
+    {
+        return sdf_tiger(p, self.centre, self.radii, self.radius);
+    }
+}
+
+pub struct Ditorus
+{
+    pub centre: Float4,
+    pub radii: Float2,
+    pub radius: f32,
+}
+
+impl Sdf<Float4> for Ditorus
+{
+    fn distance(&self, p: Float4) -> f32
+    {
+        return sdf_ditorus(p, self.centre, self.radii, self.radius);
+    }
+}
+
+pub struct Torisphere
+{
+    pub centre: Float4,
+    pub major_radius: f32,
+    pub minor_radius: f32,
+}
+
+impl Sdf<Float4> for Torisphere
+{
+    fn distance(&self, p: Float4) -> f32
+    {
+        return sdf_torisphere(p, self.centre, self.major_radius, self.minor_radius);
+    }
+}
+
+pub struct Spheritorus
+{
+    pub centre: Float4,
+    pub major_radius: f32,
+    pub minor_radius: f32,
+}
+
+impl Sdf<Float4> for Spheritorus
+{
+    fn distance(&self, p: Float4) -> f32
+    {
+        return sdf_spheritorus(p, self.centre, self.major_radius, self.minor_radius);
+    }
+}
+
+pub struct RegularPolychoron
+{
+    pub centre: Float4,
+    pub polychoron: Polychoron,
+    pub edge_radius: f32,
+}
+
+impl Sdf<Float4> for RegularPolychoron
+{
+    fn distance(&self, p: Float4) -> f32
+    {
+        return sdf_polychoron(p, self.centre, &self.polychoron, self.edge_radius);
+    }
+}
+
+// Transforms
+// These move the sample point into the child's space rather than moving the child
+
+pub struct Translate<V: Vector>
+{
+    pub offset: V,
+    pub child: SdfNode<V>,
+}
+
+impl<V: Vector + Send + Sync> Sdf<V> for Translate<V>
+{
+    fn distance(&self, p: V) -> f32
+    {
+        return self.child.distance(p - self.offset);
+    }
+
+    fn distance_packet(&self, p: V::Packet) -> F32x8
+    {
+        return self.child.distance_packet(p - V::Packet::splat(self.offset));
+    }
+}
+
+pub struct Rotate3
+{
+    pub rotation: Rotor3,
+    pub child: SdfNode<Float3>,
+}
+
+impl Sdf<Float3> for Rotate3
+{
+    fn distance(&self, p: Float3) -> f32
+    {
+        return self.child.distance(Rotor3::reverse(&self.rotation) * p);
+    }
+
+    fn distance_packet(&self, p: Float3x8) -> F32x8
+    {
+        return self.child.distance_packet(Rotor3::reverse(&self.rotation) * p);
+    }
+}
+
+pub struct Rotate4
+{
+    pub rotation: Rotor4,
+    pub child: SdfNode<Float4>,
+}
+
+impl Sdf<Float4> for Rotate4
+{
+    fn distance(&self, p: Float4) -> f32
+    {
+        return self.child.distance(Rotor4::reverse(&self.rotation) * p);
+    }
+
+    fn distance_packet(&self, p: Float4x8) -> F32x8
+    {
+        return self.child.distance_packet(Rotor4::reverse(&self.rotation) * p);
+    }
+}
+
+/// Uniform scale, the distance is scaled back so it stays a bound
+pub struct Scale<V: Vector>
+{
+    pub factor: f32,
+    pub child: SdfNode<V>,
+}
+
+impl<V: Vector + Send + Sync> Sdf<V> for Scale<V>
+{
+    fn distance(&self, p: V) -> f32
+    {
+        return self.child.distance(p / self.factor) * self.factor;
+    }
+
+    fn distance_packet(&self, p: V::Packet) -> F32x8
+    {
+        return self.child.distance_packet(p / self.factor) * self.factor;
+    }
+}
+
+// Domain Operators
+// Like the transforms these warp the sample point, see `domain` for how each one behaves
+
+pub struct Repeat<V: Vector>
+{
+    pub period: V,
+    pub child: SdfNode<V>,
+}
+
+impl<V: Vector + Send + Sync> Sdf<V> for Repeat<V>
+{
+    fn distance(&self, p: V) -> f32
+    {
+        return self.child.distance(domain::repeat(p, self.period));
+    }
+}
+
+pub struct RepeatLimited<V: Vector>
+{
+    pub period: V,
+    pub count: V,
+    pub child: SdfNode<V>,
+}
+
+impl<V: Vector + Send + Sync> Sdf<V> for RepeatLimited<V>
+{
+    fn distance(&self, p: V) -> f32
+    {
+        return self.child.distance(domain::repeat_limited(p, self.period, self.count));
+    }
+}
+
+pub struct Mirror<V: Vector>
+{
+    pub normal: V,
+    pub offset: f32,
+    pub child: SdfNode<V>,
+}
+
+impl<V: Vector + Send + Sync> Sdf<V> for Mirror<V>
+{
+    fn distance(&self, p: V) -> f32
+    {
+        return self.child.distance(domain::mirror(p, self.normal, self.offset));
+    }
+}
+
+pub struct Symmetry<V: Vector>
+{
+    pub axes: V,
+    pub child: SdfNode<V>,
+}
+
+impl<V: Vector + Send + Sync> Sdf<V> for Symmetry<V>
+{
+    fn distance(&self, p: V) -> f32
+    {
+        return self.child.distance(domain::symmetry(p, self.axes));
+    }
+}
+
+pub struct Elongate<V: Vector>
+{
+    pub size: V,
+    pub child: SdfNode<V>,
+}
+
+impl<V: Vector + Send + Sync> Sdf<V> for Elongate<V>
+{
+    fn distance(&self, p: V) -> f32
+    {
+        return self.child.distance(domain::elongate(p, self.size));
+    }
+}
+
+pub struct Round<V: Vector>
+{
+    pub radius: f32,
+    pub child: SdfNode<V>,
+}
+
+impl<V: Vector> Sdf<V> for Round<V>
+{
+    fn distance(&self, p: V) -> f32
+    {
+        return domain::round(self.child.distance(p), self.radius);
+    }
+
+    fn distance_packet(&self, p: V::Packet) -> F32x8
+    {
+        return self.child.distance_packet(p).map(|distance| domain::round(distance, self.radius));
+    }
+}
+
+pub struct Onion<V: Vector>
+{
+    pub thickness: f32,
+    pub child: SdfNode<V>,
+}
+
+impl<V: Vector> Sdf<V> for Onion<V>
+{
+    fn distance(&self, p: V) -> f32
+    {
+        return domain::onion(self.child.distance(p), self.thickness);
+    }
+
+    fn distance_packet(&self, p: V::Packet) -> F32x8
+    {
+        return self.child.distance_packet(p).map(|distance| domain::onion(distance, self.thickness));
+    }
+}
+
+/// Twists around an axis orthogonal to the plane, or bends when the axis lies in the plane
+pub struct Twist3
+{
+    pub plane: Bivector3,
+    pub axis: usize,
+    /// Radians per unit along the axis
+    pub rate: f32,
+    pub child: SdfNode<Float3>,
+}
+
+impl Sdf<Float3> for Twist3
+{
+    fn distance(&self, p: Float3) -> f32
+    {
+        return self.child.distance(domain::twist::<Float3, Bivector3, Rotor3>(p, &self.plane, self.axis, self.rate));
+    }
+}
+
+/// Twists around an axis orthogonal to the plane, or bends when the axis lies in the plane
+pub struct Twist4
+{
+    pub plane: Bivector4,
+    pub axis: usize,
+    /// Radians per unit along the axis
+    pub rate: f32,
+    pub child: SdfNode<Float4>,
+}
+
+impl Sdf<Float4> for Twist4
+{
+    fn distance(&self, p: Float4) -> f32
+    {
+        return self.child.distance(domain::twist::<Float4, Bivector4, Rotor4>(p, &self.plane, self.axis, self.rate));
+    }
+}
+
+// CSG Operators
+// Each takes a blend, use Blend::Sharp for the plain operators
+
+pub struct Union<V: Vector>
+{
+    pub children: Vec<SdfNode<V>>,
+    pub blend: Blend,
+}
+
+impl<V: Vector> Sdf<V> for Union<V>
+{
+    fn distance(&self, p: V) -> f32
+    {
+        return self.children.iter().map(|child| child.distance(p)).reduce(|a, b| blend_min(a, b, self.blend)).unwrap_or(f32::MAX);
+    }
+
+    fn distance_packet(&self, p: V::Packet) -> F32x8
+    {
+        return self.children.iter().map(|child| child.distance_packet(p)).reduce(|a, b| F32x8::zip_map(a, b, |a, b| blend_min(a, b, self.blend))).unwrap_or(F32x8::splat(f32::MAX));
+    }
+}
+
+pub struct Intersection<V: Vector>
+{
+    pub children: Vec<SdfNode<V>>,
+    pub blend: Blend,
+}
+
+impl<V: Vector> Sdf<V> for Intersection<V>
+{
+    fn distance(&self, p: V) -> f32
+    {
+        return self.children.iter().map(|child| child.distance(p)).reduce(|a, b| blend_max(a, b, self.blend)).unwrap_or(f32::MIN);
+    }
+
+    fn distance_packet(&self, p: V::Packet) -> F32x8
+    {
+        return self.children.iter().map(|child| child.distance_packet(p)).reduce(|a, b| F32x8::zip_map(a, b, |a, b| blend_max(a, b, self.blend))).unwrap_or(F32x8::splat(f32::MIN));
+    }
+}
+
+/// Removes the subtracted shape from the base shape
+pub struct Subtraction<V: Vector>
+{
+    pub base: SdfNode<V>,
+    pub subtracted: SdfNode<V>,
+    pub blend: Blend,
+}
+
+impl<V: Vector> Sdf<V> for Subtraction<V>
+{
+    fn distance(&self, p: V) -> f32
+    {
+        return csg::subtraction(self.base.distance(p), self.subtracted.distance(p), self.blend).0;
+    }
+
+    fn distance_packet(&self, p: V::Packet) -> F32x8
+    {
+        return csg::combine_packet(CsgOperation::Subtraction, self.base.distance_packet(p), self.subtracted.distance_packet(p), self.blend).0;
+    }
+}
+
+/// The space covered by exactly one of the two shapes
+pub struct Xor<V: Vector>
+{
+    pub a: SdfNode<V>,
+    pub b: SdfNode<V>,
+    pub blend: Blend,
+}
+
+impl<V: Vector> Sdf<V> for Xor<V>
+{
+    fn distance(&self, p: V) -> f32
+    {
+        return csg::xor(self.a.distance(p), self.b.distance(p), self.blend).0;
+    }
+
+    fn distance_packet(&self, p: V::Packet) -> F32x8
+    {
+        return csg::combine_packet(CsgOperation::Xor, self.a.distance_packet(p), self.b.distance_packet(p), self.blend).0;
+    }
+}
+
+#[cfg(test)]
+#[path = "sdf_tests.rs"]
+mod tests;
@@ -0,0 +1,118 @@
+use crate::csg::Blend;
+use crate::mathematics::approx_equal;
+use crate::mathematics::bivector4::Bivector4;
+use crate::mathematics::f32x8::{F32x8, LANES};
+use crate::mathematics::float2::Float2;
+use crate::mathematics::float3::Float3;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::float4x8::Float4x8;
+use crate::mathematics::multivectors::{Rotor, VectorPacket};
+use crate::mathematics::PI;
+use crate::mathematics::rotor4::Rotor4;
+use crate::raymarching::{self, RaymarchSettings};
+use crate::sdf::*;
+
+fn unit_box4() -> SdfNode<Float4>
+{
+    return Box::new(Box4{ centre: Float4::new(0.0, 0.0, 0.0, 0.0), size: Float4::new(1.0, 1.0, 1.0, 1.0), edge_radius: 0.0 });
+}
+
+fn sphere4(centre: Float4, radius: f32) -> SdfNode<Float4>
+{
+    return Box::new(Sphere{ centre: centre, radius: radius });
+}
+
+#[test]
+fn closures_are_distance_functions()
+{
+    let plane = |p: Float3| p.y;
+    assert!(approx_equal(plane.distance(Float3::new(5.0, 2.0, 1.0)), 2.0));
+
+    let ro: Float3 = Float3::new(0.0, 3.0, 0.0);
+    let rd: Float3 = Float3::new(0.0, -1.0, 0.0);
+    let distance: f32 = raymarching::raymarch(&ro, &rd, &plane, &RaymarchSettings::DEFAULT).distance;
+    assert!(f32::abs(distance - 3.0) < 0.001);
+}
+
+#[test]
+fn leaves_match_primitives()
+{
+    let p: Float4 = Float4::new(3.0, 0.0, 0.0, 0.0);
+    assert!(approx_equal(unit_box4().distance(p), 2.0));
+    assert!(approx_equal(sphere4(Float4::new(0.0, 0.0, 0.0, 0.0), 1.5).distance(p), 1.5));
+}
+
+#[test]
+fn transforms()
+{
+    let translated = Translate{ offset: Float4::new(0.0, 0.0, 0.0, 5.0), child: unit_box4() };
+    assert!(approx_equal(translated.distance(Float4::new(0.0, 0.0, 0.0, 5.0)), -1.0));
+    assert!(approx_equal(translated.distance(Float4::new(0.0, 0.0, 0.0, 2.0)), 2.0));
+
+    let scaled = Scale{ factor: 2.0, child: unit_box4() };
+    assert!(approx_equal(scaled.distance(Float4::new(3.0, 0.0, 0.0, 0.0)), 1.0));
+
+    // A box rotated 45 degrees in XW reaches sqrt(2) along X
+    let rotation: Rotor4 = Rotor4::bivector_angle(&Bivector4::new(0.0, 0.0, 0.0, 1.0, 0.0, 0.0), PI / 4.0);
+    let rotated = Rotate4{ rotation: rotation, child: unit_box4() };
+    assert!(f32::abs(rotated.distance(Float4::new(3.0, 0.0, 0.0, 0.0)) - (3.0 - f32::sqrt(2.0))) < 1e-5);
+}
+
+#[test]
+fn csg()
+{
+    let origin: Float4 = Float4::new(0.0, 0.0, 0.0, 0.0);
+    let offset: Float4 = Float4::new(2.0, 0.0, 0.0, 0.0);
+
+    let union = Union{ children: vec![sphere4(origin, 1.0), sphere4(offset, 1.0)], blend: Blend::Sharp };
+    assert!(approx_equal(union.distance(Float4::new(1.0, 0.0, 0.0, 0.0)), 0.0));
+    assert!(approx_equal(union.distance(Float4::new(4.0, 0.0, 0.0, 0.0)), 1.0));
+
+    let intersection = Intersection{ children: vec![sphere4(origin, 1.5), sphere4(offset, 1.5)], blend: Blend::Sharp };
+    assert!(approx_equal(intersection.distance(Float4::new(1.0, 0.0, 0.0, 0.0)), -0.5));
+    assert!(intersection.distance(origin) > 0.0);
+
+    let subtraction = Subtraction{ base: unit_box4(), subtracted: sphere4(origin, 0.5), blend: Blend::Sharp };
+    assert!(approx_equal(subtraction.distance(origin), 0.5));
+    assert!(approx_equal(subtraction.distance(Float4::new(0.75, 0.0, 0.0, 0.0)), -0.25));
+
+    let xor = Xor{ a: sphere4(origin, 1.5), b: sphere4(offset, 1.5), blend: Blend::Sharp };
+    assert!(approx_equal(xor.distance(Float4::new(1.0, 0.0, 0.0, 0.0)), 0.5));
+    assert!(approx_equal(xor.distance(Float4::new(-1.0, 0.0, 0.0, 0.0)), -0.5));
+
+    // Blending pulls the surface out where the spheres meet
+    let smooth = Union{ children: vec![sphere4(origin, 1.0), sphere4(offset, 1.0)], blend: Blend::Polynomial(0.5) };
+    assert!(smooth.distance(Float4::new(1.0, 0.0, 0.0, 0.0)) < 0.0);
+    assert!(approx_equal(smooth.distance(Float4::new(-2.0, 0.0, 0.0, 0.0)), 1.0));
+}
+
+#[test]
+fn packets_match_single_points()
+{
+    let origin: Float4 = Float4::new(0.0, 0.0, 0.0, 0.0);
+    let offset: Float4 = Float4::new(2.0, 0.0, 0.0, 0.0);
+    let rotation: Rotor4 = Rotor4::bivector_angle(&Bivector4::new(0.0, 0.0, 1.0, 1.0, 0.0, 0.0), 0.6);
+
+    let sdfs: Vec<SdfNode<Float4>> = vec![
+        unit_box4(),
+        sphere4(offset, 1.5),
+        Box::new(Capsule{ a: origin, b: offset, radius: 0.5 }),
+        Box::new(HalfSpace{ normal: Float4::new(0.0, 1.0, 0.0, 0.0), offset: 0.5 }),
+        Box::new(Rotate4{ rotation: rotation, child: Box::new(Scale{ factor: 0.5, child: unit_box4() }) }),
+        Box::new(Union{ children: vec![sphere4(origin, 1.0), Box::new(Translate{ offset: offset, child: unit_box4() })], blend: Blend::Polynomial(0.5) }),
+        Box::new(Subtraction{ base: unit_box4(), subtracted: sphere4(origin, 1.2), blend: Blend::Chamfer(0.1) }),
+        // No packet version, measured one lane at a time
+        Box::new(Duocylinder{ centre: origin, radii: Float2::new(1.0, 0.5) }),
+    ];
+
+    let points: [Float4; LANES] = std::array::from_fn(|i| Float4::new(i as f32 * 0.5 - 2.0, 1.0 - i as f32 * 0.3, 0.2 * i as f32, -0.4));
+    let packet: Float4x8 = Float4x8::from_lanes(points);
+    for (index, sdf) in sdfs.iter().enumerate()
+    {
+        let distances: F32x8 = sdf.distance_packet(packet);
+        for (i, point) in points.iter().enumerate()
+        {
+            assert!(f32::abs(distances.lane(i) - sdf.distance(*point)) < 1e-5, "sdf {} lane {}", index, i);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! 3D cross sections of 4D scenes, as seen in 4D Games.
+//!
+//! Instead of a perspective projection of all four dimensions, the viewer lives inside a 3D
+//! hyperplane of the 4D world. Rays start on the hyperplane and never leave it, so the image shows
+//! the cross section of every object the hyperplane passes through. Marching stays safe because the
+//! distance to an object in 4D is never more than the distance to its cross section.
+
+use crate::camera::{Camera, Camera4};
+use crate::mathematics::float4::Float4;
+use crate::mathematics::multivectors::{Magnitude, Rotor, Vector};
+use crate::mathematics::rotor4::Rotor4;
+use crate::multi_view;
+
+/// The camera's hidden axis before its rotation
+const CAMERA_W: Float4 = Float4{ x: 0.0, y: 0.0, z: 0.0, w: 1.0 };
+
+/// The hyperplane of points p where dot(normal, p) is offset
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Slice
+{
+    /// Unit normal of the hyperplane, the one direction the viewer can not see along
+    pub normal: Float4,
+    /// Distance of the hyperplane from the origin along the normal
+    pub offset: f32,
+}
+
+impl Slice
+{
+    pub fn new(normal: Float4, offset: f32) -> Slice
+    {
+        return Slice{ normal: normal.normalized(), offset: offset };
+    }
+
+    /// The hyperplane through the camera across its own w axis, which every camera ray already lies in
+    pub fn from_camera(camera: &Camera4) -> Slice
+    {
+        let normal: Float4 = camera.get_camera_rotation() * CAMERA_W;
+        return Slice::new(normal, Float4::dot(normal, camera.get_camera_position()));
+    }
+
+    /// The turn of the camera that points its hidden w axis along the normal, so the turned camera's
+    /// rays span the hyperplane the same way they span the one across its own w axis
+    pub fn camera_turn(&self, camera: &Camera4) -> Rotor4
+    {
+        return multi_view::turn_to_normal(Rotor4::reverse(&camera.get_camera_rotation()) * self.normal);
+    }
+
+    /// How far p is from the hyperplane along the normal
+    pub fn signed_distance(&self, p: Float4) -> f32
+    {
+        return Float4::dot(self.normal, p) - self.offset;
+    }
+
+    /// The closest point on the hyperplane to p
+    pub fn project_point(&self, p: Float4) -> Float4
+    {
+        return p - self.normal * self.signed_distance(p);
+    }
+
+    /// The part of a direction along the hyperplane, stretched back to its old length. Directions
+    /// straight along the normal have no part in the hyperplane
+    pub fn project_direction(&self, d: Float4) -> Float4
+    {
+        let projected: Float4 = d - self.normal * Float4::dot(self.normal, d);
+        let length: f32 = projected.length();
+        if length == 0.0
+        {
+            return projected;
+        }
+        return projected * (d.length() / length);
+    }
+
+    /// Moves the hyperplane along its normal
+    pub fn slide(&mut self, distance: f32)
+    {
+        self.offset += distance;
+    }
+
+    /// Turns the hyperplane about a point, which stays where it is on the hyperplane
+    pub fn rotate_about(&mut self, rotation: Rotor4, pivot: Float4)
+    {
+        let pivot: Float4 = self.project_point(pivot);
+        self.normal = (rotation * self.normal).normalized();
+        self.offset = Float4::dot(self.normal, pivot);
+    }
+}
+
+#[cfg(test)]
+#[path = "slice_tests.rs"]
+mod tests;
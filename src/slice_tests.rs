@@ -0,0 +1,134 @@
+use std::sync::{Arc, RwLock};
+
+use crate::application::Application;
+use crate::camera::{Camera, Camera4};
+use crate::framebuffer::Framebuffer;
+use crate::mathematics::approx_equal;
+use crate::mathematics::bivector4::Bivector4;
+use crate::mathematics::float2::Float2;
+use crate::mathematics::float3::Float3;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::multivectors::{Magnitude, Rotor, Vector};
+use crate::mathematics::rotor4::Rotor4;
+use crate::renderer;
+use crate::scene::{Scene, SubScene4};
+use crate::scene_file::parse_scene;
+use crate::slice::*;
+use crate::worker_pool::WorkerPool;
+
+#[test]
+fn projection_stays_on_the_hyperplane()
+{
+    let slice: Slice = Slice::new(Float4::new(0.0, 0.0, 0.0, 2.0), 0.5);
+    assert_eq!(slice.normal, Float4::new(0.0, 0.0, 0.0, 1.0));
+
+    let p: Float4 = Float4::new(1.0, 2.0, 3.0, 4.0);
+    assert!(approx_equal(slice.signed_distance(p), 3.5));
+    assert_eq!(slice.project_point(p), Float4::new(1.0, 2.0, 3.0, 0.5));
+
+    // Directions lose their part along the normal but keep their length
+    let d: Float4 = Float4::new(0.0, 0.0, 3.0, 4.0);
+    assert_eq!(slice.project_direction(d), Float4::new(0.0, 0.0, 5.0, 0.0));
+    assert_eq!(slice.project_direction(slice.normal), Float4::new(0.0, 0.0, 0.0, 0.0));
+}
+
+#[test]
+fn slices_slide_and_turn_about_a_point()
+{
+    let mut slice: Slice = Slice::new(Float4::new(0.0, 0.0, 0.0, 1.0), 0.0);
+    slice.slide(0.25);
+    assert!(approx_equal(slice.offset, 0.25));
+
+    let pivot: Float4 = Float4::new(0.0, 0.0, 4.0, 0.25);
+    slice.rotate_about(Rotor4::bivector_angle(&Bivector4::new(0.0, 0.0, 0.0, 0.0, 0.0, 1.0), 0.3), pivot);
+    assert!(approx_equal(slice.normal.length(), 1.0));
+    assert!(slice.normal.z != 0.0);
+    assert!(approx_equal(slice.signed_distance(pivot), 0.0));
+}
+
+#[test]
+fn camera_rays_already_lie_in_the_camera_slice()
+{
+    let rotation: Rotor4 = Rotor4::bivector_angle(&Bivector4::new(1.0, 0.5, 0.0, 0.7, 0.2, 0.4), 1.1);
+    let camera: Camera4 = Camera4::new(Float4::new(0.0, 0.0, 0.0, 0.0), Float4::new(0.0, 0.0, 4.0, 0.0), rotation, 1.0, 70.0, 1.0);
+    let slice: Slice = Slice::from_camera(&camera);
+
+    assert!(approx_equal(slice.signed_distance(camera.get_camera_position()), 0.0));
+    for uv in [Float2::new(0.0, 0.0), Float2::new(0.3, 0.9), Float2::new(1.0, 0.5)]
+    {
+        assert!(approx_equal(Float4::dot(slice.normal, camera.get_ray_direction(uv)), 0.0));
+    }
+}
+
+#[test]
+fn cross_sections_shrink_away_from_the_centre()
+{
+    // A hypersphere cut at w shows a sphere of radius sqrt(1 - w^2)
+    let scene: Scene = parse_scene("object sphere\n    position 0 0 0 0\n    radius 1\nend\ncamera\n    offset 0 0 4 0\nend\n", 1.0).unwrap();
+    let scene: Arc<RwLock<Scene>> = Arc::new(RwLock::new(scene));
+    let application: Application = Application::new(1.0, 64, false);
+    let pool: WorkerPool = WorkerPool::new(2);
+
+    let covered = |w: f32| -> usize {
+        {
+            let mut locked = scene.write().unwrap();
+            let scene_4d: &mut SubScene4 = locked.scene_4d.as_mut().unwrap();
+            scene_4d.slice = Some(Slice::new(Float4::new(0.0, 0.0, 0.0, 1.0), w));
+        }
+        let framebuffer: Framebuffer = renderer::render_image(&pool, &scene, &application);
+        return framebuffer.colours().iter().filter(|colour| **colour != Float3::new(0.0, 0.0, 0.0)).count();
+    };
+
+    let centre: usize = covered(0.0);
+    let off_centre: usize = covered(0.8);
+    assert!(centre > 0);
+    assert!(off_centre > 0 && off_centre < centre / 2, "{} {}", centre, off_centre);
+    assert_eq!(covered(1.5), 0);
+}
+
+#[test]
+fn slices_across_other_axes_still_span_a_3d_view()
+{
+    // The default camera looks down z, straight along the normal of this slice
+    let scene: Scene = parse_scene("object sphere\n    position 0 0 0 0\n    radius 1\nend\ncamera\n    offset 0 0 4 0\nend\n", 1.0).unwrap();
+    let mut scene_4d: Box<SubScene4> = scene.scene_4d.unwrap();
+    let slice: Slice = Slice::new(Float4::new(0.0, 0.0, 1.0, 0.0), 0.0);
+    scene_4d.slice = Some(slice);
+
+    let (origin, centre): (Float4, Float4) = scene_4d.ray(Float2::new(0.5, 0.5));
+    let (_, right): (Float4, Float4) = scene_4d.ray(Float2::new(1.0, 0.5));
+    let (_, up): (Float4, Float4) = scene_4d.ray(Float2::new(0.5, 1.0));
+    assert!(approx_equal(slice.signed_distance(origin), 0.0));
+    for direction in [centre, right, up]
+    {
+        assert!(approx_equal(Float4::dot(slice.normal, direction), 0.0));
+    }
+    // The rays turn left and right, up and down, and look forwards, three separate directions
+    let forward: Float4 = centre.normalized();
+    let sideways: Float4 = right - forward * Float4::dot(forward, right);
+    let vertical: Float4 = up - forward * Float4::dot(forward, up);
+    assert!(centre.length() > 0.5 && sideways.length() > 0.1 && vertical.length() > 0.1);
+    assert!(approx_equal(Float4::dot(sideways.normalized(), vertical.normalized()), 0.0));
+
+    // The camera looks at the hypersphere from within the hyperplane, its cross section is a sphere
+    let hit = crate::raymarching::raymarch(&origin, &centre, scene_4d.as_ref(), &crate::raymarching::RaymarchSettings::DEFAULT);
+    assert!(hit.hit);
+    assert!(approx_equal(hit.distance * centre.length(), 3.0));
+}
+
+#[test]
+fn camera_turn_keeps_slices_across_the_camera_w()
+{
+    let rotation: Rotor4 = Rotor4::bivector_angle(&Bivector4::new(0.0, 0.0, 0.0, 1.0, 0.0, 0.0), 0.6)
+        * Rotor4::bivector_angle(&Bivector4::new(1.0, 0.0, 0.0, 0.0, 0.0, 0.0), 0.9)
+        * Rotor4::bivector_angle(&Bivector4::new(0.0, 0.0, 0.0, 0.0, 0.0, 1.0), 0.4);
+    let camera: Camera4 = Camera4::new(Float4::new(0.0, 0.0, 0.0, 0.0), Float4::new(0.0, 0.0, 4.0, 0.0), rotation, 1.0, 70.0, 1.0);
+    let turn: Rotor4 = Slice::from_camera(&camera).camera_turn(&camera);
+    let w: Float4 = turn * Float4::new(0.0, 0.0, 0.0, 1.0);
+    assert!(approx_equal(w.w, 1.0));
+
+    let normal: Float4 = Float4::new(1.0, -1.0, 0.5, 0.2).normalized();
+    let turn: Rotor4 = Slice::new(normal, 0.0).camera_turn(&camera);
+    assert!(((rotation * turn) * Float4::new(0.0, 0.0, 0.0, 1.0) - normal).length() < 1e-5);
+}
+
@@ -0,0 +1,109 @@
+//! Rigid transforms with uniform scale for placing objects in a scene.
+//!
+//! A transform maps a point from an object's local space into the space of its parent. Distance
+//! functions are evaluated by mapping the sample point the other way with `to_local`, so the
+//! object itself never has to move.
+
+use crate::mathematics::float3::Float3;
+use crate::mathematics::float3x8::Float3x8;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::float4x8::Float4x8;
+use crate::mathematics::multivectors::{Rotor, VectorPacket};
+use crate::mathematics::rotor3::Rotor3;
+use crate::mathematics::rotor4::Rotor4;
+
+#[derive(Copy, Clone, Debug)]
+pub struct Transform3
+{
+    pub position: Float3,
+    pub rotation: Rotor3,
+    pub scale: f32,
+}
+
+impl Transform3
+{
+    pub const IDENTITY: Transform3 = Transform3{ position: Float3{ x: 0.0, y: 0.0, z: 0.0 }, rotation: Rotor3::IDENTITY, scale: 1.0 };
+
+    pub fn new(position: Float3, rotation: Rotor3, scale: f32) -> Transform3
+    {
+        return Transform3{ position: position, rotation: rotation, scale: scale };
+    }
+
+    /// Maps a point from the parent's space into local space
+    pub fn to_local(&self, p: Float3) -> Float3
+    {
+        return (Rotor3::reverse(&self.rotation) * (p - self.position)) / self.scale;
+    }
+
+    /// Maps every lane of a packet from the parent's space into local space
+    pub fn to_local_packet(&self, p: Float3x8) -> Float3x8
+    {
+        return (Rotor3::reverse(&self.rotation) * (p - Float3x8::splat(self.position))) / self.scale;
+    }
+
+    /// Maps a point from local space into the parent's space
+    pub fn to_world(&self, p: Float3) -> Float3
+    {
+        return self.rotation * (p * self.scale) + self.position;
+    }
+
+    /// The transform that applies child and then parent
+    pub fn combine(parent: &Transform3, child: &Transform3) -> Transform3
+    {
+        return Transform3{
+            position: parent.to_world(child.position),
+            rotation: parent.rotation * child.rotation,
+            scale: parent.scale * child.scale,
+        };
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Transform4
+{
+    pub position: Float4,
+    pub rotation: Rotor4,
+    pub scale: f32,
+}
+
+impl Transform4
+{
+    pub const IDENTITY: Transform4 = Transform4{ position: Float4{ x: 0.0, y: 0.0, z: 0.0, w: 0.0 }, rotation: Rotor4::IDENTITY, scale: 1.0 };
+
+    pub fn new(position: Float4, rotation: Rotor4, scale: f32) -> Transform4
+    {
+        return Transform4{ position: position, rotation: rotation, scale: scale };
+    }
+
+    /// Maps a point from the parent's space into local space
+    pub fn to_local(&self, p: Float4) -> Float4
+    {
+        return (Rotor4::reverse(&self.rotation) * (p - self.position)) / self.scale;
+    }
+
+    /// Maps every lane of a packet from the parent's space into local space
+    pub fn to_local_packet(&self, p: Float4x8) -> Float4x8
+    {
+        return (Rotor4::reverse(&self.rotation) * (p - Float4x8::splat(self.position))) / self.scale;
+    }
+
+    /// Maps a point from local space into the parent's space
+    pub fn to_world(&self, p: Float4) -> Float4
+    {
+        return self.rotation * (p * self.scale) + self.position;
+    }
+
+    /// The transform that applies child and then parent
+    pub fn combine(parent: &Transform4, child: &Transform4) -> Transform4
+    {
+        return Transform4{
+            position: parent.to_world(child.position),
+            rotation: parent.rotation * child.rotation,
+            scale: parent.scale * child.scale,
+        };
+    }
+}
+
+#[cfg(test)]
+#[path = "transform_tests.rs"]
+mod tests;
@@ -0,0 +1,47 @@
+use crate::mathematics::{PI, approx_equal4};
+use crate::mathematics::bivector4::Bivector4;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::multivectors::Rotor;
+use crate::mathematics::rotor4::Rotor4;
+use crate::transform::Transform4;
+
+fn quarter_turn(plane: Bivector4) -> Rotor4
+{
+    return Rotor4::bivector_angle(&plane, PI / 2.0);
+}
+
+#[test]
+fn to_local_inverts_to_world()
+{
+    let rotation: Rotor4 = quarter_turn(Bivector4::new(0.0, 0.0, 0.0, 1.0, 0.0, 0.0));
+    let transform: Transform4 = Transform4::new(Float4::new(1.0, 2.0, 3.0, 4.0), rotation, 2.0);
+
+    let p: Float4 = Float4::new(0.5, -1.0, 2.0, 0.25);
+    assert!(approx_equal4(transform.to_local(transform.to_world(p)), p));
+    assert!(approx_equal4(transform.to_world(Float4::new(0.0, 0.0, 0.0, 0.0)), transform.position));
+
+    // Scale stretches the local axes before they are rotated into place
+    let x: Float4 = transform.to_world(Float4::new(1.0, 0.0, 0.0, 0.0)) - transform.position;
+    assert!(f32::abs(x.x) < 1e-5 && f32::abs(f32::abs(x.w) - 2.0) < 1e-5);
+}
+
+#[test]
+fn combine_applies_child_then_parent()
+{
+    let parent: Transform4 = Transform4::new(
+        Float4::new(0.0, 0.0, 0.0, 5.0),
+        quarter_turn(Bivector4::new(0.0, 0.0, 1.0, 0.0, 0.0, 0.0)),
+        2.0
+    );
+    let child: Transform4 = Transform4::new(
+        Float4::new(1.0, 0.0, 0.0, 0.0),
+        quarter_turn(Bivector4::new(0.0, 0.0, 0.0, 0.0, 0.0, 1.0)),
+        0.5
+    );
+
+    let world: Transform4 = Transform4::combine(&parent, &child);
+    let p: Float4 = Float4::new(0.3, 0.7, -0.2, 1.0);
+    assert!(approx_equal4(world.to_world(p), parent.to_world(child.to_world(p))));
+    assert!(approx_equal4(world.to_local(world.to_world(p)), p));
+    assert_eq!(world.scale, 1.0);
+}
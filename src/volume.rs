@@ -0,0 +1,157 @@
+//! X-ray views of 4D scenes, showing the 3D shadow of each object rather than its surface.
+//!
+//! Every ray of the view still lies in a 3D hyperplane, but instead of stopping at the first
+//! surface it walks through the scene in even steps. At each step the occupancy of the scene is
+//! integrated along a line across the hyperplane, through w or the camera's hidden axis, giving how
+//! much of the object lies behind and in front of that point in 4D. The integrated density then
+//! emits and absorbs light along the ray, the usual way of rendering a volume, so thick parts of an
+//! object glow and hide what is behind them while its thin outskirts stay see through.
+
+use crate::mathematics::f32x8::{F32x8, LANES};
+use crate::mathematics::float3::Float3;
+use crate::mathematics::float4::Float4;
+use crate::mathematics::float4x8::Float4x8;
+use crate::mathematics::multivectors::{Magnitude, VectorPacket};
+use crate::raymarching::RaymarchSettings;
+use crate::scene::{Material, SubScene4};
+use crate::sdf::Sdf;
+
+/// Transmittance a ray stops at, nothing behind it would show
+const OPAQUE: f32 = 0.005;
+
+/// The line occupancy is integrated along
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum VolumeAxis
+{
+    /// The world's w axis, whichever way the camera faces
+    W,
+    /// The one axis the view can not see along, across the hyperplane the ray lies in
+    #[default]
+    Hidden,
+}
+
+impl VolumeAxis
+{
+    pub const NAMES: &'static str = "w or hidden";
+
+    pub fn from_name(name: &str) -> Option<VolumeAxis>
+    {
+        return match name
+        {
+            "w" => Some(VolumeAxis::W),
+            "hidden" => Some(VolumeAxis::Hidden),
+            _ => None,
+        };
+    }
+
+    pub fn name(&self) -> &'static str
+    {
+        return match self
+        {
+            VolumeAxis::W => "w",
+            VolumeAxis::Hidden => "hidden",
+        };
+    }
+
+    /// Unit direction of the axis, hidden is the normal of the ray's hyperplane
+    pub fn direction(&self, hidden: Float4) -> Float4
+    {
+        return match self
+        {
+            VolumeAxis::W => Float4::new(0.0, 0.0, 0.0, 1.0),
+            VolumeAxis::Hidden => hidden.normalized(),
+        };
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Volume
+{
+    pub axis: VolumeAxis,
+    /// Occupancy is integrated from -extent to extent along the axis
+    pub extent: f32,
+    /// Points along the axis the integral is taken from
+    pub samples: u32,
+    /// Distance between the points a ray takes density from
+    pub step: f32,
+    /// How quickly density hides what is behind it
+    pub absorption: f32,
+    /// Width over which occupancy fades from 1 inside a surface to 0 outside it
+    pub softness: f32,
+}
+
+impl Volume
+{
+    pub const DEFAULT: Volume = Volume{ axis: VolumeAxis::Hidden, extent: 2.0, samples: 16, step: 0.1, absorption: 1.0, softness: 0.05 };
+
+    /// How much of the line through p along axis is inside an object, as a length, and the colour of
+    /// what it passes through
+    pub fn density(&self, p: Float4, axis: Float4, scene_4d: &SubScene4) -> (f32, Float3)
+    {
+        let samples: usize = usize::max(self.samples as usize, 1);
+        let spacing: f32 = 2.0 * self.extent / samples as f32;
+
+        let mut density: f32 = 0.0;
+        let mut colour: Float3 = Float3::new(0.0, 0.0, 0.0);
+        // Points along the axis are evaluated a packet at a time, lanes past the last point are left out
+        for start in (0..samples).step_by(LANES)
+        {
+            let points: [Float4; LANES] = std::array::from_fn(|i| p + axis * (-self.extent + ((start + i) as f32 + 0.5) * spacing));
+            let (distances, object_ids): (F32x8, [Option<usize>; LANES]) = scene_4d.evaluate_packet(Float4x8::from_lanes(points));
+            let occupancy: F32x8 = distances.map(|d| 0.5 - d / self.softness).clamp(0.0, 1.0);
+            for (i, object_id) in object_ids.iter().enumerate().take(samples - start)
+            {
+                let weight: f32 = occupancy.lane(i) * spacing;
+                let material: Material = object_id.map_or(Material::WHITE, |id| scene_4d.objects[id].material);
+                density += weight;
+                colour += material.colour * weight;
+            }
+        }
+        if density == 0.0
+        {
+            return (0.0, colour);
+        }
+        return (density, colour / density);
+    }
+
+    /// Colour of the ray from ro along rd through the density, on a black background
+    pub fn integrate(&self, ro: Float4, rd: Float4, axis: Float4, scene_4d: &SubScene4, settings: &RaymarchSettings) -> Float3
+    {
+        let rd: Float4 = rd.normalized();
+        let mut colour: Float3 = Float3::new(0.0, 0.0, 0.0);
+        let mut transmittance: f32 = 1.0;
+        let mut t: f32 = 0.0;
+
+        while t < settings.max_distance && transmittance > OPAQUE
+        {
+            let p: Float4 = ro + rd * t;
+            // Nothing is within d of p, so the line through p is empty when d is more than its half
+            // length, and stays empty while p moves on by the difference
+            let clearance: f32 = scene_4d.distance(p) - self.extent - self.softness;
+            if clearance > self.step
+            {
+                t += clearance;
+                continue;
+            }
+
+            let (density, emission): (f32, Float3) = self.density(p, axis, scene_4d);
+            let alpha: f32 = 1.0 - f32::exp(-self.absorption * density * self.step);
+            colour += emission * (transmittance * alpha);
+            transmittance *= 1.0 - alpha;
+            t += self.step;
+        }
+        return colour;
+    }
+}
+
+impl Default for Volume
+{
+    fn default() -> Self
+    {
+        return Volume::DEFAULT;
+    }
+}
+
+#[cfg(test)]
+#[path = "volume_tests.rs"]
+mod tests;
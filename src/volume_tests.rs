@@ -0,0 +1,67 @@
+use crate::mathematics::approx_equal;
+use crate::mathematics::float3::Float3;
+use crate::mathematics::float4::Float4;
+use crate::raymarching::RaymarchSettings;
+use crate::scene::{Scene, SubScene4};
+use crate::scene_file::parse_scene;
+use crate::volume::*;
+
+const W: Float4 = Float4{ x: 0.0, y: 0.0, z: 0.0, w: 1.0 };
+
+fn hypersphere() -> Scene
+{
+    return parse_scene("object sphere\n    position 0 0 0 0\n    radius 1\n    colour 1 0.5 0\nend\ncamera\n    offset 0 0 4 0\nend\n", 1.0).unwrap();
+}
+
+#[test]
+fn axes_go_by_name()
+{
+    assert_eq!(VolumeAxis::from_name("hidden"), Some(VolumeAxis::Hidden));
+    assert_eq!(VolumeAxis::from_name(VolumeAxis::W.name()), Some(VolumeAxis::W));
+    assert_eq!(VolumeAxis::from_name("z"), None);
+
+    let hidden: Float4 = Float4::new(0.0, 0.0, 2.0, 0.0);
+    assert_eq!(VolumeAxis::W.direction(hidden), W);
+    assert_eq!(VolumeAxis::Hidden.direction(hidden), Float4::new(0.0, 0.0, 1.0, 0.0));
+}
+
+#[test]
+fn density_is_the_length_inside_along_the_axis()
+{
+    let scene: Scene = hypersphere();
+    let scene_4d: &SubScene4 = scene.scene_4d.as_ref().unwrap();
+    let volume: Volume = Volume{ samples: 64, softness: 0.01, ..Volume::DEFAULT };
+
+    // A unit hypersphere is 2 long through its centre and 2 * sqrt(1 - r^2) at r from it
+    let (centre, colour): (f32, Float3) = volume.density(Float4::new(0.0, 0.0, 0.0, 0.0), W, scene_4d);
+    assert!(f32::abs(centre - 2.0) < 0.05, "Density {} through the centre", centre);
+    assert_eq!(colour, Float3::new(1.0, 0.5, 0.0));
+
+    let (off_centre, _): (f32, Float3) = volume.density(Float4::new(0.6, 0.0, 0.0, 0.0), W, scene_4d);
+    assert!(f32::abs(off_centre - 1.6) < 0.05, "Density {} at 0.6 from the centre", off_centre);
+
+    assert_eq!(volume.density(Float4::new(0.0, 1.5, 0.0, 0.0), W, scene_4d).0, 0.0);
+}
+
+#[test]
+fn thicker_parts_show_brighter()
+{
+    let scene: Scene = hypersphere();
+    let scene_4d: &SubScene4 = scene.scene_4d.as_ref().unwrap();
+    let settings: RaymarchSettings = RaymarchSettings::DEFAULT;
+    let volume: Volume = Volume{ absorption: 0.5, ..Volume::DEFAULT };
+    let ro: Float4 = Float4::new(0.0, 0.0, 4.0, 0.0);
+
+    let through_centre: Float3 = volume.integrate(ro, Float4::new(0.0, 0.0, -1.0, 0.0), W, scene_4d, &settings);
+    let near_edge: Float3 = volume.integrate(ro, Float4::new(0.0, 0.85, -4.0, 0.0), W, scene_4d, &settings);
+    let missed: Float3 = volume.integrate(ro, Float4::new(0.0, 1.0, -1.0, 0.0), W, scene_4d, &settings);
+    assert!(through_centre.x > near_edge.x && near_edge.x > 0.0);
+    assert_eq!(missed, Float3::new(0.0, 0.0, 0.0));
+    // The light takes the colour of the object, and never more than it emits
+    assert!(through_centre.x <= 1.0 && approx_equal(through_centre.y / through_centre.x, 0.5));
+    assert_eq!(through_centre.z, 0.0);
+
+    // Absorbing more hides more of the far side, which gets closer to fully lit
+    let opaque: Float3 = Volume{ absorption: 10.0, ..volume }.integrate(ro, Float4::new(0.0, 0.0, -1.0, 0.0), W, scene_4d, &settings);
+    assert!(opaque.x > through_centre.x && opaque.x > 0.99);
+}
@@ -0,0 +1,192 @@
+//! Shading that shows where along w the surfaces of a 4D scene are.
+//!
+//! Plain diffuse shading looks the same whatever the w of a surface, so a tint can replace the
+//! material colour with a colour ramp of either the hit's w coordinate or its distance from the
+//! hyperplane the view lies in. Ghosting marches extra layers of rays on hyperplanes parallel to the
+//! view's, and blends what they hit faintly over the image, showing geometry just off the slice.
+
+use crate::mathematics::float3::Float3;
+use crate::mathematics::float4::Float4;
+use crate::render_mode;
+use crate::slice::Slice;
+
+/// Colours a value from 0 to 1 is mapped to
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum ColourRamp
+{
+    /// Blue through white to red, the middle of the range stands out as white
+    #[default]
+    CoolWarm,
+    /// Blue through cyan, green and yellow to red, as the steps debug view
+    Heat,
+    /// Dark purple through teal to yellow, evenly bright all the way
+    Viridis,
+    /// Black to white
+    Grey,
+}
+
+impl ColourRamp
+{
+    pub const NAMES: &'static str = "coolwarm, heat, viridis or grey";
+
+    pub const ALL: [ColourRamp; 4] = [ColourRamp::CoolWarm, ColourRamp::Heat, ColourRamp::Viridis, ColourRamp::Grey];
+
+    pub fn from_name(name: &str) -> Option<ColourRamp>
+    {
+        return ColourRamp::ALL.into_iter().find(|ramp| ramp.name() == name);
+    }
+
+    pub fn name(&self) -> &'static str
+    {
+        return match self
+        {
+            ColourRamp::CoolWarm => "coolwarm",
+            ColourRamp::Heat => "heat",
+            ColourRamp::Viridis => "viridis",
+            ColourRamp::Grey => "grey",
+        };
+    }
+
+    /// The colour at t, which is clamped between 0 and 1
+    pub fn sample(&self, t: f32) -> Float3
+    {
+        let t: f32 = f32::clamp(t, 0.0, 1.0);
+        return match self
+        {
+            ColourRamp::CoolWarm => interpolate(&[Float3::new(0.23, 0.30, 0.75), Float3::new(0.87, 0.87, 0.87), Float3::new(0.71, 0.02, 0.15)], t),
+            ColourRamp::Heat => render_mode::heatmap(t),
+            ColourRamp::Viridis => interpolate(&[
+                Float3::new(0.27, 0.00, 0.33),
+                Float3::new(0.23, 0.32, 0.55),
+                Float3::new(0.13, 0.57, 0.55),
+                Float3::new(0.37, 0.79, 0.38),
+                Float3::new(0.99, 0.91, 0.14),
+            ], t),
+            ColourRamp::Grey => Float3::new(t, t, t),
+        };
+    }
+}
+
+/// Linear interpolation between evenly spaced colours
+fn interpolate(stops: &[Float3], t: f32) -> Float3
+{
+    let position: f32 = t * (stops.len() - 1) as f32;
+    let index: usize = usize::min(position as usize, stops.len() - 2);
+    let fraction: f32 = position - index as f32;
+    return stops[index] + (stops[index + 1] - stops[index]) * fraction;
+}
+
+/// What a tint measures of each hit
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum WMeasure
+{
+    /// The hit's w coordinate
+    #[default]
+    W,
+    /// Signed distance of the hit from the hyperplane the view lies in. Every ray stays in that
+    /// hyperplane so only ghosts are off it
+    Slice,
+}
+
+impl WMeasure
+{
+    pub const NAMES: &'static str = "w or slice";
+
+    pub fn from_name(name: &str) -> Option<WMeasure>
+    {
+        return match name
+        {
+            "w" => Some(WMeasure::W),
+            "slice" => Some(WMeasure::Slice),
+            _ => None,
+        };
+    }
+
+    pub fn name(&self) -> &'static str
+    {
+        return match self
+        {
+            WMeasure::W => "w",
+            WMeasure::Slice => "slice",
+        };
+    }
+}
+
+/// Colours hits by a colour ramp of how far along w they are, in place of their material colour
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct WTint
+{
+    pub measure: WMeasure,
+    pub ramp: ColourRamp,
+    /// Values from -range to range cover the whole ramp, 0 is its middle
+    pub range: f32,
+}
+
+impl WTint
+{
+    pub const DEFAULT: WTint = WTint{ measure: WMeasure::W, ramp: ColourRamp::CoolWarm, range: 1.0 };
+
+    /// The colour of a hit at p, hyperplane is the one the view lies in
+    pub fn colour(&self, p: Float4, hyperplane: &Slice) -> Float3
+    {
+        let value: f32 = match self.measure
+        {
+            WMeasure::W => p.w,
+            WMeasure::Slice => hyperplane.signed_distance(p),
+        };
+        return self.ramp.sample(0.5 + value / (2.0 * self.range));
+    }
+}
+
+impl Default for WTint
+{
+    fn default() -> Self
+    {
+        return WTint::DEFAULT;
+    }
+}
+
+/// Faint layers of the geometry on either side of the view's hyperplane
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Ghosting
+{
+    /// Hyperplanes marched on each side of the view's
+    pub layers: u32,
+    /// Distance between neighbouring layers along the normal
+    pub spacing: f32,
+    /// Opacity a layer right on the view's hyperplane would have, layers further off fade out
+    pub opacity: f32,
+}
+
+impl Ghosting
+{
+    pub const DEFAULT: Ghosting = Ghosting{ layers: 2, spacing: 0.1, opacity: 0.5 };
+
+    /// Offsets of the layers along the normal, the furthest first so nearer ones are blended over them
+    pub fn offsets(&self) -> Vec<f32>
+    {
+        return (1..=self.layers).rev()
+            .flat_map(|layer| [-(layer as f32), layer as f32])
+            .map(|layer| layer * self.spacing)
+            .collect();
+    }
+
+    /// Opacity of the layer at an offset, fading to nothing one spacing past the last layer
+    pub fn layer_opacity(&self, offset: f32) -> f32
+    {
+        let range: f32 = (self.layers + 1) as f32 * self.spacing;
+        return self.opacity * f32::max(1.0 - f32::abs(offset) / range, 0.0);
+    }
+}
+
+impl Default for Ghosting
+{
+    fn default() -> Self
+    {
+        return Ghosting::DEFAULT;
+    }
+}
+
+#[cfg(test)]
+#[path = "w_shading_tests.rs"]
+mod tests;
@@ -0,0 +1,96 @@
+use std::sync::{Arc, RwLock};
+
+use crate::application::Application;
+use crate::framebuffer::Framebuffer;
+use crate::mathematics::float3::Float3;
+use crate::mathematics::float4::Float4;
+use crate::renderer;
+use crate::scene::{Scene, SubScene4};
+use crate::scene_file::parse_scene;
+use crate::slice::Slice;
+use crate::w_shading::*;
+use crate::worker_pool::WorkerPool;
+
+#[test]
+fn ramps_go_by_name_and_clamp()
+{
+    for ramp in ColourRamp::ALL
+    {
+        assert_eq!(ColourRamp::from_name(ramp.name()), Some(ramp));
+        assert_eq!(ramp.sample(-1.0), ramp.sample(0.0));
+        assert_eq!(ramp.sample(2.0), ramp.sample(1.0));
+        assert!(ramp.sample(0.0) != ramp.sample(1.0));
+    }
+    assert_eq!(ColourRamp::from_name("rainbow"), None);
+    assert_eq!(ColourRamp::Grey.sample(0.25), Float3::new(0.25, 0.25, 0.25));
+    // The middle of the diverging ramp is its light grey stop
+    assert_eq!(ColourRamp::CoolWarm.sample(0.5), Float3::new(0.87, 0.87, 0.87));
+}
+
+#[test]
+fn tints_measure_w_or_the_distance_from_the_slice()
+{
+    let hyperplane: Slice = Slice::new(Float4::new(0.0, 0.0, 0.0, 1.0), 0.5);
+    let p: Float4 = Float4::new(3.0, 2.0, 1.0, 0.5);
+    let by_w: WTint = WTint{ measure: WMeasure::W, ramp: ColourRamp::Grey, range: 1.0 };
+    assert_eq!(by_w.colour(p, &hyperplane), Float3::new(0.75, 0.75, 0.75));
+
+    let by_slice: WTint = WTint{ measure: WMeasure::Slice, ..by_w };
+    assert_eq!(by_slice.colour(p, &hyperplane), Float3::new(0.5, 0.5, 0.5));
+    assert_eq!(by_slice.colour(Float4::new(0.0, 0.0, 0.0, -2.0), &hyperplane), Float3::new(0.0, 0.0, 0.0));
+
+    assert_eq!(WMeasure::from_name("slice"), Some(WMeasure::Slice));
+    assert_eq!(WMeasure::from_name("z"), None);
+}
+
+#[test]
+fn ghost_layers_fade_with_distance()
+{
+    let ghosting: Ghosting = Ghosting{ layers: 2, spacing: 0.1, opacity: 0.6 };
+    assert_eq!(ghosting.offsets(), vec![-0.2, 0.2, -0.1, 0.1]);
+    assert!(ghosting.layer_opacity(0.1) > ghosting.layer_opacity(-0.2));
+    assert!(ghosting.layer_opacity(0.0) == ghosting.opacity);
+    assert_eq!(ghosting.layer_opacity(0.3), 0.0);
+    assert!(Ghosting{ layers: 0, ..ghosting }.offsets().is_empty());
+}
+
+#[test]
+fn ghosts_show_geometry_just_off_the_slice()
+{
+    // Near the edge of a hypersphere the cross section is small, and the layers towards its centre larger
+    let scene: Scene = parse_scene("object sphere\n    position 0 0 0 0\n    radius 1\nend\ncamera\n    offset 0 0 3 0\nend\n", 1.0).unwrap();
+    let scene: Arc<RwLock<Scene>> = Arc::new(RwLock::new(scene));
+    let application: Application = Application::new(1.0, 48, false);
+    let pool: WorkerPool = WorkerPool::new(2);
+
+    let render = |ghosting: Option<Ghosting>, w_tint: Option<WTint>| -> Vec<Float3> {
+        {
+            let mut locked = scene.write().unwrap();
+            let scene_4d: &mut SubScene4 = locked.scene_4d.as_mut().unwrap();
+            scene_4d.slice = Some(Slice::new(Float4::new(0.0, 0.0, 0.0, 1.0), 0.9));
+            scene_4d.ghosting = ghosting;
+            scene_4d.w_tint = w_tint;
+        }
+        let framebuffer: Framebuffer = renderer::render_image(&pool, &scene, &application);
+        return framebuffer.colours();
+    };
+    let covered = |colours: &Vec<Float3>| -> usize {
+        return colours.iter().filter(|colour| **colour != Float3::new(0.0, 0.0, 0.0)).count();
+    };
+
+    let plain: Vec<Float3> = render(None, None);
+    let ghosted: Vec<Float3> = render(Some(Ghosting::DEFAULT), None);
+    assert!(covered(&ghosted) > covered(&plain));
+    // Ghosts are only faint over the black background
+    for (ghost, background) in ghosted.iter().zip(&plain).filter(|(_, background)| **background == Float3::new(0.0, 0.0, 0.0))
+    {
+        assert!(ghost.x <= Ghosting::DEFAULT.opacity, "Ghost {:?} over {:?} is too bright", ghost, background);
+    }
+
+    // Tinting by w colours the whole cross section the same, as it all has the slice's w
+    let tint: WTint = WTint{ measure: WMeasure::W, ramp: ColourRamp::Heat, range: 1.0 };
+    let tinted: Vec<Float3> = render(None, Some(tint));
+    assert_eq!(covered(&tinted), covered(&plain));
+    let colour: Float3 = tinted[24 * 48 + 24];
+    assert!(colour.x > colour.z && colour.y > colour.z);
+}
@@ -0,0 +1,547 @@
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+use std::sync::{Arc, Mutex, RwLock};
+
+extern crate sdl2;
+use sdl2::event::Event;
+use sdl2::keyboard::{Keycode, Mod};
+use sdl2::pixels::{Color, PixelFormatEnum};
+use sdl2::render::{Texture, WindowCanvas};
+use sdl2::rect::Rect;
+use sdl2::surface::Surface;
+use sdl2::ttf::Font;
+use sdl2::image::LoadSurface;
+
+use raymarching::application::Application;
+use raymarching::camera::Camera;
+use raymarching::camera_controller::{CameraController, CameraMode, MoveAxis, RotationPlane};
+use raymarching::framebuffer::Framebuffer;
+use raymarching::mathematics::bivector3::Bivector3;
+use raymarching::mathematics::bivector4::Bivector4;
+use raymarching::mathematics::float3::Float3;
+use raymarching::mathematics::float4::Float4;
+use raymarching::mathematics::multivectors::{Magnitude, Rotor};
+use raymarching::mathematics::rotor3::Rotor3;
+use raymarching::mathematics::rotor4::Rotor4;
+use raymarching::multi_view;
+use raymarching::progressive::ProgressiveRenderer;
+use raymarching::render_mode::RenderMode;
+use raymarching::renderer;
+use raymarching::resolution::DynamicResolution;
+use raymarching::scene::{Scene, SubScene3, SubScene4};
+use raymarching::slice::Slice;
+use raymarching::volume::Volume;
+use raymarching::w_shading::{Ghosting, WMeasure, WTint};
+use raymarching::worker_pool::WorkerPool;
+
+fn render(canvas: &mut WindowCanvas, texture: &mut Texture, pool: &WorkerPool, scene: &Arc<RwLock<Scene>>, application: &Application, framebuffer: &Arc<Framebuffer>, progressive: Option<&mut ProgressiveRenderer>) -> Result<(), String>
+{
+    // Progressive rendering refines the last frame instead of starting over, once it is finished
+    // the same image is shown again
+    match progressive
+    {
+        Some(progressive) => { progressive.render_next_pass(pool, scene, application, framebuffer); }
+        None => renderer::render_frame(pool, scene, application, framebuffer),
+    }
+
+    // Upload the whole frame at once rather than drawing each pixel. Frames rendered smaller than
+    // the window only fill the top left of the framebuffer, and that area is stretched over the window
+    let area: Rect = Rect::new(0, 0, application.width, application.height);
+    texture.with_lock(Some(area), |buffer: &mut [u8], pitch: usize| framebuffer.write_rgba8_area(buffer, pitch, application.width, application.height))?;
+    canvas.copy(texture, Some(area), None)?;
+
+    Ok(())
+}
+
+/// Moves the scene on by a frame, returns whether anything changed
+fn update(delta_time: f64, scene: &mut Scene, controller: &CameraController) -> Result<bool, String>
+{
+    if scene.is4d
+    {
+        let scene_4d: &mut Box<SubScene4> = scene.scene_4d.as_mut().expect("Error: scene_4d not set scene.is4d is true"); 
+        // Scenes with spinning objects keep the camera still
+        if scene_4d.objects.iter().any(|object| object.spin.length() > 0.0)
+        {
+            scene_4d.spin_objects(delta_time as f32);
+            return Ok(true);
+        }
+        if !controller.auto_rotate || controller.mode == CameraMode::FreeFly
+        {
+            return Ok(false);
+        }
+    }
+    else 
+    {
+        let scene_3d: &mut Box<SubScene3> = scene.scene_3d.as_mut().expect("Error: scene_3d not set scene.is4d is false"); 
+        // Scenes with spinning objects keep the camera still
+        if scene_3d.objects.iter().any(|object| object.spin.length() > 0.0)
+        {
+            scene_3d.spin_objects(delta_time as f32);
+            return Ok(true);
+        }
+        if !controller.auto_rotate || controller.mode == CameraMode::FreeFly
+        {
+            return Ok(false);
+        }
+    }
+
+    // A cross section stays where it is in the world, so the camera only orbits within it rather
+    // than turning away from it towards w
+    let has_slice: bool = scene.scene_4d.as_ref().is_some_and(|scene_4d| scene_4d.slice.is_some());
+    let w: f32 = if has_slice { 0.0 } else { 1.0 };
+    let r4: Rotor4 = Rotor4::bivector_angle(&Bivector4::new(1.0, 1.0, 1.0, w, w, w), delta_time as f32);
+    let r3: Rotor3 = Rotor3::bivector_angle(&Bivector3::new(1.0, 1.0, 1.0), delta_time as f32);
+    turn_camera(scene, controller.mode, r4, r3);
+    Ok(true)
+}
+
+/// Turns the camera with whichever rotor fits the scene. The orbit camera turns about its pivot
+/// and the lights turn with it, the free flying camera turns in place in a still world
+fn turn_camera(scene: &mut Scene, mode: CameraMode, r4: Rotor4, r3: Rotor3)
+{
+    if scene.is4d
+    {
+        let scene_4d: &mut Box<SubScene4> = scene.scene_4d.as_mut().expect("Error: scene_4d not set scene.is4d is true"); 
+        if mode == CameraMode::FreeFly
+        {
+            scene_4d.cameras_mut().for_each(|camera| camera.turn_in_place(r4));
+            return;
+        }
+        scene_4d.cameras_mut().for_each(|camera| camera.rotate_camera(r4));
+        for light_source in scene_4d.light_sources.iter_mut()
+        {
+            *light_source = r4 * *light_source;
+        }
+    }
+    else 
+    {
+        let scene_3d: &mut Box<SubScene3> = scene.scene_3d.as_mut().expect("Error: scene_3d not set scene.is4d is false"); 
+        if mode == CameraMode::FreeFly
+        {
+            scene_3d.camera.turn_in_place(r3);
+            return;
+        }
+        scene_3d.camera.rotate_camera(r3);
+        for light_source in scene_3d.light_sources.iter_mut()
+        {
+            *light_source = r3 * *light_source;
+        }
+    }
+}
+
+/// Moves the free flying camera along the axes held, returns whether it moved
+fn fly_camera(scene: &mut Scene, controller: &CameraController, delta_time: f32) -> bool
+{
+    if scene.is4d
+    {
+        let scene_4d: &mut Box<SubScene4> = scene.scene_4d.as_mut().expect("Error: scene_4d not set scene.is4d is true"); 
+        let Some(offset) = controller.movement4(scene_4d.camera.get_camera_rotation(), delta_time) else {
+            return false;
+        };
+        scene_4d.cameras_mut().for_each(|camera| camera.move_camera(offset));
+    }
+    else 
+    {
+        let scene_3d: &mut Box<SubScene3> = scene.scene_3d.as_mut().expect("Error: scene_3d not set scene.is4d is false"); 
+        let Some(offset) = controller.movement3(scene_3d.camera.get_camera_rotation(), delta_time) else {
+            return false;
+        };
+        scene_3d.camera.move_camera(offset);
+    }
+    return true;
+}
+
+/// Moves the camera towards its pivot by scroll steps, or away for negative steps
+fn zoom_camera(scene: &mut Scene, controller: &CameraController, steps: f32)
+{
+    if scene.is4d
+    {
+        let scene_4d: &mut Box<SubScene4> = scene.scene_4d.as_mut().expect("Error: scene_4d not set scene.is4d is true"); 
+        for camera in scene_4d.cameras_mut()
+        {
+            let offset_position: Float4 = camera.get_offset_position();
+            camera.set_offset_position(controller.zoom(offset_position, steps));
+        }
+    }
+    else 
+    {
+        let scene_3d: &mut Box<SubScene3> = scene.scene_3d.as_mut().expect("Error: scene_3d not set scene.is4d is false"); 
+        let offset_position: Float3 = scene_3d.camera.get_offset_position();
+        scene_3d.camera.set_offset_position(controller.zoom(offset_position, steps));
+    }
+}
+
+/// The plane a key turns the camera in and which way, shift moves the keys onto the planes with w
+fn key_plane(keycode: Keycode, ana_kata: bool) -> Option<(RotationPlane, f32)>
+{
+    let (plane, plane_w, direction): (RotationPlane, RotationPlane, f32) = match keycode
+    {
+        Keycode::Left => (RotationPlane::XZ, RotationPlane::XW, -1.0),
+        Keycode::Right => (RotationPlane::XZ, RotationPlane::XW, 1.0),
+        Keycode::Down => (RotationPlane::YZ, RotationPlane::YW, -1.0),
+        Keycode::Up => (RotationPlane::YZ, RotationPlane::YW, 1.0),
+        Keycode::Q => (RotationPlane::XY, RotationPlane::ZW, -1.0),
+        Keycode::E => (RotationPlane::XY, RotationPlane::ZW, 1.0),
+        _ => return None,
+    };
+    return Some(if ana_kata { (plane_w, direction) } else { (plane, direction) });
+}
+
+/// Cuts a 4D scene to the cross section across the camera's w axis, or goes back to the full
+/// projection, returns whether the view changed
+fn toggle_slice(scene: &mut Scene) -> bool
+{
+    let Some(scene_4d) = scene.scene_4d.as_mut().filter(|_| scene.is4d) else {
+        return false;
+    };
+    scene_4d.slice = if scene_4d.slice.is_some() { None } else { Some(Slice::from_camera(&scene_4d.camera)) };
+    return true;
+}
+
+/// Splits a 4D scene into its XYZ, XYW, XZW and YZW cross sections, or goes back to the single
+/// view, returns whether the view changed
+fn toggle_views(scene: &mut Scene) -> bool
+{
+    let Some(scene_4d) = scene.scene_4d.as_mut().filter(|_| scene.is4d) else {
+        return false;
+    };
+    scene_4d.views = if scene_4d.views.is_empty() { multi_view::turned_views(&scene_4d.camera, &multi_view::axis_slice_turns()) } else { Vec::new() };
+    return true;
+}
+
+/// Moves a 4D scene's tint on from none to tinting by w and then by distance from the slice,
+/// returns whether the view changed
+fn cycle_w_tint(scene: &mut Scene) -> bool
+{
+    let Some(scene_4d) = scene.scene_4d.as_mut().filter(|_| scene.is4d) else {
+        return false;
+    };
+    scene_4d.w_tint = match scene_4d.w_tint
+    {
+        None => Some(WTint::DEFAULT),
+        Some(tint) if tint.measure == WMeasure::W => Some(WTint{ measure: WMeasure::Slice, ..tint }),
+        Some(_) => None,
+    };
+    return true;
+}
+
+/// Shows or hides the ghosts of a 4D scene's geometry just off the slice, returns whether the view changed
+fn toggle_ghosting(scene: &mut Scene) -> bool
+{
+    let Some(scene_4d) = scene.scene_4d.as_mut().filter(|_| scene.is4d) else {
+        return false;
+    };
+    scene_4d.ghosting = if scene_4d.ghosting.is_some() { None } else { Some(Ghosting::DEFAULT) };
+    return true;
+}
+
+/// Switches a 4D scene between its surfaces and the X-ray view of its volume, returns whether the view changed
+fn toggle_volume(scene: &mut Scene) -> bool
+{
+    let Some(scene_4d) = scene.scene_4d.as_mut().filter(|_| scene.is4d) else {
+        return false;
+    };
+    scene_4d.volume = if scene_4d.volume.is_some() { None } else { Some(Volume::DEFAULT) };
+    return true;
+}
+
+/// Handles the cross section keys of 4D scenes, returns whether the view changed. Page up and page
+/// down slide the slice along its normal, and J and L, I and K, and U and O turn it in the XW, YW
+/// and ZW planes about the camera
+fn slice_key(scene: &mut Scene, keycode: Keycode) -> bool
+{
+    let Some(scene_4d) = scene.scene_4d.as_mut().filter(|_| scene.is4d) else {
+        return false;
+    };
+    let camera_position: Float4 = scene_4d.camera.get_camera_position();
+    let Some(slice) = scene_4d.slice.as_mut() else {
+        return false;
+    };
+    let (plane, direction): (RotationPlane, f32) = match keycode
+    {
+        Keycode::PageUp => { slice.slide(SLICE_SLIDE_STEP); return true; }
+        Keycode::PageDown => { slice.slide(-SLICE_SLIDE_STEP); return true; }
+        Keycode::L => (RotationPlane::XW, 1.0),
+        Keycode::J => (RotationPlane::XW, -1.0),
+        Keycode::I => (RotationPlane::YW, 1.0),
+        Keycode::K => (RotationPlane::YW, -1.0),
+        Keycode::O => (RotationPlane::ZW, 1.0),
+        Keycode::U => (RotationPlane::ZW, -1.0),
+        _ => return false,
+    };
+    slice.rotate_about(Rotor4::bivector_angle(&plane.bivector4(), direction * SLICE_TURN_STEP), camera_position);
+    return true;
+}
+
+/// The axis a key moves the free flying camera along and which way
+fn key_axis(keycode: Keycode) -> Option<(MoveAxis, f32)>
+{
+    return match keycode
+    {
+        Keycode::W => Some((MoveAxis::Forward, 1.0)),
+        Keycode::S => Some((MoveAxis::Forward, -1.0)),
+        Keycode::D => Some((MoveAxis::Right, 1.0)),
+        Keycode::A => Some((MoveAxis::Right, -1.0)),
+        Keycode::X => Some((MoveAxis::Up, 1.0)),
+        Keycode::Z => Some((MoveAxis::Up, -1.0)),
+        Keycode::T => Some((MoveAxis::Ana, 1.0)),
+        Keycode::G => Some((MoveAxis::Ana, -1.0)),
+        _ => None,
+    };
+}
+
+fn is_shift(keymod: Mod) -> bool
+{
+    return keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD);
+}
+
+struct Time
+{
+    pub application_up_time: f64,
+    pub frame_delta_time: f64,
+}
+
+/// How far a cross section slides and how many radians it turns for each key press or repeat
+const SLICE_SLIDE_STEP: f32 = 0.05;
+const SLICE_TURN_STEP: f32 = 0.05;
+
+static APPLICATION_TIME: Mutex<Time> = Mutex::new(Time{application_up_time: 0.0, frame_delta_time: 0.0});
+
+pub struct WindowOptions
+{
+    /// Frame rate the render size changes to hold
+    pub target_fps: Option<f32>,
+    /// Refine still views progressively up to this many samples per pixel
+    pub progressive_samples: Option<u32>,
+}
+
+/// Opens the window and renders until it is closed, space pauses and resumes the animation and M
+/// cycles through the debug views. Dragging, the arrow keys and Q and E turn the camera, see `key_plane`,
+/// the scroll wheel zooms and R turns the automatic rotation on and off. F switches to the free flying
+/// camera, which moves with the keys in `key_axis`. H cuts 4D scenes to a cross section, see `slice_key`,
+/// and V splits them into the four axis cross sections. C cycles the w tints and B shows ghosts of
+/// the geometry just off the slice, and P switches to the X-ray view of the volume
+pub fn run(scene: Arc<RwLock<Scene>>, application: Application, pool: WorkerPool, options: WindowOptions) -> Result<(), String>
+{
+    // Create Window
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+
+    let mut window = video_subsystem.window("4D Raymarching", application.width, application.height)
+        .position_centered()
+        .build()
+        .unwrap();
+
+    window.set_resizable(false);
+
+    match Surface::from_file("assets/ClientIcon.ico")
+    {
+        Err(msg) => { print!("Error: Unable to create Surface from specified file. {}", msg) }
+        Ok(window_icon) => { window.set_icon(window_icon) }
+    }
+
+    let mut canvas = window.into_canvas().build().unwrap();
+    let texture_creator = canvas.texture_creator();
+
+    // The frame is rendered into the framebuffer and streamed to this texture, smoothly scaled when
+    // the render size is smaller than the window
+    sdl2::hint::set("SDL_RENDER_SCALE_QUALITY", "linear");
+    let mut frame_texture: Texture = texture_creator.create_texture_streaming(PixelFormatEnum::RGBA32, application.width, application.height)
+        .map_err(|e| e.to_string())?;
+    let framebuffer: Arc<Framebuffer> = Arc::new(Framebuffer::new(application.width, application.height));
+
+    // Prepare Fonts
+    let ttf_context = sdl2::ttf::init().unwrap();
+    let font_path: &Path = Path::new(&"assets/CascadiaCode.ttf");
+    let font: Font = ttf_context.load_font(font_path, 12)?;
+
+    let mut event_pump = sdl_context.event_pump()?;
+    
+    let mut fps_text: String;
+    let mut fps_text_surface: Surface;
+    let mut fps_text_texture: Texture;
+    let mut fps_text_rect: Rect;
+
+    let mut resolution: Option<DynamicResolution> = options.target_fps.map(DynamicResolution::new);
+    let mut progressive: Option<ProgressiveRenderer> = options.progressive_samples.map(ProgressiveRenderer::new);
+    let mut paused: bool = false;
+    let mut controller: CameraController = CameraController::new();
+
+    let applciation_start_time: Instant = Instant::now();
+    'running: loop {
+        
+        let frame_start_time: Instant = Instant::now();
+        
+        // Handle Events
+        let mut view_changed: bool = false;
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit {..} => break 'running,
+                Event::KeyDown { keycode: Some(Keycode::Space), repeat: false, .. } => paused = !paused,
+                Event::KeyDown { keycode: Some(Keycode::R), repeat: false, .. } => controller.auto_rotate = !controller.auto_rotate,
+                Event::KeyDown { keycode: Some(Keycode::M), repeat: false, .. } =>
+                {
+                    let mut scene = scene.write().expect("Scene lock was poisoned");
+                    scene.render_mode = scene.render_mode.next();
+                    view_changed = true;
+                }
+                Event::KeyDown { keycode: Some(Keycode::F), repeat: false, .. } => controller.toggle_mode(),
+                Event::KeyDown { keycode: Some(Keycode::H), repeat: false, .. } => view_changed |= toggle_slice(&mut scene.write().expect("Scene lock was poisoned")),
+                Event::KeyDown { keycode: Some(Keycode::V), repeat: false, .. } => view_changed |= toggle_views(&mut scene.write().expect("Scene lock was poisoned")),
+                Event::KeyDown { keycode: Some(Keycode::C), repeat: false, .. } => view_changed |= cycle_w_tint(&mut scene.write().expect("Scene lock was poisoned")),
+                Event::KeyDown { keycode: Some(Keycode::B), repeat: false, .. } => view_changed |= toggle_ghosting(&mut scene.write().expect("Scene lock was poisoned")),
+                Event::KeyDown { keycode: Some(Keycode::P), repeat: false, .. } => view_changed |= toggle_volume(&mut scene.write().expect("Scene lock was poisoned")),
+                Event::KeyDown { keycode: Some(keycode), keymod, repeat, .. } =>
+                {
+                    // Sliding and turning the cross section repeat while the key is held
+                    if slice_key(&mut scene.write().expect("Scene lock was poisoned"), keycode)
+                    {
+                        view_changed = true;
+                    }
+                    else if !repeat
+                    {
+                        if let Some((plane, direction)) = key_plane(keycode, is_shift(keymod))
+                        {
+                            controller.hold(plane, direction);
+                        }
+                        if let Some((axis, direction)) = key_axis(keycode)
+                        {
+                            controller.hold_move(axis, direction);
+                        }
+                    }
+                }
+                Event::KeyUp { keycode: Some(keycode), .. } =>
+                {
+                    // Shift may have changed since the key went down, so release both of its planes
+                    for ana_kata in [false, true]
+                    {
+                        if let Some((plane, _)) = key_plane(keycode, ana_kata)
+                        {
+                            controller.hold(plane, 0.0);
+                        }
+                    }
+                    if let Some((axis, _)) = key_axis(keycode)
+                    {
+                        controller.hold_move(axis, 0.0);
+                    }
+                }
+                Event::MouseMotion { mousestate, xrel, yrel, .. } if mousestate.left() || mousestate.right() =>
+                {
+                    // Right dragging, or dragging with shift held, turns towards w
+                    let ana_kata: bool = mousestate.right() || is_shift(sdl_context.keyboard().mod_state());
+                    let r4: Rotor4 = controller.drag_rotation4(xrel as f32, yrel as f32, ana_kata);
+                    let r3: Rotor3 = controller.drag_rotation3(xrel as f32, yrel as f32);
+                    turn_camera(&mut scene.write().expect("Scene lock was poisoned"), controller.mode, r4, r3);
+                    view_changed = true;
+                }
+                // Scrolling zooms the orbit camera and changes the speed of the free flying camera
+                Event::MouseWheel { y, .. } if y != 0 && controller.mode == CameraMode::FreeFly => controller.change_speed(y as f32),
+                Event::MouseWheel { y, .. } if y != 0 =>
+                {
+                    zoom_camera(&mut scene.write().expect("Scene lock was poisoned"), &controller, y as f32);
+                    view_changed = true;
+                }
+                _ => {}
+            }
+        }
+        
+        // Update any other logic
+        let delta_time: f64 = APPLICATION_TIME.lock().unwrap().frame_delta_time;
+        // Held keys turn the camera a little every frame
+        if controller.is_turning()
+        {
+            let r4: Rotor4 = controller.key_rotation4(delta_time as f32).unwrap_or(Rotor4::IDENTITY);
+            let r3: Rotor3 = controller.key_rotation3(delta_time as f32).unwrap_or(Rotor3::IDENTITY);
+            turn_camera(&mut scene.write().expect("Scene lock was poisoned"), controller.mode, r4, r3);
+            view_changed = true;
+        }
+        view_changed |= fly_camera(&mut scene.write().expect("Scene lock was poisoned"), &controller, delta_time as f32);
+        if !paused
+        {
+            view_changed |= update(delta_time, &mut scene.write().expect("Scene lock was poisoned"), &controller)?;
+        }
+        // The view has changed so any refinement so far is out of date
+        if view_changed && let Some(progressive) = progressive.as_mut()
+        {
+            progressive.reset();
+        }
+        
+        // Render
+        let render_application: Application = resolution.map_or(application, |resolution| resolution.render_application(&application));
+        canvas.clear();
+        render(&mut canvas, &mut frame_texture, &pool, &scene, &render_application, &framebuffer, progressive.as_mut())?;
+        
+        // Render FPS Text
+        fps_text = format!("{:.1}fps {:.6}s", 1.0f64 / delta_time, delta_time);
+        if resolution.is_some()
+        {
+            fps_text = format!("{} {}x{}", fps_text, render_application.width, render_application.height);
+        }
+        if let Some(slice) = scene.read().expect("Scene lock was poisoned").scene_4d.as_ref().and_then(|scene_4d| scene_4d.slice)
+        {
+            fps_text = format!("{} slice {:.2}", fps_text, slice.offset);
+        }
+        if let Some(scene_4d) = scene.read().expect("Scene lock was poisoned").scene_4d.as_ref()
+        {
+            if !scene_4d.views.is_empty()
+            {
+                fps_text = format!("{} {} views", fps_text, scene_4d.views.len());
+            }
+            if let Some(tint) = scene_4d.w_tint
+            {
+                fps_text = format!("{} {} tint", fps_text, tint.measure.name());
+            }
+            if scene_4d.ghosting.is_some()
+            {
+                fps_text = format!("{} ghosts", fps_text);
+            }
+            if let Some(volume) = scene_4d.volume
+            {
+                fps_text = format!("{} volume along {}", fps_text, volume.axis.name());
+            }
+        }
+        if controller.mode == CameraMode::FreeFly
+        {
+            fps_text = format!("{} free fly {:.1}/s", fps_text, controller.move_speed);
+        }
+        let render_mode: RenderMode = scene.read().expect("Scene lock was poisoned").render_mode;
+        if render_mode != RenderMode::Shaded
+        {
+            fps_text = format!("{} {}", fps_text, render_mode.name());
+        }
+        if let Some(progressive) = &progressive
+        {
+            fps_text = format!("{} {}/{} samples", fps_text, progressive.samples(), progressive.max_samples);
+        }
+        fps_text_surface = font.render(&fps_text).blended(Color::RGBA(255, 255, 255, 255)).unwrap();
+        let width: u32 = fps_text_surface.width();
+        let height: u32 = fps_text_surface.height();
+        fps_text_texture = texture_creator.create_texture_from_surface(fps_text_surface).unwrap();
+        fps_text_rect = Rect::new(application.width as i32 - width as i32,
+                                  application.height as i32 - height as i32,
+                                  width,
+                                  height);
+        canvas.copy(&fps_text_texture, None, Some(fps_text_rect))?;
+        
+        // Present full image
+        canvas.present();
+
+        // A finished image has nothing left to refine, wait for input rather than spinning
+        if progressive.as_ref().is_some_and(|progressive| progressive.is_finished())
+        {
+            thread::sleep(Duration::from_millis(10));
+        }
+        
+        // Update time
+        let mut time = APPLICATION_TIME.lock().unwrap();
+        time.application_up_time = applciation_start_time.elapsed().as_secs_f64();
+        time.frame_delta_time = frame_start_time.elapsed().as_secs_f64();
+        if let Some(resolution) = resolution.as_mut()
+        {
+            resolution.update(time.frame_delta_time);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,154 @@
+//! A persistent pool of render threads that share a queue of tiles.
+//!
+//! The threads are created once and wait for work between frames. Each frame the image is split
+//! into tiles and every worker pulls the next unrendered tile from a shared counter, so threads
+//! that land on cheap tiles simply take more of them.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+/// Width and height of a tile in pixels
+pub const TILE_SIZE: u32 = 16;
+
+/// A rectangle of pixels, y counts up from the bottom of the image like the renderer's uvs
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Tile
+{
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Tile
+{
+    /// Splits an image into tiles of at most tile_size pixels, ordered row by row
+    pub fn split(width: u32, height: u32, tile_size: u32) -> Vec<Tile>
+    {
+        let mut tiles: Vec<Tile> = Vec::new();
+        for y in (0..height).step_by(tile_size as usize)
+        {
+            for x in (0..width).step_by(tile_size as usize)
+            {
+                tiles.push(Tile{ x: x, y: y, width: u32::min(tile_size, width - x), height: u32::min(tile_size, height - y) });
+            }
+        }
+        return tiles;
+    }
+}
+
+/// Work sent to every worker once per frame, it runs until the frame's tiles are used up
+type Task = Arc<dyn Fn() + Send + Sync>;
+
+pub struct WorkerPool
+{
+    senders: Vec<Sender<Task>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl WorkerPool
+{
+    /// Creates a pool with one worker per thread, threads of 0 uses every available core
+    pub fn new(threads: usize) -> WorkerPool
+    {
+        let threads: usize = if threads == 0 { WorkerPool::available_threads() } else { threads };
+
+        let mut senders: Vec<Sender<Task>> = Vec::with_capacity(threads);
+        let mut workers: Vec<JoinHandle<()>> = Vec::with_capacity(threads);
+        for i in 0..threads
+        {
+            let (sender, receiver): (Sender<Task>, Receiver<Task>) = mpsc::channel();
+            senders.push(sender);
+            workers.push(
+                thread::Builder::new()
+                    .name(format!("render worker {}", i))
+                    .spawn(move || {
+                        // Runs until the pool is dropped and the channel closes
+                        for task in receiver
+                        {
+                            task();
+                        }
+                    })
+                    .expect("Unable to spawn render worker")
+            );
+        }
+
+        return WorkerPool{ senders: senders, workers: workers };
+    }
+
+    pub fn available_threads() -> usize
+    {
+        return thread::available_parallelism().map_or(1, |threads| threads.get());
+    }
+
+    pub fn thread_count(&self) -> usize
+    {
+        return self.workers.len();
+    }
+
+    /// Runs job on every tile across the pool and waits for every worker to finish with it
+    /// Results are returned with their tile in the order they completed
+    pub fn run_tiles<T, F>(&self, tiles: Vec<Tile>, job: F) -> Vec<(Tile, T)>
+    where
+        T: Send + 'static,
+        F: Fn(&Tile) -> T + Send + Sync + 'static,
+    {
+        let tile_count: usize = tiles.len();
+        let tiles: Arc<Vec<Tile>> = Arc::new(tiles);
+        let next_tile: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+        let (result_sender, result_receiver) = mpsc::channel::<(Tile, T)>();
+
+        // Tuples drop in order, so the job and everything it captured is released before the
+        // sender that tells run_tiles the frame is finished
+        let work: (F, Sender<(Tile, T)>) = (job, result_sender);
+        let task: Task = Arc::new(move || {
+            // Borrowing the whole tuple makes the closure capture it as one value
+            let work: &(F, Sender<(Tile, T)>) = &work;
+            let (job, result_sender) = work;
+            loop
+            {
+                let index: usize = next_tile.fetch_add(1, Ordering::Relaxed);
+                if index >= tiles.len()
+                {
+                    break;
+                }
+                let tile: Tile = tiles[index];
+                if result_sender.send((tile, job(&tile))).is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        for sender in &self.senders
+        {
+            // A worker that panicked has closed its channel, the others pick up its tiles
+            let _ = sender.send(task.clone());
+        }
+        drop(task);
+
+        // The result channel closes once every worker has let go of the task, so nothing the job
+        // captured is still shared when this returns
+        let results: Vec<(Tile, T)> = result_receiver.iter().collect();
+        assert_eq!(results.len(), tile_count, "A render worker stopped before the frame was finished");
+        return results;
+    }
+}
+
+impl Drop for WorkerPool
+{
+    fn drop(&mut self)
+    {
+        self.senders.clear();
+        for worker in self.workers.drain(..)
+        {
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "worker_pool_tests.rs"]
+mod tests;
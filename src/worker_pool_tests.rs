@@ -0,0 +1,51 @@
+use std::collections::HashSet;
+use std::thread;
+
+use crate::worker_pool::{Tile, WorkerPool};
+
+#[test]
+fn tiles_cover_the_image_once()
+{
+    let tiles: Vec<Tile> = Tile::split(50, 20, 16);
+    assert_eq!(tiles.len(), 4 * 2);
+    assert_eq!(tiles[3], Tile{ x: 48, y: 0, width: 2, height: 16 });
+    assert_eq!(tiles[7], Tile{ x: 48, y: 16, width: 2, height: 4 });
+
+    let area: u32 = tiles.iter().map(|tile| tile.width * tile.height).sum();
+    assert_eq!(area, 50 * 20);
+}
+
+#[test]
+fn every_tile_is_run_once()
+{
+    let pool: WorkerPool = WorkerPool::new(4);
+    assert_eq!(pool.thread_count(), 4);
+
+    // The same pool is reused across frames
+    for _frame in 0..3
+    {
+        let tiles: Vec<Tile> = Tile::split(100, 100, 16);
+        let results = pool.run_tiles(tiles.clone(), |tile| tile.x + tile.y * 1000);
+        assert_eq!(results.len(), tiles.len());
+
+        let seen: HashSet<u32> = results.iter().map(|(tile, value)| {
+            assert_eq!(*value, tile.x + tile.y * 1000);
+            return *value;
+        }).collect();
+        assert_eq!(seen.len(), tiles.len());
+    }
+}
+
+#[test]
+fn work_is_shared_between_threads()
+{
+    let pool: WorkerPool = WorkerPool::new(4);
+    let results = pool.run_tiles(Tile::split(64, 64, 4), |_tile| {
+        thread::sleep(std::time::Duration::from_millis(1));
+        return thread::current().name().map(String::from);
+    });
+
+    let threads: HashSet<Option<String>> = results.into_iter().map(|(_, name)| name).collect();
+    assert!(threads.len() > 1);
+    assert!(threads.iter().all(|name| name.as_deref().is_some_and(|name| name.starts_with("render worker"))));
+}